@@ -240,6 +240,7 @@ fn testnet_genesis(
 			safe_xcm_version: Some(SAFE_XCM_VERSION),
 		},
 		transaction_payment: Default::default(),
+		identity: Default::default(),
 		council: Default::default(),
 		treasury: Default::default(),
 		tokens: Default::default(),
@@ -481,6 +482,7 @@ fn subzero_genesis(
 			safe_xcm_version: Some(SAFE_XCM_VERSION),
 		},
 		transaction_payment: Default::default(),
+		identity: Default::default(),
 		council: Default::default(),
 		treasury: Default::default(),
 		tokens: orml_tokens::GenesisConfig {
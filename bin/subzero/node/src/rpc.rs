@@ -7,7 +7,7 @@
 
 use std::sync::Arc;
 
-use subzero_runtime::{opaque::Block, AccountId, Balance, Index as Nonce};
+use subzero_runtime::{opaque::Block, AccountId, Balance, CurrencyId, Index as Nonce};
 
 use sc_client_api::AuxStore;
 pub use sc_rpc::{DenyUnsafe, SubscriptionTaskExecutor};
@@ -15,6 +15,7 @@ use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
+use xcm::VersionedMultiLocation;
 
 /// A type representing all RPC extensions.
 pub type RpcExtension = jsonrpsee::RpcModule<()>;
@@ -42,10 +43,21 @@ where
 		+ Sync
 		+ 'static,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: module_tx_payment_rpc::TxPaymentRuntimeApi<Block, Balance, CurrencyId>,
+	C::Api: module_asset_registry_rpc::AssetRegistryRuntimeApi<
+		Block,
+		CurrencyId,
+		Balance,
+		VersionedMultiLocation,
+	>,
+	C::Api: pallet_identity_rpc::IdentityRuntimeApi<Block, AccountId>,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + Sync + Send + 'static,
 {
+	use module_asset_registry_rpc::{AssetRegistry, AssetRegistryApiServer};
+	use module_tx_payment_rpc::{TxPayment, TxPaymentApiServer};
+	use pallet_identity_rpc::{Identity, IdentityApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
 
@@ -53,6 +65,9 @@ where
 	let FullDeps { client, pool, deny_unsafe } = deps;
 
 	module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
-	module.merge(TransactionPayment::new(client).into_rpc())?;
+	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	module.merge(TxPayment::new(client.clone()).into_rpc())?;
+	module.merge(AssetRegistry::new(client.clone()).into_rpc())?;
+	module.merge(Identity::new(client).into_rpc())?;
 	Ok(module)
 }
@@ -0,0 +1,48 @@
+;; Calls a chain extension with the input of the contract.
+;;
+;; The input is the `u32` id of the chain extension function (the extension id in the upper and
+;; the function id in the lower 16 bits) followed by the SCALE encoded arguments. The contract
+;; returns the `u32` status code of the call followed by the output of the function.
+(module
+	(import "seal0" "seal_call_chain_extension"
+		(func $seal_call_chain_extension (param i32 i32 i32 i32 i32) (result i32))
+	)
+	(import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+	(import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
+	(import "env" "memory" (memory 16 16))
+
+	;; [0, 4) length of the input buffer
+	(data (i32.const 0) "\00\04")
+
+	;; [4, 1028) input buffer, the function id followed by the arguments
+
+	;; [2048, 2052) length of the output buffer
+	(data (i32.const 2048) "\00\04")
+
+	;; [2052, 2056) status code of the call
+
+	;; [2056, 3080) output buffer
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		(call $seal_input (i32.const 4) (i32.const 0))
+
+		(i32.store
+			(i32.const 2052)
+			(call $seal_call_chain_extension
+				(i32.load (i32.const 4))							;; id
+				(i32.const 8)										;; input_ptr
+				(i32.sub (i32.load (i32.const 0)) (i32.const 4))	;; input_len
+				(i32.const 2056)									;; output_ptr
+				(i32.const 2048)									;; output_len_ptr
+			)
+		)
+
+		(call $seal_return
+			(i32.const 0)
+			(i32.const 2052)
+			(i32.add (i32.load (i32.const 2048)) (i32.const 4))
+		)
+	)
+)
@@ -0,0 +1,231 @@
+//! Balances of `Currencies` and metadata of `AssetRegistry` for ink! contracts.
+//!
+//! Transfers, reserves and unreserves always act on the account of the calling contract.
+
+use super::{charge_call, write_output, ContractsSchedule};
+use crate::{
+	xcm_config::AssetRegistryMapping, AccountId, Balance, Balances, Currencies, CurrencyId,
+	GetNativeCurrencyId, Runtime,
+};
+use codec::{Decode, Encode};
+use frame_support::{
+	dispatch::{DispatchError, DispatchResult},
+	traits::{Currency, ExistenceRequirement, Get},
+	weights::Weight,
+};
+use orml_currencies::WeightInfo as _;
+use orml_traits::{MultiCurrency, MultiReservableCurrency};
+use pallet_balances::WeightInfo as _;
+use pallet_contracts::chain_extension::{
+	ChainExtension, Environment, Ext, InitState, RegisteredChainExtension, RetVal,
+};
+use primitives::currency::{AssetIdMapping, AssetIds};
+use scale_info::TypeInfo;
+use sp_runtime::{RuntimeDebug, TokenError};
+
+type TokensError = orml_tokens::Error<Runtime>;
+type CurrenciesPalletError = orml_currencies::Error<Runtime>;
+type BalancesError = pallet_balances::Error<Runtime>;
+type CurrenciesWeight = <Runtime as orml_currencies::Config>::WeightInfo;
+type BalancesWeight = <Runtime as pallet_balances::Config>::WeightInfo;
+
+/// Functions of `CurrenciesExtension`. Deployed contracts depend on these ids, they must never
+/// change.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+enum Func {
+	/// `(CurrencyId, AccountId) -> Balance`, the free balance of an account.
+	BalanceOf = 0,
+	/// `CurrencyId -> Balance`, the total issuance of a currency.
+	TotalIssuance = 1,
+	/// `(CurrencyId, AccountId, Balance) -> ()`, transfers from the contract.
+	Transfer = 2,
+	/// `(CurrencyId, Balance) -> ()`, reserves on the contract.
+	Reserve = 3,
+	/// `(CurrencyId, Balance) -> Balance`, unreserves on the contract and returns the amount
+	/// which could not be unreserved.
+	Unreserve = 4,
+	/// `CurrencyId -> Option<AssetMetadata>`, the metadata of a token or a registered foreign
+	/// asset.
+	Metadata = 5,
+}
+
+impl TryFrom<u16> for Func {
+	type Error = DispatchError;
+
+	fn try_from(func_id: u16) -> Result<Self, Self::Error> {
+		Ok(match func_id {
+			0 => Func::BalanceOf,
+			1 => Func::TotalIssuance,
+			2 => Func::Transfer,
+			3 => Func::Reserve,
+			4 => Func::Unreserve,
+			5 => Func::Metadata,
+			_ => return Err(DispatchError::Other("unknown function of the currencies extension")),
+		})
+	}
+}
+
+/// Status codes of failed transfers and reserves, `0` is returned on success.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo)]
+#[repr(u32)]
+pub enum CurrenciesError {
+	/// The free balance is too low.
+	BalanceTooLow = 1,
+	/// The account would end up with less than the existential deposit.
+	BelowMinimum = 2,
+	/// The transfer would kill the account of the contract.
+	KeepAlive = 3,
+	/// The balance is locked.
+	Frozen = 4,
+	/// A balance or the issuance would overflow.
+	Overflow = 5,
+	/// Any other error.
+	Other = 255,
+}
+
+impl From<DispatchError> for CurrenciesError {
+	fn from(error: DispatchError) -> Self {
+		let is_any = |errors: &[DispatchError]| errors.contains(&error);
+		match error {
+			DispatchError::Token(TokenError::BelowMinimum) => Self::BelowMinimum,
+			DispatchError::Token(TokenError::Frozen) => Self::Frozen,
+			DispatchError::Arithmetic(_) => Self::Overflow,
+			_ if is_any(&[
+				TokensError::BalanceTooLow.into(),
+				CurrenciesPalletError::BalanceTooLow.into(),
+				BalancesError::InsufficientBalance.into(),
+			]) =>
+				Self::BalanceTooLow,
+			_ if is_any(&[
+				TokensError::ExistentialDeposit.into(),
+				BalancesError::ExistentialDeposit.into(),
+			]) =>
+				Self::BelowMinimum,
+			_ if is_any(&[TokensError::KeepAlive.into(), BalancesError::KeepAlive.into()]) =>
+				Self::KeepAlive,
+			_ if is_any(&[
+				TokensError::LiquidityRestrictions.into(),
+				BalancesError::LiquidityRestrictions.into(),
+			]) =>
+				Self::Frozen,
+			_ => Self::Other,
+		}
+	}
+}
+
+/// Exposes `Currencies` and `AssetRegistry` to contracts, see `Func` for the functions.
+#[derive(Default)]
+pub struct CurrenciesExtension;
+
+impl RegisteredChainExtension<Runtime> for CurrenciesExtension {
+	const ID: u16 = 1;
+}
+
+impl ChainExtension<Runtime> for CurrenciesExtension {
+	fn call<E: Ext<T = Runtime>>(
+		&mut self,
+		env: Environment<E, InitState>,
+	) -> Result<RetVal, DispatchError> {
+		let func = Func::try_from(env.func_id())?;
+		let mut env = env.buf_in_buf_out();
+		charge_call(&mut env)?;
+		let host_fn_weights = ContractsSchedule::get().host_fn_weights;
+
+		let output = match func {
+			Func::BalanceOf => {
+				env.charge_weight(host_fn_weights.balance)?;
+				let (currency_id, who): (CurrencyId, AccountId) = env.read_as()?;
+				<Currencies as MultiCurrency<AccountId>>::free_balance(currency_id, &who).encode()
+			},
+			Func::TotalIssuance => {
+				env.charge_weight(host_fn_weights.balance)?;
+				let currency_id: CurrencyId = env.read_as()?;
+				<Currencies as MultiCurrency<AccountId>>::total_issuance(currency_id).encode()
+			},
+			Func::Transfer => {
+				let (currency_id, to, amount): (CurrencyId, AccountId, Balance) = env.read_as()?;
+				env.charge_weight(transfer_weight(currency_id))?;
+				let from = env.ext().address().clone();
+				return Ok(ret_val(transfer(currency_id, &from, &to, amount)))
+			},
+			Func::Reserve => {
+				let (currency_id, amount): (CurrencyId, Balance) = env.read_as()?;
+				env.charge_weight(reserve_weight(currency_id))?;
+				let who = env.ext().address().clone();
+				return Ok(ret_val(<Currencies as MultiReservableCurrency<AccountId>>::reserve(
+					currency_id,
+					&who,
+					amount,
+				)))
+			},
+			Func::Unreserve => {
+				let (currency_id, amount): (CurrencyId, Balance) = env.read_as()?;
+				env.charge_weight(reserve_weight(currency_id))?;
+				let who = env.ext().address().clone();
+				<Currencies as MultiReservableCurrency<AccountId>>::unreserve(
+					currency_id,
+					&who,
+					amount,
+				)
+				.encode()
+			},
+			Func::Metadata => {
+				env.charge_weight(host_fn_weights.get_storage)?;
+				let currency_id: CurrencyId = env.read_as()?;
+				let metadata =
+					AssetRegistryMapping::get_asset_metadata(AssetIds::NativeAssetId(currency_id))
+						.encode();
+				env.charge_weight(
+					host_fn_weights.get_storage_per_byte.saturating_mul(metadata.len() as u64),
+				)?;
+				metadata
+			},
+		};
+		write_output(&mut env, &output)?;
+		Ok(RetVal::Converging(0))
+	}
+}
+
+/// Native transfers keep the account of the contract alive, as transfers of `pallet_contracts`
+/// do. Transfers of other currencies go through `Currencies::transfer` and may take the balance of
+/// the contract below the existential deposit of the currency, which removes it.
+fn transfer(
+	currency_id: CurrencyId,
+	from: &AccountId,
+	to: &AccountId,
+	amount: Balance,
+) -> DispatchResult {
+	if currency_id == GetNativeCurrencyId::get() {
+		<Balances as Currency<AccountId>>::transfer(
+			from,
+			to,
+			amount,
+			ExistenceRequirement::KeepAlive,
+		)
+	} else {
+		<Currencies as MultiCurrency<AccountId>>::transfer(currency_id, from, to, amount)
+	}
+}
+
+fn transfer_weight(currency_id: CurrencyId) -> Weight {
+	if currency_id == GetNativeCurrencyId::get() {
+		CurrenciesWeight::transfer_native_currency()
+	} else {
+		CurrenciesWeight::transfer_non_native_currency()
+	}
+}
+
+/// Reserves and unreserves of the native currency cost as much as `force_unreserve` of
+/// `pallet_balances`. Those of other currencies are bounded by `update_balance` of
+/// `orml_currencies`, which also reads and writes the account of a token.
+fn reserve_weight(currency_id: CurrencyId) -> Weight {
+	if currency_id == GetNativeCurrencyId::get() {
+		BalancesWeight::force_unreserve()
+	} else {
+		CurrenciesWeight::update_balance_non_native_currency()
+	}
+}
+
+fn ret_val(result: DispatchResult) -> RetVal {
+	RetVal::Converging(result.map_or_else(|error| CurrenciesError::from(error) as u32, |()| 0))
+}
@@ -0,0 +1,46 @@
+//! Chain extensions giving ink! contracts access to the runtime, their ABI is documented in
+//! `README.md`.
+
+use crate::Runtime;
+use frame_support::{
+	dispatch::{DispatchError, DispatchResult},
+	traits::Get,
+};
+use pallet_contracts::chain_extension::{BufInBufOutState, Environment, Ext};
+
+mod currencies;
+mod nfts;
+mod randomness;
+
+pub use currencies::{CurrenciesError, CurrenciesExtension};
+pub use nfts::{NftsError, NftsExtension};
+pub use randomness::{RandomnessError, RandomnessExtension};
+
+/// Dispatches every call to the extension whose `RegisteredChainExtension::ID` equals the upper
+/// 16 bits of the id passed by the contract, calls of unknown extensions trap the contract.
+pub type RuntimeExtensions = (CurrenciesExtension, NftsExtension, RandomnessExtension);
+
+type ContractsSchedule = <Runtime as pallet_contracts::Config>::Schedule;
+
+/// Charges the overhead of `seal_call_chain_extension`, which `pallet_contracts` leaves to the
+/// extensions and the weights of the functions do not cover: the host call and the copy of the
+/// input into the runtime, priced like `seal_input`.
+fn charge_call<E: Ext<T = Runtime>>(
+	env: &mut Environment<E, BufInBufOutState>,
+) -> Result<(), DispatchError> {
+	let weights = ContractsSchedule::get().host_fn_weights;
+	let input = weights.input_per_byte.saturating_mul(env.in_len().into());
+	env.charge_weight(weights.input.saturating_add(input))?;
+	Ok(())
+}
+
+/// Writes the output of a function into the contract, each byte priced like one of `seal_return`.
+fn write_output<E: Ext<T = Runtime>>(
+	env: &mut Environment<E, BufInBufOutState>,
+	output: &[u8],
+) -> DispatchResult {
+	env.write(output, false, Some(ContractsSchedule::get().host_fn_weights.return_per_byte))
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,204 @@
+//! Game items of `Nfts` for ink! contracts.
+//!
+//! Mints, transfers and attribute changes are dispatched with the calling contract as signed
+//! origin, so `Nfts` checks that the contract holds the Issuer role to mint, the Admin role to set
+//! attributes and owns the items it transfers. Burns check that the contract owns the item.
+
+use super::{charge_call, write_output, ContractsSchedule};
+use crate::{AccountId, KeyLimit, Nfts, Runtime, RuntimeOrigin, ValueLimit};
+use codec::{Decode, Encode};
+use frame_support::{
+	dispatch::{DispatchError, DispatchResult},
+	traits::{
+		tokens::nonfungibles_v2::{Inspect, Mutate},
+		Get,
+	},
+	BoundedVec,
+};
+use pallet_contracts::chain_extension::{
+	ChainExtension, Environment, Ext, InitState, RegisteredChainExtension, RetVal,
+};
+use pallet_nfts::{AttributeNamespace, ItemConfig, ItemSettings, WeightInfo};
+use scale_info::TypeInfo;
+use sp_runtime::{MultiAddress, RuntimeDebug};
+
+type CollectionId = <Runtime as pallet_nfts::Config>::CollectionId;
+type ItemId = <Runtime as pallet_nfts::Config>::ItemId;
+type PalletError = pallet_nfts::Error<Runtime>;
+type BalancesError = pallet_balances::Error<Runtime>;
+type NftsWeight = <Runtime as pallet_nfts::Config>::WeightInfo;
+
+/// Functions of `NftsExtension`. Deployed contracts depend on these ids, they must never change.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+enum Func {
+	/// `(CollectionId, ItemId) -> Option<AccountId>`, the owner of an item.
+	Owner = 0,
+	/// `(CollectionId, ItemId, Key) -> Option<Vec<u8>>`, an attribute of an item set by the
+	/// collection admin.
+	Attribute = 1,
+	/// `(CollectionId, ItemId, AccountId) -> ()`, mints an item, the contract needs the Issuer
+	/// role.
+	Mint = 2,
+	/// `(CollectionId, ItemId) -> ()`, burns an item owned by the contract.
+	Burn = 3,
+	/// `(CollectionId, ItemId, AccountId) -> ()`, transfers an item owned by the contract or
+	/// approved to it.
+	Transfer = 4,
+	/// `(CollectionId, ItemId, Key, Value) -> ()`, sets an attribute of an item, the contract
+	/// needs the Admin role.
+	SetAttribute = 5,
+}
+
+impl TryFrom<u16> for Func {
+	type Error = DispatchError;
+
+	fn try_from(func_id: u16) -> Result<Self, Self::Error> {
+		Ok(match func_id {
+			0 => Func::Owner,
+			1 => Func::Attribute,
+			2 => Func::Mint,
+			3 => Func::Burn,
+			4 => Func::Transfer,
+			5 => Func::SetAttribute,
+			_ => return Err(DispatchError::Other("unknown function of the nfts extension")),
+		})
+	}
+}
+
+/// Status codes of failed mints, burns, transfers and attribute changes, `0` is returned on
+/// success.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo)]
+#[repr(u32)]
+pub enum NftsError {
+	/// The contract lacks the collection role or does not own the item.
+	NoPermission = 1,
+	/// The collection does not exist.
+	UnknownCollection = 2,
+	/// The item does not exist.
+	UnknownItem = 3,
+	/// The item already exists.
+	AlreadyExists = 4,
+	/// The item or its attributes are locked.
+	Locked = 5,
+	/// The collection reached its max supply.
+	MaxSupplyReached = 6,
+	/// The contract cannot pay the deposit.
+	BalanceTooLow = 7,
+	/// Any other error.
+	Other = 255,
+}
+
+impl From<DispatchError> for NftsError {
+	fn from(error: DispatchError) -> Self {
+		let is_any = |errors: &[DispatchError]| errors.contains(&error);
+		if is_any(&[PalletError::NoPermission.into(), PalletError::WrongOwner.into()]) {
+			Self::NoPermission
+		} else if is_any(&[PalletError::UnknownCollection.into()]) {
+			Self::UnknownCollection
+		} else if is_any(&[PalletError::UnknownItem.into()]) {
+			Self::UnknownItem
+		} else if is_any(&[PalletError::AlreadyExists.into()]) {
+			Self::AlreadyExists
+		} else if is_any(&[
+			PalletError::ItemLocked.into(),
+			PalletError::ItemsNonTransferable.into(),
+			PalletError::LockedItemAttributes.into(),
+			PalletError::LockedCollectionAttributes.into(),
+		]) {
+			Self::Locked
+		} else if is_any(&[PalletError::MaxSupplyReached.into()]) {
+			Self::MaxSupplyReached
+		} else if is_any(&[BalancesError::InsufficientBalance.into()]) {
+			Self::BalanceTooLow
+		} else {
+			Self::Other
+		}
+	}
+}
+
+/// Exposes `Nfts` to contracts, see `Func` for the functions.
+#[derive(Default)]
+pub struct NftsExtension;
+
+impl RegisteredChainExtension<Runtime> for NftsExtension {
+	const ID: u16 = 2;
+}
+
+impl ChainExtension<Runtime> for NftsExtension {
+	fn call<E: Ext<T = Runtime>>(
+		&mut self,
+		env: Environment<E, InitState>,
+	) -> Result<RetVal, DispatchError> {
+		let func = Func::try_from(env.func_id())?;
+		let mut env = env.buf_in_buf_out();
+		charge_call(&mut env)?;
+		let host_fn_weights = ContractsSchedule::get().host_fn_weights;
+		let contract = env.ext().address().clone();
+		let origin = RuntimeOrigin::signed(contract.clone());
+
+		let result = match func {
+			Func::Owner => {
+				env.charge_weight(host_fn_weights.get_storage)?;
+				let (collection, item): (CollectionId, ItemId) = env.read_as()?;
+				let owner = <Nfts as Inspect<AccountId>>::owner(&collection, &item);
+				write_output(&mut env, &owner.encode())?;
+				Ok(())
+			},
+			Func::Attribute => {
+				env.charge_weight(host_fn_weights.get_storage)?;
+				let (collection, item, key): (CollectionId, ItemId, BoundedVec<u8, KeyLimit>) =
+					env.read_as()?;
+				let value =
+					<Nfts as Inspect<AccountId>>::attribute(&collection, &item, &key).encode();
+				env.charge_weight(
+					host_fn_weights.get_storage_per_byte.saturating_mul(value.len() as u64),
+				)?;
+				write_output(&mut env, &value)?;
+				Ok(())
+			},
+			Func::Mint => {
+				env.charge_weight(NftsWeight::force_mint())?;
+				let (collection, item, to): (CollectionId, ItemId, AccountId) = env.read_as()?;
+				Nfts::force_mint(
+					origin,
+					collection,
+					item,
+					MultiAddress::Id(to),
+					ItemConfig { settings: ItemSettings::all_enabled() },
+				)
+			},
+			Func::Burn => {
+				env.charge_weight(NftsWeight::burn())?;
+				let (collection, item): (CollectionId, ItemId) = env.read_as()?;
+				<Nfts as Mutate<AccountId, ItemConfig>>::burn(&collection, &item, Some(&contract))
+			},
+			Func::Transfer => {
+				env.charge_weight(NftsWeight::transfer())?;
+				let (collection, item, to): (CollectionId, ItemId, AccountId) = env.read_as()?;
+				Nfts::transfer(origin, collection, item, MultiAddress::Id(to))
+			},
+			Func::SetAttribute => {
+				env.charge_weight(NftsWeight::set_attribute())?;
+				let (collection, item, key, value): (
+					CollectionId,
+					ItemId,
+					BoundedVec<u8, KeyLimit>,
+					BoundedVec<u8, ValueLimit>,
+				) = env.read_as()?;
+				Nfts::set_attribute(
+					origin,
+					collection,
+					Some(item),
+					AttributeNamespace::CollectionOwner,
+					key,
+					value,
+				)
+			},
+		};
+		Ok(ret_val(result))
+	}
+}
+
+fn ret_val(result: DispatchResult) -> RetVal {
+	RetVal::Converging(result.map_or_else(|error| NftsError::from(error) as u32, |()| 0))
+}
@@ -0,0 +1,124 @@
+//! Randomness requests of `RelayRandomness` for ink! contracts.
+//!
+//! The calling contract owns its requests. `seal_random` gives the randomness of the last block,
+//! which the collator of the current block knows, requests commit to the randomness of a future
+//! block instead.
+
+use super::{charge_call, write_output};
+use crate::{Hash, RelayRandomness, Runtime};
+use codec::{Decode, Encode};
+use frame_support::dispatch::DispatchError;
+use module_relay_randomness::{RequestId, WeightInfo};
+use pallet_contracts::chain_extension::{
+	ChainExtension, Environment, Ext, InitState, RegisteredChainExtension, RetVal,
+};
+use scale_info::TypeInfo;
+use sp_core::H256;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+type PalletError = module_relay_randomness::Error<Runtime>;
+type BalancesError = pallet_balances::Error<Runtime>;
+type RandomnessWeight = <Runtime as module_relay_randomness::Config>::WeightInfo;
+
+/// Functions of `RandomnessExtension`. Deployed contracts depend on these ids, they must never
+/// change.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+enum Func {
+	/// `H256 -> RequestId`, requests the randomness of a future block mixed with a salt and
+	/// reserves the request deposit on the contract.
+	Request = 0,
+	/// `RequestId -> Hash`, reveals the randomness of a request and removes the request.
+	Reveal = 1,
+	/// `RequestId -> ()`, removes a request without revealing it.
+	Cancel = 2,
+}
+
+impl TryFrom<u16> for Func {
+	type Error = DispatchError;
+
+	fn try_from(func_id: u16) -> Result<Self, Self::Error> {
+		Ok(match func_id {
+			0 => Func::Request,
+			1 => Func::Reveal,
+			2 => Func::Cancel,
+			_ => return Err(DispatchError::Other("unknown function of the randomness extension")),
+		})
+	}
+}
+
+/// Status codes of failed requests, reveals and cancels, `0` is returned on success.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo)]
+#[repr(u32)]
+pub enum RandomnessError {
+	/// The request does not exist.
+	UnknownRequest = 1,
+	/// The request belongs to another account.
+	NotOwner = 2,
+	/// The block of the request has not been built yet.
+	TooEarly = 3,
+	/// The block of the request had no relay randomness, the request can only be cancelled.
+	Unavailable = 4,
+	/// The free balance is too low for the request deposit.
+	BalanceTooLow = 5,
+	/// Any other error.
+	Other = 255,
+}
+
+impl From<DispatchError> for RandomnessError {
+	fn from(error: DispatchError) -> Self {
+		match error {
+			_ if error == PalletError::UnknownRequest.into() => Self::UnknownRequest,
+			_ if error == PalletError::NotOwner.into() => Self::NotOwner,
+			_ if error == PalletError::TooEarly.into() => Self::TooEarly,
+			_ if error == PalletError::RandomnessUnavailable.into() => Self::Unavailable,
+			_ if error == BalancesError::InsufficientBalance.into() => Self::BalanceTooLow,
+			_ => Self::Other,
+		}
+	}
+}
+
+/// Exposes the requests of `RelayRandomness` to contracts, see `Func` for the functions.
+#[derive(Default)]
+pub struct RandomnessExtension;
+
+impl RegisteredChainExtension<Runtime> for RandomnessExtension {
+	const ID: u16 = 3;
+}
+
+impl ChainExtension<Runtime> for RandomnessExtension {
+	fn call<E: Ext<T = Runtime>>(
+		&mut self,
+		env: Environment<E, InitState>,
+	) -> Result<RetVal, DispatchError> {
+		let func = Func::try_from(env.func_id())?;
+		let mut env = env.buf_in_buf_out();
+		charge_call(&mut env)?;
+		let contract = env.ext().address().clone();
+
+		let result = match func {
+			Func::Request => {
+				env.charge_weight(RandomnessWeight::request_randomness())?;
+				let salt: H256 = env.read_as()?;
+				RelayRandomness::request(contract, salt).map(|id| id.encode())
+			},
+			Func::Reveal => {
+				env.charge_weight(RandomnessWeight::reveal_randomness())?;
+				let id: RequestId = env.read_as()?;
+				RelayRandomness::reveal(&contract, id).map(|randomness: Hash| randomness.encode())
+			},
+			Func::Cancel => {
+				env.charge_weight(RandomnessWeight::cancel_request())?;
+				let id: RequestId = env.read_as()?;
+				RelayRandomness::cancel(&contract, id).map(|()| Vec::new())
+			},
+		};
+		match result {
+			Ok(output) => {
+				write_output(&mut env, &output)?;
+				Ok(RetVal::Converging(0))
+			},
+			Err(error) => Ok(RetVal::Converging(RandomnessError::from(error) as u32)),
+		}
+	}
+}
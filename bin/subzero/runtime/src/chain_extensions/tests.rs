@@ -0,0 +1,298 @@
+// Tests of the chain extensions, calling them from the `chain_extension.wat` fixture contract.
+
+use super::{
+	CurrenciesError, CurrenciesExtension, NftsError, NftsExtension, RandomnessError,
+	RandomnessExtension,
+};
+use crate::{
+	dollar, AccountId, Balance, Balances, Contracts, Currencies, CurrencyId, ForeignAssetId, Nfts,
+	RandomnessRequestDeposit, RelayRandomness, Runtime, RuntimeBlockWeights, RuntimeOrigin, System,
+	PLAY, ZERO,
+};
+use codec::{Decode, Encode};
+use frame_support::traits::{tokens::nonfungibles_v2::Inspect, Currency, GenesisBuild};
+use module_relay_randomness::RequestId;
+use orml_traits::{MultiCurrency, MultiReservableCurrency};
+use pallet_contracts::{chain_extension::RegisteredChainExtension, Determinism};
+use pallet_contracts_primitives::Code;
+use pallet_nfts::{CollectionConfig, CollectionSettings, ItemConfig, ItemSettings, MintSettings};
+use primitives::currency::AssetMetadata;
+use sp_core::H256;
+use sp_runtime::MultiAddress;
+
+const ALICE: AccountId = AccountId::new([1u8; 32]);
+const BOB: AccountId = AccountId::new([2u8; 32]);
+
+// Ids of the functions, the extension id in the upper and the function id in the lower 16 bits.
+const BALANCE_OF: u32 = 0x0001_0000;
+const TOTAL_ISSUANCE: u32 = 0x0001_0001;
+const TRANSFER: u32 = 0x0001_0002;
+const RESERVE: u32 = 0x0001_0003;
+const UNRESERVE: u32 = 0x0001_0004;
+const METADATA: u32 = 0x0001_0005;
+const NFT_OWNER: u32 = 0x0002_0000;
+const NFT_ATTRIBUTE: u32 = 0x0002_0001;
+const NFT_MINT: u32 = 0x0002_0002;
+const NFT_BURN: u32 = 0x0002_0003;
+const NFT_TRANSFER: u32 = 0x0002_0004;
+const NFT_SET_ATTRIBUTE: u32 = 0x0002_0005;
+const RANDOMNESS_REQUEST: u32 = 0x0003_0000;
+const RANDOMNESS_REVEAL: u32 = 0x0003_0001;
+const RANDOMNESS_CANCEL: u32 = 0x0003_0002;
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+	pallet_balances::GenesisConfig::<Runtime> { balances: vec![(ALICE, 10_000 * dollar(ZERO))] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Instantiates the fixture contract with 100 ZERO and funds it with 100 PLAY.
+fn deploy() -> AccountId {
+	let wasm = wat::parse_str(include_str!("../../fixtures/chain_extension.wat")).unwrap();
+	let contract = Contracts::bare_instantiate(
+		ALICE,
+		100 * dollar(ZERO),
+		RuntimeBlockWeights::get().max_block,
+		None,
+		Code::Upload(wasm),
+		vec![],
+		vec![],
+		false,
+	)
+	.result
+	.unwrap()
+	.account_id;
+	<Currencies as MultiCurrency<AccountId>>::deposit(PLAY, &contract, 100 * dollar(PLAY)).unwrap();
+	contract
+}
+
+/// Calls the chain extension function `id` from `contract`, returns the status code and the output
+/// of the function.
+fn call(contract: &AccountId, id: u32, input: impl Encode) -> (u32, Vec<u8>) {
+	let mut data = id.encode();
+	input.encode_to(&mut data);
+	let output = Contracts::bare_call(
+		ALICE,
+		contract.clone(),
+		0,
+		RuntimeBlockWeights::get().max_block,
+		None,
+		data,
+		false,
+		Determinism::Deterministic,
+	)
+	.result
+	.unwrap()
+	.data;
+	let mut output = &output[..];
+	(u32::decode(&mut output).unwrap(), output.to_vec())
+}
+
+fn play_of(who: &AccountId) -> (Balance, Balance) {
+	(
+		<Currencies as MultiCurrency<AccountId>>::free_balance(PLAY, who),
+		<Currencies as MultiReservableCurrency<AccountId>>::reserved_balance(PLAY, who),
+	)
+}
+
+/// Creates collection `0` owned by `owner`, which gets all roles of the collection.
+fn create_collection(owner: &AccountId) {
+	let config = CollectionConfig {
+		settings: CollectionSettings::all_enabled(),
+		max_supply: None,
+		mint_settings: MintSettings::default(),
+	};
+	Nfts::force_create(RuntimeOrigin::root(), MultiAddress::Id(owner.clone()), config).unwrap();
+}
+
+#[test]
+fn extension_ids_should_not_change() {
+	assert_eq!(CurrenciesExtension::ID, 1);
+	assert_eq!(NftsExtension::ID, 2);
+	assert_eq!(RandomnessExtension::ID, 3);
+}
+
+#[test]
+fn balance_of_and_total_issuance_should_work() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy();
+		let (status, output) = call(&contract, BALANCE_OF, (PLAY, contract.clone()));
+		assert_eq!(status, 0);
+		assert_eq!(Balance::decode(&mut &output[..]), Ok(100 * dollar(PLAY)));
+
+		let (status, output) = call(&contract, BALANCE_OF, (ZERO, ALICE));
+		assert_eq!(status, 0);
+		assert_eq!(Balance::decode(&mut &output[..]), Ok(Balances::free_balance(&ALICE)));
+
+		let (status, output) = call(&contract, TOTAL_ISSUANCE, PLAY);
+		assert_eq!(status, 0);
+		assert_eq!(Balance::decode(&mut &output[..]), Ok(100 * dollar(PLAY)));
+	});
+}
+
+#[test]
+fn transfer_should_move_funds_of_the_contract() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy();
+		let (status, _) = call(&contract, TRANSFER, (PLAY, BOB, 40 * dollar(PLAY)));
+		assert_eq!(status, 0);
+		assert_eq!(play_of(&contract), (60 * dollar(PLAY), 0));
+		assert_eq!(play_of(&BOB), (40 * dollar(PLAY), 0));
+
+		let (status, _) = call(&contract, TRANSFER, (PLAY, BOB, 61 * dollar(PLAY)));
+		assert_eq!(status, CurrenciesError::BalanceTooLow as u32);
+		assert_eq!(play_of(&contract), (60 * dollar(PLAY), 0));
+
+		// Unlike native transfers, transfers of other currencies may empty the contract.
+		let (status, _) = call(&contract, TRANSFER, (PLAY, BOB, 60 * dollar(PLAY)));
+		assert_eq!(status, 0);
+		assert_eq!(play_of(&contract), (0, 0));
+		assert_eq!(play_of(&BOB), (100 * dollar(PLAY), 0));
+	});
+}
+
+#[test]
+fn transfer_of_the_native_currency_should_work() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy();
+		let free = Balances::free_balance(&contract);
+		let (status, _) = call(&contract, TRANSFER, (ZERO, BOB, dollar(ZERO) / 2));
+		assert_eq!(status, 0);
+		assert_eq!(Balances::free_balance(&contract), free - dollar(ZERO) / 2);
+		assert_eq!(Balances::free_balance(&BOB), dollar(ZERO) / 2);
+
+		let (status, _) = call(&contract, TRANSFER, (ZERO, BOB, free));
+		assert_eq!(status, CurrenciesError::BalanceTooLow as u32);
+	});
+}
+
+#[test]
+fn reserve_and_unreserve_should_work() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy();
+		let (status, _) = call(&contract, RESERVE, (PLAY, 30 * dollar(PLAY)));
+		assert_eq!(status, 0);
+		assert_eq!(play_of(&contract), (70 * dollar(PLAY), 30 * dollar(PLAY)));
+
+		let (status, _) = call(&contract, RESERVE, (PLAY, 71 * dollar(PLAY)));
+		assert_eq!(status, CurrenciesError::BalanceTooLow as u32);
+
+		let (status, output) = call(&contract, UNRESERVE, (PLAY, 40 * dollar(PLAY)));
+		assert_eq!(status, 0);
+		assert_eq!(Balance::decode(&mut &output[..]), Ok(10 * dollar(PLAY)));
+		assert_eq!(play_of(&contract), (100 * dollar(PLAY), 0));
+	});
+}
+
+#[test]
+fn metadata_should_work() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy();
+		let (status, output) = call(&contract, METADATA, PLAY);
+		assert_eq!(status, 0);
+		let metadata = Option::<AssetMetadata<Balance, Vec<u8>>>::decode(&mut &output[..])
+			.unwrap()
+			.unwrap();
+		assert_eq!(metadata.symbol, b"PLAY".to_vec());
+
+		let (status, output) =
+			call(&contract, METADATA, CurrencyId::ForeignAsset(ForeignAssetId::MAX));
+		assert_eq!(status, 0);
+		assert_eq!(Option::<AssetMetadata<Balance, Vec<u8>>>::decode(&mut &output[..]), Ok(None));
+	});
+}
+
+#[test]
+fn nfts_should_mint_set_attributes_transfer_and_burn() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy();
+		create_collection(&contract);
+
+		let (status, _) = call(&contract, NFT_MINT, (0u32, 7u32, contract.clone()));
+		assert_eq!(status, 0);
+		let (status, output) = call(&contract, NFT_OWNER, (0u32, 7u32));
+		assert_eq!(status, 0);
+		assert_eq!(Option::<AccountId>::decode(&mut &output[..]), Ok(Some(contract.clone())));
+
+		let (status, _) =
+			call(&contract, NFT_SET_ATTRIBUTE, (0u32, 7u32, b"level".to_vec(), b"3".to_vec()));
+		assert_eq!(status, 0);
+		let (status, output) = call(&contract, NFT_ATTRIBUTE, (0u32, 7u32, b"level".to_vec()));
+		assert_eq!(status, 0);
+		assert_eq!(Option::<Vec<u8>>::decode(&mut &output[..]), Ok(Some(b"3".to_vec())));
+
+		let (status, _) = call(&contract, NFT_MINT, (0u32, 7u32, BOB));
+		assert_eq!(status, NftsError::AlreadyExists as u32);
+
+		let (status, _) = call(&contract, NFT_TRANSFER, (0u32, 7u32, BOB));
+		assert_eq!(status, 0);
+		assert_eq!(<Nfts as Inspect<AccountId>>::owner(&0, &7), Some(BOB));
+
+		let (status, _) = call(&contract, NFT_BURN, (0u32, 7u32));
+		assert_eq!(status, NftsError::NoPermission as u32);
+		let (status, _) = call(&contract, NFT_MINT, (0u32, 8u32, contract.clone()));
+		assert_eq!(status, 0);
+		let (status, _) = call(&contract, NFT_BURN, (0u32, 8u32));
+		assert_eq!(status, 0);
+		assert_eq!(<Nfts as Inspect<AccountId>>::owner(&0, &8), None);
+	});
+}
+
+#[test]
+fn nfts_should_require_the_collection_roles() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy();
+		create_collection(&ALICE);
+
+		let (status, _) = call(&contract, NFT_MINT, (0u32, 7u32, contract.clone()));
+		assert_eq!(status, NftsError::NoPermission as u32);
+
+		Nfts::force_mint(
+			RuntimeOrigin::signed(ALICE),
+			0,
+			7,
+			MultiAddress::Id(contract.clone()),
+			ItemConfig { settings: ItemSettings::all_enabled() },
+		)
+		.unwrap();
+		let (status, _) =
+			call(&contract, NFT_SET_ATTRIBUTE, (0u32, 7u32, b"level".to_vec(), b"3".to_vec()));
+		assert_eq!(status, NftsError::NoPermission as u32);
+
+		let (status, _) = call(&contract, NFT_MINT, (1u32, 7u32, contract.clone()));
+		assert_eq!(status, NftsError::UnknownCollection as u32);
+	});
+}
+
+#[test]
+fn randomness_requests_should_belong_to_the_contract() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy();
+		let reserved = Balances::reserved_balance(&contract);
+		let (status, output) = call(&contract, RANDOMNESS_REQUEST, H256::repeat_byte(1));
+		assert_eq!(status, 0);
+		assert_eq!(RequestId::decode(&mut &output[..]), Ok(0));
+		assert_eq!(
+			Balances::reserved_balance(&contract),
+			reserved + RandomnessRequestDeposit::get()
+		);
+
+		// The randomness of the requested block is not known before that block is built.
+		let (status, _) = call(&contract, RANDOMNESS_REVEAL, 0u64);
+		assert_eq!(status, RandomnessError::TooEarly as u32);
+
+		let id = RelayRandomness::request(ALICE, H256::zero()).unwrap();
+		let (status, _) = call(&contract, RANDOMNESS_CANCEL, id);
+		assert_eq!(status, RandomnessError::NotOwner as u32);
+
+		let (status, _) = call(&contract, RANDOMNESS_CANCEL, 0u64);
+		assert_eq!(status, 0);
+		assert_eq!(Balances::reserved_balance(&contract), reserved);
+		let (status, _) = call(&contract, RANDOMNESS_REVEAL, 0u64);
+		assert_eq!(status, RandomnessError::UnknownRequest as u32);
+	});
+}
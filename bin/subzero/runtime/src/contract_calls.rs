@@ -0,0 +1,124 @@
+//! Runtime calls contracts may dispatch with `call_runtime`.
+//!
+//! Deployed contracts encode these calls themselves, so a whitelisted call must keep its pallet
+//! index, call index and arguments. Calls are never removed from the whitelist, every version only
+//! adds calls. The tests pin the indices of every whitelisted call.
+
+use crate::RuntimeCall;
+use frame_support::{dispatch::GetCallMetadata, traits::Contains};
+
+/// Latest version of the whitelist.
+pub const VERSION: u32 = 1;
+
+/// Whitelisted calls as `(version, pallet, call)`, `version` being the version which added the
+/// call.
+pub const WHITELIST: &[(u32, &str, &str)] = &[
+	(1, "Currencies", "transfer"),
+	(1, "Currencies", "transfer_native_currency"),
+	(1, "Nfts", "transfer"),
+	(1, "Flow", "contribute"),
+	(1, "Signal", "vote"),
+];
+
+/// `pallet_contracts::Config::CallFilter` of the runtime, lets contracts dispatch the calls of
+/// `WHITELIST`.
+pub struct ContractCallFilter;
+
+impl Contains<RuntimeCall> for ContractCallFilter {
+	fn contains(call: &RuntimeCall) -> bool {
+		let metadata = call.get_call_metadata();
+		WHITELIST.iter().any(|(_, pallet, function)| {
+			*pallet == metadata.pallet_name && *function == metadata.function_name
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{AccountId, Currencies, Flow, Nfts, Runtime, Signal, PLAY};
+	use codec::Encode;
+	use frame_support::{
+		dispatch::{GetCallIndex, GetCallName},
+		traits::PalletInfoAccess,
+	};
+	use sp_runtime::MultiAddress;
+
+	/// Pallet and call indices deployed contracts use for the whitelisted calls. A failure means
+	/// that a change of the runtime breaks deployed contracts.
+	const PINNED: &[(u32, &str, &str, u8, u8)] = &[
+		(1, "Currencies", "transfer", 61, 0),
+		(1, "Currencies", "transfer_native_currency", 61, 1),
+		(1, "Nfts", "transfer", 34, 6),
+		(1, "Flow", "contribute", 70, 2),
+		(1, "Signal", "vote", 73, 1),
+	];
+
+	fn indices_in<P: PalletInfoAccess, C: GetCallName + GetCallIndex>(
+		function: &str,
+	) -> Option<(u8, u8)> {
+		let position = C::get_call_names().iter().position(|name| *name == function)?;
+		Some((P::index() as u8, *C::get_call_indices().get(position)?))
+	}
+
+	fn indices(pallet: &str, function: &str) -> Option<(u8, u8)> {
+		match pallet {
+			"Currencies" => indices_in::<Currencies, orml_currencies::Call<Runtime>>(function),
+			"Nfts" => indices_in::<Nfts, pallet_nfts::Call<Runtime>>(function),
+			"Flow" => indices_in::<Flow, gamedao_flow::Call<Runtime>>(function),
+			"Signal" => indices_in::<Signal, gamedao_signal::Call<Runtime>>(function),
+			_ => None,
+		}
+	}
+
+	#[test]
+	fn whitelisted_calls_should_keep_their_indices() {
+		let whitelist: Vec<_> = PINNED.iter().map(|(v, p, c, _, _)| (*v, *p, *c)).collect();
+		assert_eq!(WHITELIST, &whitelist[..]);
+		assert_eq!(WHITELIST.iter().map(|(version, _, _)| *version).max(), Some(VERSION));
+		for (_, pallet, function, pallet_index, call_index) in PINNED {
+			assert_eq!(
+				indices(pallet, function),
+				Some((*pallet_index, *call_index)),
+				"{}::{} changed its indices",
+				pallet,
+				function
+			);
+		}
+	}
+
+	#[test]
+	fn whitelisted_calls_should_keep_their_encoding() {
+		let dest = AccountId::new([1u8; 32]);
+		let call = RuntimeCall::Currencies(orml_currencies::Call::transfer {
+			dest: MultiAddress::Id(dest.clone()),
+			currency_id: PLAY,
+			amount: 10,
+		});
+		assert!(ContractCallFilter::contains(&call));
+		let mut expected = vec![61, 0, 0];
+		expected.extend(dest.encode());
+		expected.extend(PLAY.encode());
+		expected.extend(codec::Compact(10u128).encode());
+		assert_eq!(call.encode(), expected);
+
+		let call = RuntimeCall::Nfts(pallet_nfts::Call::transfer {
+			collection: 0,
+			item: 7,
+			dest: MultiAddress::Id(dest.clone()),
+		});
+		assert!(ContractCallFilter::contains(&call));
+		assert_eq!(&call.encode()[..2], &[34, 6]);
+	}
+
+	#[test]
+	fn other_calls_should_be_filtered() {
+		let dest = MultiAddress::Id(AccountId::new([1u8; 32]));
+		assert!(!ContractCallFilter::contains(&RuntimeCall::System(frame_system::Call::remark {
+			remark: vec![]
+		})));
+		assert!(!ContractCallFilter::contains(&RuntimeCall::Currencies(
+			orml_currencies::Call::update_balance { who: dest, currency_id: PLAY, amount: 10 }
+		)));
+	}
+}
@@ -7,6 +7,8 @@
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 mod weights;
+pub mod chain_extensions;
+pub mod contract_calls;
 pub mod xcm_config;
 pub mod constants;
 
@@ -17,9 +19,10 @@ use sp_api::impl_runtime_apis;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
-	traits::{Convert, AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, Verify},
+	helpers_128bit::multiply_by_rational_with_rounding,
+	traits::{Convert, AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, Verify, Zero},
 	transaction_validity::{TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, MultiSignature, Percent
+	ApplyExtrinsicResult, MultiSignature, Percent, Rounding
 };
 
 use sp_std::prelude::*;
@@ -37,7 +40,7 @@ use frame_support::{
 		tokens::nonfungibles_v2::Inspect,
 		AsEnsureOriginWithArg, ConstU32, ConstU64, ConstU8, Contains, EitherOfDiverse,
 		Everything, EnsureOrigin, EnsureOriginWithArg, EqualPrivilegeOnly, InstanceFilter,
-		LockIdentifier, U128CurrencyToVote, Nothing, ConstBool
+		LockIdentifier, U128CurrencyToVote, ConstBool
 	},
 	weights::{
 		constants::WEIGHT_REF_TIME_PER_SECOND, ConstantMultiplier, Weight, WeightToFeeCoefficient,
@@ -62,19 +65,22 @@ use polkadot_runtime_common::{BlockHashCount, SlowAdjustingFeeUpdate};
 use weights::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight};
 
 // XCM Imports
-use xcm::latest::prelude::BodyId;
+use xcm::{latest::prelude::BodyId, VersionedMultiLocation};
 use xcm_executor::XcmExecutor;
 
 pub use constants::{fee::*, time::*};
 pub use primitives::{
 	currency::{ZERO, PLAY, GAME, DOT, KSM, AssetIds, CurrencyId, CustomMetadata, ForeignAssetId, TokenSymbol},
 	dollar, cent, millicent,
-	Amount, ReserveIdentifier
+	Amount, Denomination, ReserveIdentifier
 };
 
 use orml_currencies::BasicCurrencyAdapter;
 use orml_traits::{parameter_type_with_key, GetByKey};
 
+use module_tx_payment::{FeeSource, FeeSplit};
+use pallet_identity_rpc_runtime_api::{IdentitySummary, JudgementSummary};
+
 use pallet_nfts::PalletFeatures;
 
 /// Alias to 512-bit hash when used in the context of a transaction signature on the chain.
@@ -120,7 +126,7 @@ pub type SignedExtra = (
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
-	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	module_tx_payment::ChargeCurrencyTxPayment<Runtime>,
 );
 
 /// Unchecked extrinsic type as expected by this runtime.
@@ -144,8 +150,46 @@ pub type Executive = frame_executive::Executive<
 // `OnRuntimeUpgrade`.
 type Migrations = (
 	pallet_contracts::Migration<Runtime>,
+	RemoveRandomnessCollectiveFlip,
 );
 
+/// Keys of `RandomnessCollectiveFlip` removed per runtime upgrade.
+pub const COLLECTIVE_FLIP_REMOVAL_LIMIT: u32 = 64;
+
+/// Removes all storage of `RandomnessCollectiveFlip`, replaced by `RelayRandomness`: its random
+/// material and its storage version, as `RemovePallet` of later releases does. Once the storage is
+/// gone it only checks that the prefix is empty.
+///
+/// At most [`COLLECTIVE_FLIP_REMOVAL_LIMIT`] keys are removed per upgrade, far more than the two
+/// keys of the pallet; anything left is removed by the following upgrades.
+///
+/// Remove it from `Migrations` after the next release.
+pub struct RemoveRandomnessCollectiveFlip;
+impl frame_support::traits::OnRuntimeUpgrade for RemoveRandomnessCollectiveFlip {
+	fn on_runtime_upgrade() -> Weight {
+		let prefix = sp_core::hashing::twox_128(b"RandomnessCollectiveFlip");
+		if !frame_support::storage::unhashed::contains_prefixed_key(&prefix) {
+			return RocksDbWeight::get().reads(1)
+		}
+		let removed = frame_support::storage::unhashed::clear_prefix(
+			&prefix,
+			Some(COLLECTIVE_FLIP_REMOVAL_LIMIT),
+			None,
+		);
+		RocksDbWeight::get().reads_writes(removed.loops.into(), removed.backend.into())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+		let prefix = sp_core::hashing::twox_128(b"RandomnessCollectiveFlip");
+		frame_support::ensure!(
+			!frame_support::storage::unhashed::contains_prefixed_key(&prefix),
+			"storage of RandomnessCollectiveFlip is left"
+		);
+		Ok(())
+	}
+}
+
 /// Handles converting a weight scalar to a fee value, based on the scale and granularity of the
 /// node's balance type.
 ///
@@ -198,11 +242,11 @@ impl_opaque_keys! {
 pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("subzero"),
 	impl_name: create_runtime_str!("live"),
-	authoring_version: 75,
-	spec_version: 75,
+	authoring_version: 78,
+	spec_version: 78,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -407,13 +451,58 @@ parameter_types! {
 
 impl pallet_transaction_payment::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
-	type OnChargeTransaction = pallet_transaction_payment::CurrencyAdapter<Balances, ()>;
+	type OnChargeTransaction =
+		pallet_transaction_payment::CurrencyAdapter<Balances, module_tx_payment::DealWithFees<Runtime>>;
 	type WeightToFee = WeightToFee;
 	type LengthToFee = ConstantMultiplier<Balance, TransactionByteFee>;
 	type FeeMultiplierUpdate = SlowAdjustingFeeUpdate<Self>;
 	type OperationalFeeMultiplier = ConstU8<5>;
 }
 
+/// Converts native fees with the `fee::base_tx_in_token` ratios for tokens and the registered
+/// `fee_per_second` for foreign assets.
+pub struct CurrencyFeeRate;
+impl module_tx_payment::FeeRate<CurrencyId, Balance> for CurrencyFeeRate {
+	fn convert(currency_id: CurrencyId, native_fee: Balance) -> Option<Balance> {
+		let (numerator, denominator) = match currency_id {
+			CurrencyId::Token(symbol) =>
+				(base_tx_in_token(symbol), base_tx_in_token(TokenSymbol::ZERO)),
+			CurrencyId::ForeignAsset(id) =>
+				(AssetRegistry::metadata(&id)?.additional.fee_per_second, zero_per_second()),
+		};
+		if numerator.is_zero() {
+			return None
+		}
+		multiply_by_rational_with_rounding(native_fee, numerator, denominator, Rounding::Up)
+	}
+}
+
+parameter_type_with_key! {
+	pub DefaultFeeSplit: |source: FeeSource| -> FeeSplit {
+		match source {
+			FeeSource::TransactionFee =>
+				FeeSplit { treasury: Percent::from_percent(60), author: Percent::from_percent(20) },
+			FeeSource::Tip =>
+				FeeSplit { treasury: Percent::zero(), author: Percent::from_percent(100) },
+			FeeSource::XcmRevenue =>
+				FeeSplit { treasury: Percent::zero(), author: Percent::from_percent(100) },
+		}
+	};
+}
+
+impl module_tx_payment::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type CurrencyId = CurrencyId;
+	type MultiCurrency = Currencies;
+	type NativeCurrencyId = GetNativeCurrencyId;
+	type FeeRate = CurrencyFeeRate;
+	type NativeCurrency = Balances;
+	type TreasuryAccount = TreasuryAccountId;
+	type DefaultFeeSplit = DefaultFeeSplit;
+	type UpdateOrigin = EnsureRootOrHalfCouncil;
+	type WeightInfo = module_tx_payment::weights::SubstrateWeight<Runtime>;
+}
+
 parameter_types! {
 	// One storage item; key size is 32; value is size 4+4+16+32 bytes = 56 bytes.
 	pub MultisigDepositBase: Balance = deposit(1, 88);
@@ -597,7 +686,22 @@ impl pallet_democracy::Config for Runtime {
 	type MaxBlacklisted = ConstU32<100>;
 }
 
-impl pallet_insecure_randomness_collective_flip::Config for Runtime {}
+parameter_types! {
+	// Requests use the randomness of the second next block, whose relay parent is not even known
+	// to the collator of the next block.
+	pub const RandomnessRequestDelay: BlockNumber = 2;
+	// A request stores its owner, deposit, block and salt.
+	pub RandomnessRequestDeposit: Balance = deposit(1, 100);
+}
+
+impl module_relay_randomness::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RelayState = ParachainSystem;
+	type Currency = Balances;
+	type RequestDeposit = RandomnessRequestDeposit;
+	type RequestDelay = RandomnessRequestDelay;
+	type WeightInfo = module_relay_randomness::weights::SubstrateWeight<Runtime>;
+}
 
 parameter_types! {
 	pub DepositPerItem: Balance = deposit(1, 0);
@@ -614,23 +718,20 @@ parameter_types! {
 
 impl pallet_contracts::Config for Runtime {
 	type Time = Timestamp;
-	type Randomness = RandomnessCollectiveFlip;
+	type Randomness = RelayRandomness;
 	type Currency = Balances;
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
-	/// The safest default is to allow no calls at all.
-	///
-	/// Runtimes should whitelist dispatchables that are allowed to be called from contracts
-	/// and make sure they are stable. Dispatchables exposed to contracts are not allowed to
-	/// change because that would break already deployed contracts. The `Call` structure itself
-	/// is not allowed to change the indices of existing pallets, too.
-	type CallFilter = Nothing;
+	/// Dispatchables exposed to contracts are not allowed to change because that would break
+	/// already deployed contracts. The `Call` structure itself is not allowed to change the indices
+	/// of existing pallets, too.
+	type CallFilter = contract_calls::ContractCallFilter;
 	type DepositPerItem = DepositPerItem;
 	type DepositPerByte = DepositPerByte;
 	type CallStack = [pallet_contracts::Frame<Self>; 5];
 	type WeightPrice = pallet_transaction_payment::Pallet<Self>;
 	type WeightInfo = pallet_contracts::weights::SubstrateWeight<Self>;
-	type ChainExtension = ();
+	type ChainExtension = chain_extensions::RuntimeExtensions;
 	type DeletionQueueDepth = DeletionQueueDepth;
 	type DeletionWeightLimit = DeletionWeightLimit;
 	type Schedule = Schedule;
@@ -846,23 +947,93 @@ parameter_types! {
 	pub BasicDeposit: Balance = 10 * dollar(ZERO);		// 258 bytes on-chain
 	pub FieldDeposit: Balance = 250 * cent(ZERO);		// 	66 bytes on-chain
 	pub SubAccountDeposit: Balance = 2 * dollar(ZERO);  // 	53 bytes on-chain
+	pub UsernameDeposit: Balance = dollar(ZERO);		// 	64 bytes on-chain
+	pub const UsernameDuration: BlockNumber = 365 * DAYS;
+}
+
+/// Converts native identity deposits at the rates of `CurrencyFeeRate`.
+pub struct CurrencyDepositRate;
+impl pallet_identity::DepositRate<CurrencyId, Balance> for CurrencyDepositRate {
+	fn convert(currency_id: CurrencyId, native_deposit: Balance) -> Option<Balance> {
+		<CurrencyFeeRate as module_tx_payment::FeeRate<CurrencyId, Balance>>::convert(
+			currency_id,
+			native_deposit,
+		)
+	}
 }
 
 impl pallet_identity::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
-	type Currency = Balances;
+	type Currency = Currencies;
+	type NativeCurrencyId = GetNativeCurrencyId;
+	type DepositRate = CurrencyDepositRate;
+	type DepositCurrencyOrigin = EnsureRootOrHalfCouncil;
 	type BasicDeposit = BasicDeposit;
 	type FieldDeposit = FieldDeposit;
 	type SubAccountDeposit = SubAccountDeposit;
 	type MaxSubAccounts = ConstU32<100>;
 	type MaxAdditionalFields = ConstU32<100>;
 	type MaxRegistrars = ConstU32<20>;
-	type Slashed = Treasury;
+	type Slashed = TreasuryAccountId;
 	type ForceOrigin = EnsureRootOrHalfCouncil;
 	type RegistrarOrigin = EnsureRootOrHalfCouncil;
+	type OffchainSignature = Signature;
+	type SigningPublicKey = <Signature as Verify>::Signer;
+	type UsernameAuthorityOrigin = EnsureRootOrHalfCouncil;
+	type UsernameDeposit = UsernameDeposit;
+	type UsernameDuration = UsernameDuration;
+	type MaxUsernameLength = ConstU32<32>;
+	type MaxSuffixLength = ConstU32<7>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
 	type WeightInfo = pallet_identity::weights::SubstrateWeight<Runtime>;
 }
 
+/// The display name, judgements and super-identity of `who` for `IdentityApi`, `None` if `who`
+/// has no identity and is no sub-identity either.
+fn identity_summary(who: AccountId) -> Option<IdentitySummary<AccountId>> {
+	use pallet_identity::{Data, Judgement};
+
+	let raw = |data: &Data| match data {
+		Data::Raw(bytes) => Some(bytes.to_vec()),
+		_ => None,
+	};
+	let (account, sub_name) = match Identity::super_of(&who) {
+		Some((parent, name)) => (parent, Some(name)),
+		None => (who, None),
+	};
+	let registration = Identity::identity(&account);
+	if registration.is_none() && sub_name.is_none() {
+		return None
+	}
+
+	let display = match (
+		registration.as_ref().and_then(|r| raw(&r.info.display)),
+		sub_name.as_ref().and_then(raw),
+	) {
+		(Some(mut parent), Some(sub)) => {
+			parent.push(b'/');
+			parent.extend(sub);
+			Some(parent)
+		},
+		(parent, sub) => parent.or(sub),
+	};
+	let mut judgements = JudgementSummary::default();
+	for (_, judgement) in Identity::judgements_of(&account) {
+		match judgement {
+			Judgement::Unknown => (),
+			Judgement::FeePaid(_) => judgements.pending += 1,
+			Judgement::Reasonable => judgements.reasonable += 1,
+			Judgement::KnownGood => judgements.known_good += 1,
+			Judgement::OutOfDate => judgements.out_of_date += 1,
+			Judgement::LowQuality => judgements.low_quality += 1,
+			Judgement::Erroneous => judgements.erroneous += 1,
+		}
+	}
+
+	Some(IdentitySummary { display, judgements, super_account: sub_name.map(|_| account) })
+}
+
 parameter_types! {
 	pub const ReservedXcmpWeight: Weight = MAXIMUM_BLOCK_WEIGHT.saturating_div(4);
 	pub const ReservedDmpWeight: Weight = MAXIMUM_BLOCK_WEIGHT.saturating_div(4);
@@ -976,13 +1147,11 @@ pub fn get_all_module_accounts() -> Vec<AccountId> {
 parameter_type_with_key! {
 	pub ExistentialDeposits: |currency_id: CurrencyId| -> Balance {
 		match currency_id {
-			CurrencyId::Token(symbol) => match symbol {
-				TokenSymbol::ZERO => cent(*currency_id),
-				TokenSymbol::PLAY => 10 * cent(*currency_id),
-				TokenSymbol::GAME => 10 * cent(*currency_id),
-				TokenSymbol::DOT => cent(*currency_id),
-				_ => cent(*currency_id),
-			},
+			CurrencyId::Token(symbol) => Denomination::of::<()>(*currency_id)
+				.map_or(Balance::max_value(), |denomination| match symbol {
+					TokenSymbol::PLAY | TokenSymbol::GAME => 10 * denomination.cent(),
+					_ => denomination.cent(),
+				}),
 			CurrencyId::ForeignAsset(id) => {
 				AssetRegistry::metadata(&id)
 					.map_or(Balance::max_value(), |metadata| metadata.existential_deposit)
@@ -1223,7 +1392,7 @@ construct_runtime!(
 		Democracy: pallet_democracy = 15,
 		Elections: pallet_elections_phragmen = 16,
 		TechnicalMembership: pallet_membership::<Instance1> = 17,
-		RandomnessCollectiveFlip: pallet_insecure_randomness_collective_flip = 18,
+		RelayRandomness: module_relay_randomness = 18,
 		Contracts: pallet_contracts = 19,
 
 		// Monetary stuff.
@@ -1231,6 +1400,7 @@ construct_runtime!(
 		Tips: pallet_tips = 21,
 		Balances: pallet_balances = 22,
 		TransactionPayment: pallet_transaction_payment = 23,
+		TxPayment: module_tx_payment = 24,
 
 		// NFT
 		Nfts: pallet_nfts = 34,
@@ -1274,6 +1444,9 @@ mod benches {
 		[pallet_timestamp, Timestamp]
 		[pallet_collator_selection, CollatorSelection]
 		[cumulus_pallet_xcmp_queue, XcmpQueue]
+		[pallet_nfts, Nfts]
+		[module_tx_payment, TxPayment]
+		[module_relay_randomness, RelayRandomness]
 
 		[gamedao_flow, Flow]
 		[gamedao_sense, Sense]
@@ -1485,6 +1658,64 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl module_tx_payment_rpc_runtime_api::TxPaymentApi<Block, Balance, CurrencyId> for Runtime {
+		fn query_fee_in_currency(
+			uxt: <Block as BlockT>::Extrinsic,
+			len: u32,
+			currency_id: CurrencyId,
+		) -> Option<Balance> {
+			TxPayment::query_fee_in_currency(uxt, len, currency_id)
+		}
+		fn quote_fee(native_fee: Balance, currency_id: CurrencyId) -> Option<Balance> {
+			TxPayment::convert_fee(currency_id, native_fee)
+		}
+	}
+
+	impl module_asset_registry_rpc_runtime_api::AssetRegistryApi<
+		Block,
+		CurrencyId,
+		Balance,
+		VersionedMultiLocation,
+	> for Runtime {
+		fn assets() -> Vec<module_asset_registry_rpc_runtime_api::AssetInfo<
+			CurrencyId,
+			Balance,
+			VersionedMultiLocation,
+		>> {
+			xcm_config::assets()
+		}
+
+		fn asset(
+			currency_id: CurrencyId,
+		) -> Option<module_asset_registry_rpc_runtime_api::AssetInfo<
+			CurrencyId,
+			Balance,
+			VersionedMultiLocation,
+		>> {
+			xcm_config::asset_info(currency_id)
+		}
+
+		fn currency_id(location: VersionedMultiLocation) -> Option<CurrencyId> {
+			xcm_config::currency_id(location)
+		}
+	}
+
+	impl pallet_identity_rpc_runtime_api::IdentityApi<Block, AccountId> for Runtime {
+		fn resolve_accounts(accounts: Vec<AccountId>) -> Vec<Option<IdentitySummary<AccountId>>> {
+			accounts.into_iter().map(identity_summary).collect()
+		}
+	}
+
+	impl pallet_identity_rpc_runtime_api::UsernameApi<Block, AccountId> for Runtime {
+		fn account_of_username(username: Vec<u8>) -> Option<AccountId> {
+			Identity::account_of_username(&username)
+		}
+
+		fn username_of(who: AccountId) -> Option<Vec<u8>> {
+			Identity::username_of(&who)
+		}
+	}
+
 	impl pallet_nfts_runtime_api::NftsApi<Block, AccountId, u32, u32> for Runtime {
 		fn owner(collection: u32, item: u32) -> Option<AccountId> {
 			<Nfts as Inspect<AccountId>>::owner(&collection, &item)
@@ -1634,3 +1865,144 @@ cumulus_pallet_parachain_system::register_validate_block! {
 	BlockExecutor = cumulus_pallet_aura_ext::BlockExecutor::<Runtime, Executive>,
 	CheckInherents = CheckInherents,
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::{
+		assert_ok,
+		storage::{storage_prefix, unhashed},
+		traits::OnRuntimeUpgrade,
+	};
+	use pallet_identity::{Data, IdentityInfo, Judgement};
+
+	const ALICE: AccountId = AccountId::new([1u8; 32]);
+	const BOB: AccountId = AccountId::new([2u8; 32]);
+	const CHARLIE: AccountId = AccountId::new([3u8; 32]);
+	const DAVE: AccountId = AccountId::new([4u8; 32]);
+	const EVE: AccountId = AccountId::new([5u8; 32]);
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		pallet_balances::GenesisConfig::<Runtime> { balances: vec![(ALICE, 1_000 * dollar(ZERO))] }
+			.assimilate_storage(&mut t)
+			.unwrap();
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	fn raw(bytes: &[u8]) -> Data {
+		Data::Raw(bytes.to_vec().try_into().unwrap())
+	}
+
+	fn info(display: &[u8]) -> IdentityInfo<ConstU32<100>> {
+		IdentityInfo {
+			additional: Default::default(),
+			display: raw(display),
+			legal: Data::None,
+			web: Data::None,
+			riot: Data::None,
+			email: Data::None,
+			pgp_fingerprint: None,
+			image: Data::None,
+			twitter: Data::None,
+			discord: Data::None,
+			steam: Data::None,
+			epic: Data::None,
+			twitch: Data::None,
+		}
+	}
+
+	#[test]
+	fn identity_summary_should_summarize_identities() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Identity::set_identity(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(info(b"alice"))
+			));
+			// Judged `Reasonable` by BOB and pending with CHARLIE, with DAVE as sub-identity.
+			for (index, registrar) in [BOB, CHARLIE].into_iter().enumerate() {
+				assert_ok!(Identity::add_registrar(RuntimeOrigin::root(), registrar));
+				assert_ok!(Identity::request_judgement(
+					RuntimeOrigin::signed(ALICE),
+					index as u32,
+					0
+				));
+			}
+			assert_ok!(Identity::provide_judgement(
+				RuntimeOrigin::signed(BOB),
+				0,
+				ALICE.into(),
+				Judgement::Reasonable
+			));
+			assert_ok!(Identity::set_subs(RuntimeOrigin::signed(ALICE), vec![(DAVE, raw(b"bot"))]));
+
+			let judgements = JudgementSummary { pending: 1, reasonable: 1, ..Default::default() };
+			assert_eq!(
+				identity_summary(ALICE),
+				Some(IdentitySummary {
+					display: Some(b"alice".to_vec()),
+					judgements: judgements.clone(),
+					super_account: None,
+				})
+			);
+			// Sub-identities show the judgements of their super-identity.
+			assert_eq!(
+				identity_summary(DAVE),
+				Some(IdentitySummary {
+					display: Some(b"alice/bot".to_vec()),
+					judgements,
+					super_account: Some(ALICE),
+				})
+			);
+		});
+	}
+
+	#[test]
+	fn identity_summary_should_be_none_without_identity() {
+		new_test_ext().execute_with(|| {
+			assert_eq!(identity_summary(EVE), None);
+			assert_eq!(identity_summary(ALICE), None);
+		});
+	}
+
+	#[test]
+	fn collective_flip_storage_should_be_removed() {
+		new_test_ext().execute_with(|| {
+			let material = storage_prefix(b"RandomnessCollectiveFlip", b"RandomMaterial");
+			let version = storage_prefix(b"RandomnessCollectiveFlip", b":__STORAGE_VERSION__:");
+			unhashed::put_raw(&material, &[1; 32]);
+			unhashed::put(&version, &0u16);
+
+			RemoveRandomnessCollectiveFlip::on_runtime_upgrade();
+			assert!(!unhashed::exists(&material));
+			assert!(!unhashed::exists(&version));
+			// Later upgrades only check that the storage is gone.
+			assert_eq!(
+				RemoveRandomnessCollectiveFlip::on_runtime_upgrade(),
+				RocksDbWeight::get().reads(1)
+			);
+		});
+	}
+
+	#[test]
+	fn collective_flip_storage_should_be_removed_in_bounded_batches() {
+		let prefix = sp_core::hashing::twox_128(b"RandomnessCollectiveFlip");
+		let mut ext = new_test_ext();
+		ext.execute_with(|| {
+			for i in 0..=COLLECTIVE_FLIP_REMOVAL_LIMIT {
+				unhashed::put_raw(&[&prefix[..], &i.to_le_bytes()].concat(), &[1]);
+			}
+		});
+		// The limit only applies to keys in the backend, not to the overlay.
+		ext.commit_all().unwrap();
+
+		ext.execute_with(|| {
+			RemoveRandomnessCollectiveFlip::on_runtime_upgrade();
+			assert!(unhashed::contains_prefixed_key(&prefix));
+			RemoveRandomnessCollectiveFlip::on_runtime_upgrade();
+			assert!(!unhashed::contains_prefixed_key(&prefix));
+		});
+	}
+}
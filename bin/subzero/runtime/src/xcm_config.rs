@@ -3,8 +3,9 @@ use super::{
 	AccountId, AllPalletsWithSystem, Balances, AssetRegistry, Balance, Convert,
 	CurrencyId::{self, ForeignAsset}, Currencies,
 	EnsureRootOrThreeFourthsCouncil,
-	ParachainInfo, ParachainSystem, PolkadotXcm,
-	Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, TreasuryAccountId, UnknownTokens, XcmpQueue,
+	ExistentialDeposits, ParachainInfo, ParachainSystem, PolkadotXcm,
+	Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, TreasuryAccountId, TxPayment, UnknownTokens,
+	XcmpQueue,
 	ZERO, PLAY, GAME, DOT, KSM
 };
 use codec::{Decode, Encode};
@@ -19,7 +20,7 @@ use frame_support::{
 };
 use pallet_xcm::XcmPassthrough;
 use polkadot_parachain::primitives::Sibling;
-use xcm::latest::prelude::*;
+use xcm::{latest::prelude::*, VersionedMultiLocation};
 use xcm_builder::{
 	AccountId32Aliases, AllowKnownQueryResponses, AllowTopLevelPaidExecutionFrom,
 	AllowSubscriptionsFrom, AllowUnpaidExecutionFrom, EnsureXcmOrigin, FixedRateOfFungible, FixedWeightBounds,
@@ -28,8 +29,14 @@ use xcm_builder::{
 	SignedToAccountId32, SovereignSignedViaLocation, TakeRevenue, TakeWeightCredit,
 };
 use xcm_executor::XcmExecutor;
+use module_asset_registry_rpc_runtime_api::AssetInfo;
+use module_tx_payment::FeeSource;
 use orml_asset_registry::{AssetRegistryTrader, FixedRateAssetRegistryTrader};
-use orml_traits::{location::AbsoluteReserveProvider, FixedConversionRateProvider, MultiCurrency, parameter_type_with_key};
+use orml_traits::{location::AbsoluteReserveProvider, FixedConversionRateProvider, GetByKey, parameter_type_with_key};
+use primitives::{
+	currency::{AssetIdMapping, AssetIds, AssetMetadata, ForeignAssetId, TokenSymbol},
+	TokenInfo,
+};
 use orml_xcm_support::{DepositToAlternative, IsNativeConcrete, MultiCurrencyAdapter, MultiNativeAsset};
 
 
@@ -140,8 +147,9 @@ parameter_types! {
 	);
 }
 
-pub struct ToAuthor;
-impl TakeRevenue for ToAuthor {
+/// Splits XCM execution fees between treasury, block author and burning, same as transaction fees.
+pub struct DistributeRevenue;
+impl TakeRevenue for DistributeRevenue {
 	fn take_revenue(revenue: MultiAsset) {
 		if let MultiAsset {
 			id: Concrete(location),
@@ -149,11 +157,7 @@ impl TakeRevenue for ToAuthor {
 		} = revenue
 		{
 			if let Some(currency_id) = CurrencyIdConvert::convert(location) {
-				if let Some(author) = pallet_authorship::Pallet::<Runtime>::author() {
-					let _ = Currencies::deposit(currency_id, &author, amount);
-				} else {
-					let _ = Currencies::deposit(currency_id, &TreasuryAccountId::get(), amount);
-				}
+				TxPayment::distribute(FeeSource::XcmRevenue, currency_id, amount);
 			}
 		}
 	}
@@ -168,11 +172,11 @@ impl FixedConversionRateProvider for ChainFixedConversionRateProvider {
 }
 
 pub type Trader = (
-	FixedRateOfFungible<DotPerSecond, ToAuthor>,
-	FixedRateOfFungible<PlayPerSecond, ToAuthor>,
-	FixedRateOfFungible<GamePerSecond, ToAuthor>,
-	FixedRateOfFungible<ZeroPerSecond, ToAuthor>,
-	AssetRegistryTrader<FixedRateAssetRegistryTrader<ChainFixedConversionRateProvider>, ToAuthor>,
+	FixedRateOfFungible<DotPerSecond, DistributeRevenue>,
+	FixedRateOfFungible<PlayPerSecond, DistributeRevenue>,
+	FixedRateOfFungible<GamePerSecond, DistributeRevenue>,
+	FixedRateOfFungible<ZeroPerSecond, DistributeRevenue>,
+	AssetRegistryTrader<FixedRateAssetRegistryTrader<ChainFixedConversionRateProvider>, DistributeRevenue>,
 );
 
 pub struct XcmConfig;
@@ -318,6 +322,73 @@ impl Convert<MultiAsset, Option<CurrencyId>> for CurrencyIdConvert {
 	}
 }
 
+/// `AssetIdMapping` over the native tokens and the foreign assets of `AssetRegistry`.
+pub struct AssetRegistryMapping;
+impl AssetIdMapping<ForeignAssetId, MultiLocation, AssetMetadata<Balance, Vec<u8>>>
+	for AssetRegistryMapping
+{
+	fn get_asset_metadata(asset_ids: AssetIds) -> Option<AssetMetadata<Balance, Vec<u8>>> {
+		match asset_ids {
+			AssetIds::NativeAssetId(currency_id @ CurrencyId::Token(_)) => Some(AssetMetadata {
+				name: currency_id.name()?.as_bytes().to_vec(),
+				symbol: currency_id.symbol()?.as_bytes().to_vec(),
+				decimals: currency_id.decimals()?,
+				minimal_balance: ExistentialDeposits::get(&currency_id),
+			}),
+			AssetIds::NativeAssetId(ForeignAsset(id)) | AssetIds::ForeignAssetId(id) => {
+				let metadata = AssetRegistry::metadata(&id)?;
+				Some(AssetMetadata {
+					name: metadata.name.to_vec(),
+					symbol: metadata.symbol.to_vec(),
+					decimals: metadata.decimals.try_into().ok()?,
+					minimal_balance: metadata.existential_deposit,
+				})
+			},
+		}
+	}
+
+	fn get_multi_location(foreign_asset_id: ForeignAssetId) -> Option<MultiLocation> {
+		AssetRegistry::multilocation(&foreign_asset_id).ok().flatten()
+	}
+
+	fn get_currency_id(multi_location: MultiLocation) -> Option<CurrencyId> {
+		CurrencyIdConvert::convert(multi_location)
+	}
+}
+
+/// Metadata and location of `currency_id`, as returned by `AssetRegistryApi`.
+pub fn asset_info(
+	currency_id: CurrencyId,
+) -> Option<AssetInfo<CurrencyId, Balance, VersionedMultiLocation>> {
+	let metadata =
+		AssetRegistryMapping::get_asset_metadata(AssetIds::NativeAssetId(currency_id))?;
+	let location: Option<MultiLocation> = CurrencyIdConvert::convert(currency_id);
+	Some(AssetInfo {
+		currency_id,
+		name: metadata.name,
+		symbol: metadata.symbol,
+		decimals: metadata.decimals,
+		existential_deposit: metadata.minimal_balance,
+		location: location.map(VersionedMultiLocation::from),
+	})
+}
+
+/// Every native token and every foreign asset of `AssetRegistry`, as returned by
+/// `AssetRegistryApi`.
+pub fn assets() -> Vec<AssetInfo<CurrencyId, Balance, VersionedMultiLocation>> {
+	TokenSymbol::all()
+		.into_iter()
+		.map(CurrencyId::Token)
+		.chain(orml_asset_registry::Metadata::<Runtime>::iter_keys().map(ForeignAsset))
+		.filter_map(asset_info)
+		.collect()
+}
+
+/// The currency located at `location`, as returned by `AssetRegistryApi`.
+pub fn currency_id(location: VersionedMultiLocation) -> Option<CurrencyId> {
+	AssetRegistryMapping::get_currency_id(location.try_into().ok()?)
+}
+
 parameter_types! {
 	pub SelfLocation: MultiLocation = MultiLocation::new(1, X1(Parachain(ParachainInfo::get().into())));
 }
@@ -371,3 +442,134 @@ impl orml_xcm::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type SovereignOrigin = EnsureRootOrThreeFourthsCouncil;
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{CustomMetadata, System};
+
+	const ASSET: ForeignAssetId = 7;
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		let t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	fn asset_location() -> MultiLocation {
+		MultiLocation::new(1, X2(Parachain(1000), GeneralIndex(1984)))
+	}
+
+	fn local_location(currency_id: CurrencyId) -> MultiLocation {
+		native_currency_location(ParachainInfo::get().into(), currency_id.encode()).unwrap()
+	}
+
+	fn register_asset() {
+		let metadata = orml_asset_registry::AssetMetadata {
+			decimals: 6,
+			name: b"Tether USD".to_vec(),
+			symbol: b"USDT".to_vec(),
+			existential_deposit: 1_000,
+			location: Some(asset_location().into()),
+			additional: CustomMetadata { fee_per_second: 1_000_000 },
+		};
+		AssetRegistry::register_asset(RuntimeOrigin::root(), metadata, Some(ASSET)).unwrap();
+	}
+
+	/// `asset_info` of `currency_id` after going through the SCALE encoding of the runtime API.
+	fn asset_info_over_the_api(
+		currency_id: CurrencyId,
+	) -> Option<AssetInfo<CurrencyId, Balance, VersionedMultiLocation>> {
+		Decode::decode(&mut &asset_info(currency_id).encode()[..]).unwrap()
+	}
+
+	#[test]
+	fn native_tokens_should_round_trip() {
+		new_test_ext().execute_with(|| {
+			for token in [ZERO, GAME, PLAY, DOT] {
+				let info = asset_info_over_the_api(token).unwrap();
+				assert_eq!(info.currency_id, token);
+				assert_eq!(Some(&info.symbol[..]), token.symbol().map(str::as_bytes));
+				assert_eq!(Some(info.decimals), token.decimals());
+				assert_eq!(info.existential_deposit, ExistentialDeposits::get(&token));
+
+				let location = info.location.unwrap();
+				assert_eq!(currency_id(location.clone()), Some(token));
+				assert_eq!(
+					AssetRegistryMapping::get_currency_id(location.try_into().unwrap()),
+					Some(token)
+				);
+			}
+			assert_eq!(
+				asset_info_over_the_api(PLAY).unwrap().location,
+				Some(local_location(PLAY).into())
+			);
+			assert_eq!(
+				asset_info_over_the_api(DOT).unwrap().location,
+				Some(MultiLocation::parent().into())
+			);
+		});
+	}
+
+	#[test]
+	fn foreign_assets_should_round_trip() {
+		new_test_ext().execute_with(|| {
+			register_asset();
+			let asset = ForeignAsset(ASSET);
+
+			let metadata =
+				AssetRegistryMapping::get_asset_metadata(AssetIds::ForeignAssetId(ASSET));
+			assert_eq!(
+				metadata,
+				Some(AssetMetadata {
+					name: b"Tether USD".to_vec(),
+					symbol: b"USDT".to_vec(),
+					decimals: 6,
+					minimal_balance: 1_000,
+				})
+			);
+			assert_eq!(
+				AssetRegistryMapping::get_asset_metadata(AssetIds::NativeAssetId(asset)),
+				metadata
+			);
+			assert_eq!(AssetRegistryMapping::get_multi_location(ASSET), Some(asset_location()));
+			assert_eq!(AssetRegistryMapping::get_currency_id(asset_location()), Some(asset));
+
+			assert_eq!(
+				asset_info_over_the_api(asset),
+				Some(AssetInfo {
+					currency_id: asset,
+					name: b"Tether USD".to_vec(),
+					symbol: b"USDT".to_vec(),
+					decimals: 6,
+					existential_deposit: 1_000,
+					location: Some(asset_location().into()),
+				})
+			);
+			assert_eq!(currency_id(asset_location().into()), Some(asset));
+			assert_eq!(assets().len(), TokenSymbol::all().len() + 1);
+			assert_eq!(assets().last().map(|info| info.currency_id), Some(asset));
+		});
+	}
+
+	#[test]
+	fn unknown_assets_should_not_be_described() {
+		new_test_ext().execute_with(|| {
+			let unknown = ForeignAsset(ASSET + 1);
+			let location = MultiLocation::new(1, X2(Parachain(1000), GeneralIndex(1)));
+
+			assert_eq!(
+				AssetRegistryMapping::get_asset_metadata(AssetIds::NativeAssetId(unknown)),
+				None
+			);
+			assert_eq!(AssetRegistryMapping::get_multi_location(ASSET + 1), None);
+			assert_eq!(AssetRegistryMapping::get_currency_id(location), None);
+			assert_eq!(asset_info_over_the_api(unknown), None);
+			assert_eq!(currency_id(location.into()), None);
+			// DOT is not transferred as a token of this chain
+			assert_eq!(currency_id(local_location(DOT).into()), None);
+			assert_eq!(assets().len(), TokenSymbol::all().len());
+		});
+	}
+}
@@ -0,0 +1,843 @@
+//! Turns the JSON exports of balances and identities into SCALE encoded call data for the
+//! migration pallet, split into chunks which fit into a block.
+//!
+//! Every call is written as one hex encoded line, in the order it has to be dispatched. The calls
+//! require root, so they still have to be wrapped into `sudo` or a council motion.
+//!
+//! For an airdrop, `claims` builds the Merkle tree over the same exports instead and writes the
+//! root, the `start_airdrop` call and every leaf with its proof and `claim` call as JSON.
+
+use std::{
+	collections::{HashMap, HashSet},
+	fs,
+	io::Write,
+	mem,
+	path::PathBuf,
+};
+
+use clap::{Args, Parser, Subcommand};
+use codec::Encode;
+use frame_support::{traits::ConstU32, BoundedVec};
+use module_migration::{
+	merkle::{self, MerkleTree},
+	LockRecord, NamedReserveRecord, VestingRecord,
+};
+use pallet_balances::Reasons;
+use pallet_identity::{Data, IdentityInfo, Judgement, RegistrarIndex};
+use serde::Deserialize;
+use sp_core::{
+	crypto::{AccountId32, Ss58Codec},
+	hashing::blake2_256,
+	H256,
+};
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+type Balance = u128;
+/// The bounds of the target runtime are checked explicitly, the encoding does not depend on them.
+type Unbounded = ConstU32<{ u32::MAX }>;
+type BalanceRecord = module_migration::BalanceRecord<AccountId32, Balance, Unbounded, Unbounded>;
+type IdentityRecord =
+	module_migration::IdentityRecord<AccountId32, Balance, Unbounded, Unbounded, Unbounded>;
+type ClaimLeaf = module_migration::ClaimLeaf<AccountId32, Balance>;
+
+// Call indices of the migration pallet, in declaration order.
+const BEGIN_BALANCES_MIGRATION: u8 = 0;
+const MIGRATE_BALANCES: u8 = 1;
+const FINALIZE_BALANCES_MIGRATION: u8 = 2;
+const MIGRATE_IDENTITIES: u8 = 3;
+const FINALIZE_IDENTITIES_MIGRATION: u8 = 4;
+const START_AIRDROP: u8 = 5;
+const CLAIM: u8 = 6;
+
+/// Pallet and call index, the cursor and the compact length prefix of the records.
+const CALL_OVERHEAD: usize = 2 + 4 + 5;
+
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+	#[command(subcommand)]
+	command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+	/// Convert a balances export into `begin_balances_migration`, the `migrate_balances` chunks
+	/// and `finalize_balances_migration`.
+	Balances {
+		#[command(flatten)]
+		shared: SharedParams,
+		/// `MaxReserves` of the target runtime.
+		#[arg(long, default_value_t = 50)]
+		max_reserves: usize,
+		/// `MaxLocks` of the target runtime.
+		#[arg(long, default_value_t = 50)]
+		max_locks: usize,
+	},
+	/// Convert an identities export into the `migrate_identities` chunks and
+	/// `finalize_identities_migration`.
+	Identities {
+		#[command(flatten)]
+		shared: SharedParams,
+		#[command(flatten)]
+		limits: IdentityLimits,
+	},
+	/// Build the Merkle tree of an airdrop over a balances and an optional identities export.
+	Claims {
+		/// Balances export, every account claims its free plus reserved balance.
+		balances: PathBuf,
+		/// Identities export, an account claims its identity along with its balance.
+		#[arg(long)]
+		identities: Option<PathBuf>,
+		/// Index of the migration pallet in `construct_runtime!`.
+		#[arg(long)]
+		pallet_index: u8,
+		/// Block from which on claims are rejected.
+		#[arg(long)]
+		deadline: u32,
+		/// File to write the tree to, stdout if omitted.
+		#[arg(long, short)]
+		output: Option<PathBuf>,
+	},
+}
+
+#[derive(Args)]
+struct IdentityLimits {
+	/// `MaxAdditionalFields` of the target runtime.
+	#[arg(long, default_value_t = 100)]
+	max_additional_fields: usize,
+	/// `MaxRegistrars` of the target runtime.
+	#[arg(long, default_value_t = 20)]
+	max_registrars: usize,
+	/// `MaxSubAccounts` of the target runtime.
+	#[arg(long, default_value_t = 100)]
+	max_sub_accounts: usize,
+}
+
+impl IdentityLimits {
+	/// No limits, for identities which are not imported as a whole.
+	const NONE: Self = Self {
+		max_additional_fields: usize::MAX,
+		max_registrars: usize::MAX,
+		max_sub_accounts: usize::MAX,
+	};
+}
+
+#[derive(Args)]
+struct SharedParams {
+	/// JSON export to convert.
+	input: PathBuf,
+	/// Index of the migration pallet in `construct_runtime!`.
+	#[arg(long)]
+	pallet_index: u8,
+	/// `MaxChunkSize` of the target runtime.
+	#[arg(long, default_value_t = 500)]
+	max_items: usize,
+	/// Maximum encoded size of one call in bytes, must leave room for the extrinsic around it.
+	#[arg(long, default_value_t = 3 * 1024 * 1024)]
+	max_bytes: usize,
+	/// File to write the calls to, stdout if omitted.
+	#[arg(long, short)]
+	output: Option<PathBuf>,
+}
+
+/// Amounts are exported as strings, small ones sometimes as numbers.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Amount {
+	Number(u64),
+	Text(String),
+}
+
+impl Amount {
+	fn value(&self) -> Result<Balance> {
+		match self {
+			Amount::Number(n) => Ok((*n).into()),
+			Amount::Text(s) => s
+				.parse::<Balance>()
+				.map_err(|e| format!("invalid amount {:?}: {}", s, e).into()),
+		}
+	}
+}
+
+#[derive(Deserialize)]
+struct AccountItem {
+	account_id: String,
+	free_balance: Amount,
+	/// Total reserved balance, including `named_reserves`.
+	reserved_balance: Amount,
+	#[serde(default)]
+	named_reserves: Vec<NamedReserveItem>,
+	#[serde(default)]
+	locks: Vec<LockItem>,
+	#[serde(default)]
+	vesting: Option<VestingItem>,
+}
+
+#[derive(Deserialize)]
+struct NamedReserveItem {
+	/// 8 byte reserve identifier.
+	id: String,
+	amount: Amount,
+}
+
+#[derive(Deserialize)]
+struct LockItem {
+	/// 8 byte lock identifier, e.g. `"vesting "`.
+	id: String,
+	amount: Amount,
+	/// `"All"`, `"Fee"` or `"Misc"`, same as `pallet_balances::Reasons`.
+	reasons: String,
+}
+
+#[derive(Deserialize)]
+struct VestingItem {
+	locked: Amount,
+	per_block: Amount,
+	starting_block: u32,
+}
+
+#[derive(Deserialize)]
+struct IdentityItem {
+	account_id: String,
+	identity: RegistrationItem,
+	#[serde(default)]
+	subs: Option<SubsItem>,
+}
+
+#[derive(Deserialize)]
+struct RegistrationItem {
+	/// Pairs of registrar index and judgement, e.g. `[0, { "feePaid": "100" }]` or
+	/// `[1, "Reasonable"]`.
+	#[serde(default)]
+	judgements: Vec<(RegistrarIndex, serde_json::Value)>,
+	deposit: Amount,
+	info: InfoItem,
+}
+
+#[derive(Deserialize)]
+struct SubsItem {
+	deposit: Amount,
+	accounts: Vec<SubItem>,
+}
+
+#[derive(Deserialize)]
+struct SubItem {
+	account_id: String,
+	#[serde(default)]
+	name: FieldItem,
+}
+
+#[derive(Deserialize)]
+struct InfoItem {
+	#[serde(default)]
+	additional: Vec<(FieldItem, FieldItem)>,
+	#[serde(default)]
+	display: FieldItem,
+	#[serde(default)]
+	legal: FieldItem,
+	#[serde(default)]
+	web: FieldItem,
+	#[serde(default)]
+	riot: FieldItem,
+	#[serde(default)]
+	email: FieldItem,
+	/// Hex encoded 20 byte fingerprint.
+	#[serde(default)]
+	pgp_fingerprint: Option<String>,
+	#[serde(default)]
+	image: FieldItem,
+	#[serde(default)]
+	twitter: FieldItem,
+	#[serde(default)]
+	discord: FieldItem,
+	#[serde(default)]
+	steam: FieldItem,
+	#[serde(default)]
+	epic: FieldItem,
+	#[serde(default)]
+	twitch: FieldItem,
+}
+
+/// An identity field, only `{ "raw": "0x.." }` carries data, anything else is `Data::None`.
+#[derive(Deserialize, Default)]
+struct FieldItem {
+	#[serde(default)]
+	raw: Option<String>,
+}
+
+fn main() -> Result<()> {
+	match Cli::parse().command {
+		Command::Balances { shared, max_reserves, max_locks } => {
+			let items: Vec<AccountItem> = read_json(&shared.input)?;
+			let records = items
+				.iter()
+				.enumerate()
+				.map(|(index, item)| -> Result<_> {
+					balance_record(item, max_reserves, max_locks).map_err(|e| {
+						format!("account {} ({}): {}", index, item.account_id, e).into()
+					})
+				})
+				.collect::<Result<Vec<_>>>()?;
+
+			let mut calls =
+				vec![call(shared.pallet_index, BEGIN_BALANCES_MIGRATION, records.len() as u32)];
+			let mut cursor = 0u32;
+			for chunk in chunks(records, shared.max_items, shared.max_bytes)? {
+				let items = chunk.len() as u32;
+				calls.push(call(shared.pallet_index, MIGRATE_BALANCES, (cursor, chunk)));
+				cursor += items;
+			}
+			calls.push(call(shared.pallet_index, FINALIZE_BALANCES_MIGRATION, ()));
+			write_calls(&shared, &calls, cursor)
+		},
+		Command::Identities { shared, limits } => {
+			let items: Vec<IdentityItem> = read_json(&shared.input)?;
+			let records = items
+				.iter()
+				.enumerate()
+				.map(|(index, item)| -> Result<_> {
+					identity_record(item, &limits).map_err(|e| {
+						format!("identity {} ({}): {}", index, item.account_id, e).into()
+					})
+				})
+				.collect::<Result<Vec<_>>>()?;
+
+			let count = records.len() as u32;
+			let mut calls = Vec::new();
+			for chunk in chunks(records, shared.max_items, shared.max_bytes)? {
+				calls.push(call(shared.pallet_index, MIGRATE_IDENTITIES, chunk));
+			}
+			calls.push(call(shared.pallet_index, FINALIZE_IDENTITIES_MIGRATION, ()));
+			write_calls(&shared, &calls, count)
+		},
+		Command::Claims { balances, identities, pallet_index, deadline, output } => {
+			let accounts: Vec<AccountItem> = read_json(&balances)?;
+			let mut infos = HashMap::new();
+			if let Some(path) = identities {
+				for item in read_json::<Vec<IdentityItem>>(&path)? {
+					let record = identity_record(&item, &IdentityLimits::NONE)
+						.map_err(|e| format!("identity {}: {}", item.account_id, e))?;
+					infos.insert(record.who, record.info);
+				}
+			}
+
+			let mut total: Balance = 0;
+			let mut claims = Vec::new();
+			let mut seen = HashSet::new();
+			for (index, item) in accounts.iter().enumerate() {
+				let (leaf, info) = claim_leaf(item, &mut infos)
+					.map_err(|e| format!("account {} ({}): {}", index, item.account_id, e))?;
+				if !seen.insert(leaf.who.clone()) {
+					return Err(
+						format!("account {} ({}) is listed twice", index, item.account_id).into()
+					);
+				}
+				total = total.checked_add(leaf.amount).ok_or("total overflows")?;
+				claims.push((leaf, info));
+			}
+			if !infos.is_empty() {
+				return Err(format!("{} identities without a balance", infos.len()).into())
+			}
+
+			let tree =
+				MerkleTree::new(claims.iter().map(|(leaf, _)| merkle::leaf_hash(leaf)).collect())
+					.ok_or("no accounts to claim")?;
+			let claims = claims
+				.into_iter()
+				.enumerate()
+				.map(|(index, (leaf, info))| {
+					let proof = tree.proof(index).expect("one leaf per claim");
+					let hashes = proof.iter().map(hex_string).collect::<Vec<_>>();
+					let claim = call(pallet_index, CLAIM, (&leaf, info.map(Box::new), proof));
+					serde_json::json!({
+						"account_id": leaf.who.to_ss58check(),
+						"amount": leaf.amount.to_string(),
+						"proof": hashes,
+						"call": hex_string(claim),
+					})
+				})
+				.collect::<Vec<_>>();
+			let start = call(pallet_index, START_AIRDROP, (tree.root(), total, deadline));
+			let tree = serde_json::json!({
+				"root": hex_string(tree.root()),
+				"total": total.to_string(),
+				"start_airdrop": hex_string(start),
+				"claims": claims,
+			});
+
+			let mut out: Box<dyn Write> = match &output {
+				Some(path) => Box::new(fs::File::create(path)?),
+				None => Box::new(std::io::stdout()),
+			};
+			serde_json::to_writer_pretty(&mut out, &tree)?;
+			writeln!(out)?;
+			eprintln!("{} claims of {} in total", accounts.len(), total);
+			Ok(())
+		},
+	}
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &PathBuf) -> Result<T> {
+	let data = fs::read(path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+	serde_json::from_slice(&data)
+		.map_err(|e| format!("cannot parse {}: {}", path.display(), e).into())
+}
+
+fn write_calls(shared: &SharedParams, calls: &[Vec<u8>], records: u32) -> Result<()> {
+	let mut out: Box<dyn Write> = match &shared.output {
+		Some(path) => Box::new(fs::File::create(path)?),
+		None => Box::new(std::io::stdout()),
+	};
+	for call in calls {
+		writeln!(out, "0x{}", hex::encode(call))?;
+	}
+	eprintln!("{} records in {} calls", records, calls.len());
+	Ok(())
+}
+
+/// The leaf of an account, with the identity it commits to taken out of `infos`.
+fn claim_leaf(
+	item: &AccountItem,
+	infos: &mut HashMap<AccountId32, IdentityInfo<Unbounded>>,
+) -> Result<(ClaimLeaf, Option<IdentityInfo<Unbounded>>)> {
+	let who = account(&item.account_id)?;
+	let amount = item
+		.free_balance
+		.value()?
+		.checked_add(item.reserved_balance.value()?)
+		.ok_or("amount overflows")?;
+	let vesting = match &item.vesting {
+		Some(v) => Some(VestingRecord {
+			locked: v.locked.value()?,
+			per_block: v.per_block.value()?,
+			starting_block: v.starting_block,
+		}),
+		None => None,
+	};
+	if let Some(vesting) = &vesting {
+		if vesting.locked > amount || vesting.per_block == 0 {
+			return Err("vesting locks more than the amount or unlocks nothing per block".into())
+		}
+	}
+	let info = infos.remove(&who);
+	let identity_hash = info
+		.as_ref()
+		.map_or_else(H256::zero, |info| info.using_encoded(blake2_256).into());
+	Ok((ClaimLeaf { who, amount, vesting, identity_hash }, info))
+}
+
+fn hex_string(bytes: impl AsRef<[u8]>) -> String {
+	format!("0x{}", hex::encode(bytes))
+}
+
+/// SCALE encoded call of the migration pallet.
+fn call(pallet_index: u8, call_index: u8, args: impl Encode) -> Vec<u8> {
+	(pallet_index, call_index, args).encode()
+}
+
+/// Splits `records` into chunks of at most `max_items` records whose call is at most `max_bytes`
+/// long.
+fn chunks<R: Encode>(records: Vec<R>, max_items: usize, max_bytes: usize) -> Result<Vec<Vec<R>>> {
+	if max_items == 0 {
+		return Err("max-items must not be zero".into())
+	}
+
+	let mut chunks = Vec::new();
+	let mut chunk = Vec::new();
+	let mut size = CALL_OVERHEAD;
+	for (index, record) in records.into_iter().enumerate() {
+		let len = record.encoded_size();
+		if CALL_OVERHEAD + len > max_bytes {
+			return Err(format!("record {} alone exceeds {} bytes", index, max_bytes).into())
+		}
+		if chunk.len() == max_items || size + len > max_bytes {
+			chunks.push(mem::take(&mut chunk));
+			size = CALL_OVERHEAD;
+		}
+		size += len;
+		chunk.push(record);
+	}
+	if !chunk.is_empty() {
+		chunks.push(chunk);
+	}
+	Ok(chunks)
+}
+
+fn balance_record(
+	item: &AccountItem,
+	max_reserves: usize,
+	max_locks: usize,
+) -> Result<BalanceRecord> {
+	if item.named_reserves.len() > max_reserves {
+		return Err(format!("more than {} named reserves", max_reserves).into())
+	}
+	if item.locks.len() > max_locks {
+		return Err(format!("more than {} locks", max_locks).into())
+	}
+
+	let named_reserves = item
+		.named_reserves
+		.iter()
+		.map(|r| -> Result<_> {
+			Ok(NamedReserveRecord { id: identifier(&r.id)?, amount: r.amount.value()? })
+		})
+		.collect::<Result<Vec<_>>>()?;
+	let locks = item
+		.locks
+		.iter()
+		.map(|l| -> Result<_> {
+			let reasons = match l.reasons.as_str() {
+				"All" => Reasons::All,
+				"Fee" => Reasons::Fee,
+				"Misc" => Reasons::Misc,
+				other => return Err(format!("invalid lock reasons {:?}", other).into()),
+			};
+			Ok(LockRecord { id: identifier(&l.id)?, amount: l.amount.value()?, reasons })
+		})
+		.collect::<Result<Vec<_>>>()?;
+	let vesting = match &item.vesting {
+		Some(v) => Some(VestingRecord {
+			locked: v.locked.value()?,
+			per_block: v.per_block.value()?,
+			starting_block: v.starting_block,
+		}),
+		None => None,
+	};
+
+	Ok(BalanceRecord {
+		who: account(&item.account_id)?,
+		free: item.free_balance.value()?,
+		reserved: item.reserved_balance.value()?,
+		named_reserves: bounded(named_reserves)?,
+		locks: bounded(locks)?,
+		vesting,
+	})
+}
+
+fn identity_record(item: &IdentityItem, limits: &IdentityLimits) -> Result<IdentityRecord> {
+	let info = &item.identity.info;
+	if info.additional.len() > limits.max_additional_fields {
+		return Err(format!("more than {} additional fields", limits.max_additional_fields).into())
+	}
+	if item.identity.judgements.len() > limits.max_registrars {
+		return Err(format!("more than {} judgements", limits.max_registrars).into())
+	}
+	let sub_accounts = item.subs.as_ref().map_or(0, |subs| subs.accounts.len());
+	if sub_accounts > limits.max_sub_accounts {
+		return Err(format!("more than {} sub accounts", limits.max_sub_accounts).into())
+	}
+
+	let additional = info
+		.additional
+		.iter()
+		.map(|(key, value)| -> Result<_> { Ok((data(key)?, data(value)?)) })
+		.collect::<Result<Vec<_>>>()?;
+	let pgp_fingerprint = match &info.pgp_fingerprint {
+		Some(fingerprint) => Some(
+			hex_bytes(fingerprint)?
+				.try_into()
+				.map_err(|_| format!("pgp fingerprint {:?} is not 20 bytes", fingerprint))?,
+		),
+		None => None,
+	};
+	let judgements = item
+		.identity
+		.judgements
+		.iter()
+		.map(|(index, judgement)| -> Result<_> { Ok((*index, parse_judgement(judgement)?)) })
+		.collect::<Result<Vec<_>>>()?;
+	let (subs, subs_deposit) = match &item.subs {
+		Some(subs) => (
+			subs.accounts
+				.iter()
+				.map(|sub| -> Result<_> { Ok((account(&sub.account_id)?, data(&sub.name)?)) })
+				.collect::<Result<Vec<_>>>()?,
+			subs.deposit.value()?,
+		),
+		None => (Vec::new(), 0),
+	};
+
+	Ok(IdentityRecord {
+		who: account(&item.account_id)?,
+		deposit: item.identity.deposit.value()?,
+		judgements: bounded(judgements)?,
+		subs: bounded(subs)?,
+		subs_deposit,
+		info: IdentityInfo {
+			additional: bounded(additional)?,
+			display: data(&info.display)?,
+			legal: data(&info.legal)?,
+			web: data(&info.web)?,
+			riot: data(&info.riot)?,
+			email: data(&info.email)?,
+			pgp_fingerprint,
+			image: data(&info.image)?,
+			twitter: data(&info.twitter)?,
+			discord: data(&info.discord)?,
+			steam: data(&info.steam)?,
+			epic: data(&info.epic)?,
+			twitch: data(&info.twitch)?,
+		},
+	})
+}
+
+/// A judgement as exported by polkadot.js, either the bare variant name or an object with the
+/// variant as its only key, in any case.
+fn parse_judgement(judgement: &serde_json::Value) -> Result<Judgement<Balance>> {
+	let (variant, value) = match judgement {
+		serde_json::Value::String(variant) => (variant.as_str(), None),
+		serde_json::Value::Object(map) if map.len() == 1 => {
+			let (variant, value) = map.iter().next().expect("one entry");
+			(variant.as_str(), Some(value))
+		},
+		_ => return Err(format!("invalid judgement {}", judgement).into()),
+	};
+	Ok(match variant.to_ascii_lowercase().as_str() {
+		"unknown" => Judgement::Unknown,
+		"feepaid" => {
+			let fee = value.ok_or("judgement FeePaid without a fee")?;
+			Judgement::FeePaid(serde_json::from_value::<Amount>(fee.clone())?.value()?)
+		},
+		"reasonable" => Judgement::Reasonable,
+		"knowngood" => Judgement::KnownGood,
+		"outofdate" => Judgement::OutOfDate,
+		"lowquality" => Judgement::LowQuality,
+		"erroneous" => Judgement::Erroneous,
+		_ => return Err(format!("unknown judgement {}", judgement).into()),
+	})
+}
+
+/// An SS58 address of any network.
+fn account(address: &str) -> Result<AccountId32> {
+	AccountId32::from_ss58check_with_version(address)
+		.map(|(account, _)| account)
+		.map_err(|e| format!("invalid address {:?}: {:?}", address, e).into())
+}
+
+/// A lock or reserve identifier, exactly 8 bytes.
+fn identifier(id: &str) -> Result<[u8; 8]> {
+	id.as_bytes()
+		.try_into()
+		.map_err(|_| format!("identifier {:?} is not 8 bytes", id).into())
+}
+
+fn data(field: &FieldItem) -> Result<Data> {
+	match &field.raw {
+		Some(raw) => {
+			let bytes = hex_bytes(raw)?;
+			let len = bytes.len();
+			Ok(Data::Raw(
+				bytes.try_into().map_err(|_| format!("raw field of {} bytes exceeds 32", len))?,
+			))
+		},
+		None => Ok(Data::None),
+	}
+}
+
+fn hex_bytes(s: &str) -> Result<Vec<u8>> {
+	hex::decode(s.trim_start_matches("0x"))
+		.map_err(|e| format!("invalid hex {:?}: {}", s, e).into())
+}
+
+fn bounded<T>(items: Vec<T>) -> Result<BoundedVec<T, Unbounded>> {
+	items.try_into().map_err(|_| "too many items".into())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use codec::DecodeAll;
+
+	// Bounds of the target runtime, the records have to decode with them.
+	type MaxChunkSize = ConstU32<16>;
+	type RuntimeBalanceRecord =
+		module_migration::BalanceRecord<AccountId32, Balance, ConstU32<50>, ConstU32<50>>;
+	type RuntimeIdentityRecord = module_migration::IdentityRecord<
+		AccountId32,
+		Balance,
+		ConstU32<100>,
+		ConstU32<20>,
+		ConstU32<100>,
+	>;
+	type RuntimeIdentityInfo = IdentityInfo<ConstU32<100>>;
+
+	const PALLET_INDEX: u8 = 80;
+
+	fn alice() -> AccountId32 {
+		AccountId32::new([1; 32])
+	}
+
+	fn bob() -> AccountId32 {
+		AccountId32::new([2; 32])
+	}
+
+	fn raw(bytes: &[u8]) -> Data {
+		Data::Raw(bytes.to_vec().try_into().unwrap())
+	}
+
+	fn accounts() -> Vec<AccountItem> {
+		serde_json::from_str(&format!(
+			r#"[
+				{{
+					"account_id": "{}",
+					"free_balance": "1000000000000",
+					"reserved_balance": "200",
+					"named_reserves": [{{ "id": "staking ", "amount": "100" }}],
+					"locks": [{{ "id": "vesting ", "amount": "500", "reasons": "Misc" }}],
+					"vesting": {{ "locked": "500", "per_block": "1", "starting_block": 7 }}
+				}},
+				{{ "account_id": "{}", "free_balance": 5, "reserved_balance": 0 }}
+			]"#,
+			alice().to_ss58check(),
+			bob().to_ss58check(),
+		))
+		.unwrap()
+	}
+
+	fn identities() -> Vec<IdentityItem> {
+		serde_json::from_str(&format!(
+			r#"[{{
+				"account_id": "{}",
+				"identity": {{
+					"judgements": [[0, {{ "feePaid": "100" }}], [1, "Reasonable"]],
+					"deposit": 1000,
+					"info": {{
+						"display": {{ "raw": "0x616c696365" }},
+						"twitter": {{ "none": null }},
+						"pgp_fingerprint": "0x0102030405060708090a0b0c0d0e0f1011121314"
+					}}
+				}},
+				"subs": {{
+					"deposit": "200",
+					"accounts": [{{ "account_id": "{}", "name": {{ "raw": "0x626f62" }} }}]
+				}}
+			}}]"#,
+			alice().to_ss58check(),
+			bob().to_ss58check(),
+		))
+		.unwrap()
+	}
+
+	#[test]
+	fn balances_calls_should_decode_into_balance_records() {
+		let records = accounts()
+			.iter()
+			.map(|item| balance_record(item, 50, 50))
+			.collect::<Result<Vec<_>>>();
+		let chunks = chunks(records.unwrap(), 1, usize::MAX).unwrap();
+		assert_eq!(chunks.len(), 2);
+
+		let decoded = chunks
+			.into_iter()
+			.enumerate()
+			.map(|(cursor, chunk)| {
+				let encoded = call(PALLET_INDEX, MIGRATE_BALANCES, (cursor as u32, chunk));
+				<(u8, u8, u32, BoundedVec<RuntimeBalanceRecord, MaxChunkSize>)>::decode_all(
+					&mut &encoded[..],
+				)
+				.unwrap()
+			})
+			.collect::<Vec<_>>();
+
+		let (pallet_index, call_index, cursor, records) = &decoded[0];
+		assert_eq!((*pallet_index, *call_index, *cursor), (PALLET_INDEX, MIGRATE_BALANCES, 0));
+		let record = &records[0];
+		assert_eq!(record.who, alice());
+		assert_eq!((record.free, record.reserved), (1_000_000_000_000, 200));
+		assert_eq!(
+			record.named_reserves.to_vec(),
+			vec![NamedReserveRecord { id: *b"staking ", amount: 100 }]
+		);
+		assert_eq!(
+			record.locks.to_vec(),
+			vec![LockRecord { id: *b"vesting ", amount: 500, reasons: Reasons::Misc }]
+		);
+		assert_eq!(
+			record.vesting,
+			Some(VestingRecord { locked: 500, per_block: 1, starting_block: 7 })
+		);
+
+		let (_, _, cursor, records) = &decoded[1];
+		assert_eq!(*cursor, 1);
+		assert_eq!((records[0].who.clone(), records[0].free, records[0].reserved), (bob(), 5, 0));
+		assert!(records[0].named_reserves.is_empty() && records[0].locks.is_empty());
+		assert_eq!(records[0].vesting, None);
+	}
+
+	#[test]
+	fn identities_calls_should_decode_into_identity_records() {
+		let limits = IdentityLimits {
+			max_additional_fields: 100,
+			max_registrars: 20,
+			max_sub_accounts: 100,
+		};
+		let records = identities()
+			.iter()
+			.map(|item| identity_record(item, &limits))
+			.collect::<Result<Vec<_>>>()
+			.unwrap();
+		let encoded = call(PALLET_INDEX, MIGRATE_IDENTITIES, records);
+
+		let (_, call_index, records) =
+			<(u8, u8, BoundedVec<RuntimeIdentityRecord, MaxChunkSize>)>::decode_all(
+				&mut &encoded[..],
+			)
+			.unwrap();
+		assert_eq!(call_index, MIGRATE_IDENTITIES);
+		let record = &records[0];
+		assert_eq!(record.who, alice());
+		assert_eq!(record.deposit, 1000);
+		assert_eq!(
+			record.judgements.to_vec(),
+			vec![(0, Judgement::FeePaid(100)), (1, Judgement::Reasonable)]
+		);
+		assert_eq!(record.subs.to_vec(), vec![(bob(), raw(b"bob"))]);
+		assert_eq!(record.subs_deposit, 200);
+		assert_eq!(record.info.display, raw(b"alice"));
+		assert_eq!(record.info.twitter, Data::None);
+		assert_eq!(
+			record.info.pgp_fingerprint,
+			Some([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20])
+		);
+	}
+
+	#[test]
+	fn claim_calls_should_decode_and_verify_against_the_root() {
+		let mut infos = identities()
+			.iter()
+			.map(|item| {
+				let record = identity_record(item, &IdentityLimits::NONE).unwrap();
+				(record.who, record.info)
+			})
+			.collect::<HashMap<_, _>>();
+		let claims = accounts()
+			.iter()
+			.map(|item| claim_leaf(item, &mut infos).unwrap())
+			.collect::<Vec<_>>();
+		let tree =
+			MerkleTree::new(claims.iter().map(|(leaf, _)| merkle::leaf_hash(leaf)).collect())
+				.unwrap();
+
+		for (index, (leaf, info)) in claims.into_iter().enumerate() {
+			let proof = tree.proof(index).unwrap();
+			let encoded = call(PALLET_INDEX, CLAIM, (&leaf, info.map(Box::new), proof));
+			let (_, call_index, decoded, identity, proof) = <(
+				u8,
+				u8,
+				module_migration::ClaimLeaf<AccountId32, Balance>,
+				Option<Box<RuntimeIdentityInfo>>,
+				BoundedVec<H256, ConstU32<32>>,
+			)>::decode_all(&mut &encoded[..])
+			.unwrap();
+
+			assert_eq!(call_index, CLAIM);
+			assert_eq!(decoded, leaf);
+			assert!(merkle::verify(&tree.root(), merkle::leaf_hash(&decoded), &proof));
+			let identity_hash = identity
+				.as_ref()
+				.map_or_else(H256::zero, |info| info.using_encoded(blake2_256).into());
+			assert_eq!(identity_hash, decoded.identity_hash);
+		}
+	}
+}
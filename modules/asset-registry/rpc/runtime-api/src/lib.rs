@@ -0,0 +1,37 @@
+//! Runtime API definition for the asset registry.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::{traits::MaybeDisplay, RuntimeDebug};
+use sp_std::vec::Vec;
+
+/// Everything a wallet needs to know to display and transfer an asset.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct AssetInfo<CurrencyId, Balance, Location> {
+	pub currency_id: CurrencyId,
+	pub name: Vec<u8>,
+	pub symbol: Vec<u8>,
+	pub decimals: u8,
+	pub existential_deposit: Balance,
+	/// Where the asset is located for XCM, `None` if it cannot be transferred cross-chain.
+	pub location: Option<Location>,
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait AssetRegistryApi<CurrencyId, Balance, Location> where
+		CurrencyId: Codec,
+		Balance: Codec + MaybeDisplay,
+		Location: Codec,
+	{
+		/// Every native token and every registered foreign asset.
+		fn assets() -> Vec<AssetInfo<CurrencyId, Balance, Location>>;
+
+		/// A single asset, `None` if it is unknown.
+		fn asset(currency_id: CurrencyId) -> Option<AssetInfo<CurrencyId, Balance, Location>>;
+
+		/// The currency located at `location`.
+		fn currency_id(location: Location) -> Option<CurrencyId>;
+	}
+}
@@ -0,0 +1,181 @@
+//! RPC interface for the asset registry.
+
+use std::{convert::TryInto, sync::Arc};
+
+use codec::{Codec, Decode, Encode};
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorCode, ErrorObject},
+};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::{Block as BlockT, MaybeDisplay};
+
+pub use module_asset_registry_rpc_runtime_api::{
+	AssetInfo, AssetRegistryApi as AssetRegistryRuntimeApi,
+};
+
+/// An asset as returned over RPC.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetDetails<CurrencyId> {
+	pub currency_id: CurrencyId,
+	pub name: String,
+	pub symbol: String,
+	pub decimals: u8,
+	pub existential_deposit: NumberOrHex,
+	/// SCALE encoded `VersionedMultiLocation`.
+	pub location: Option<Bytes>,
+}
+
+#[rpc(client, server)]
+pub trait AssetRegistryApi<BlockHash, CurrencyId> {
+	/// Every native token and every registered foreign asset.
+	#[method(name = "assetRegistry_assets")]
+	fn assets(&self, at: Option<BlockHash>) -> RpcResult<Vec<AssetDetails<CurrencyId>>>;
+
+	/// A single asset, `null` if it is unknown.
+	#[method(name = "assetRegistry_asset")]
+	fn asset(
+		&self,
+		currency_id: CurrencyId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<AssetDetails<CurrencyId>>>;
+
+	/// The currency located at a SCALE encoded `VersionedMultiLocation`.
+	#[method(name = "assetRegistry_currencyId")]
+	fn currency_id(
+		&self,
+		encoded_location: Bytes,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<CurrencyId>>;
+}
+
+/// Provides RPC methods to list the assets known to the chain.
+///
+/// `Balance` and `Location` are the types of the runtime API, the RPC methods expose them as
+/// `NumberOrHex` and SCALE encoded bytes.
+pub struct AssetRegistry<C, P, Balance, Location> {
+	/// Shared reference to the client.
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<(P, Balance, Location)>,
+}
+
+impl<C, P, Balance, Location> AssetRegistry<C, P, Balance, Location> {
+	/// Creates a new instance of the AssetRegistry Rpc helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The location was not decodable.
+	DecodeError,
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::DecodeError => 2,
+		}
+	}
+}
+
+fn runtime_error(e: impl std::fmt::Display) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(
+		Error::RuntimeError.into(),
+		"Unable to query the asset registry.",
+		Some(e.to_string()),
+	))
+	.into()
+}
+
+fn into_details<CurrencyId, Balance, Location>(
+	info: AssetInfo<CurrencyId, Balance, Location>,
+) -> RpcResult<AssetDetails<CurrencyId>>
+where
+	Balance: TryInto<NumberOrHex> + Copy + MaybeDisplay,
+	Location: Encode,
+{
+	let existential_deposit = info.existential_deposit.try_into().map_err(|_| {
+		CallError::Custom(ErrorObject::owned(
+			ErrorCode::InternalError.code(),
+			format!("{} doesn't fit in NumberOrHex representation", info.existential_deposit),
+			None::<()>,
+		))
+	})?;
+
+	Ok(AssetDetails {
+		currency_id: info.currency_id,
+		name: String::from_utf8_lossy(&info.name).into_owned(),
+		symbol: String::from_utf8_lossy(&info.symbol).into_owned(),
+		decimals: info.decimals,
+		existential_deposit,
+		location: info.location.map(|location| location.encode().into()),
+	})
+}
+
+#[async_trait]
+impl<C, Block, CurrencyId, Balance, Location>
+	AssetRegistryApiServer<<Block as BlockT>::Hash, CurrencyId>
+	for AssetRegistry<C, Block, Balance, Location>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: AssetRegistryRuntimeApi<Block, CurrencyId, Balance, Location>,
+	CurrencyId: Codec + Serialize + serde::de::DeserializeOwned + Send + Sync + 'static,
+	Balance: Codec + MaybeDisplay + Copy + TryInto<NumberOrHex> + Send + Sync + 'static,
+	Location: Codec + Send + Sync + 'static,
+{
+	fn assets(&self, at: Option<Block::Hash>) -> RpcResult<Vec<AssetDetails<CurrencyId>>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.assets(at_hash)
+			.map_err(runtime_error)?
+			.into_iter()
+			.map(into_details)
+			.collect()
+	}
+
+	fn asset(
+		&self,
+		currency_id: CurrencyId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<AssetDetails<CurrencyId>>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.asset(at_hash, currency_id)
+			.map_err(runtime_error)?
+			.map(into_details)
+			.transpose()
+	}
+
+	fn currency_id(
+		&self,
+		encoded_location: Bytes,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<CurrencyId>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let location = Location::decode(&mut &*encoded_location).map_err(|e| {
+			CallError::Custom(ErrorObject::owned(
+				Error::DecodeError.into(),
+				"Unable to query the asset registry.",
+				Some(format!("{:?}", e)),
+			))
+		})?;
+
+		api.currency_id(at_hash, location).map_err(runtime_error)
+	}
+}
@@ -0,0 +1,55 @@
+//! Runtime API definition for the identity pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// Number of judgements of an identity of every kind.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct JudgementSummary {
+	/// Requested judgements whose fee is paid.
+	pub pending: u32,
+	pub reasonable: u32,
+	pub known_good: u32,
+	pub out_of_date: u32,
+	pub low_quality: u32,
+	pub erroneous: u32,
+}
+
+/// What a client needs to display an account.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct IdentitySummary<AccountId> {
+	/// The raw display name, `parent/sub` for sub-identities. `None` if the display name is not
+	/// stored raw.
+	pub display: Option<Vec<u8>>,
+	/// The judgements of the identity, of the super-identity for sub-identities.
+	pub judgements: JudgementSummary,
+	/// The account of the super-identity, `None` unless the account is a sub-identity.
+	pub super_account: Option<AccountId>,
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait IdentityApi<AccountId> where
+		AccountId: Codec,
+	{
+		/// The identity of every account of `accounts`, in the same order. `None` for accounts
+		/// that have no identity and are no sub-identity either.
+		fn resolve_accounts(accounts: Vec<AccountId>) -> Vec<Option<IdentitySummary<AccountId>>>;
+	}
+
+	pub trait UsernameApi<AccountId> where
+		AccountId: Codec,
+	{
+		/// Account owning the unexpired `username`, matched case-insensitively.
+		fn account_of_username(username: Vec<u8>) -> Option<AccountId>;
+		/// Primary username of `who`, unless it expired.
+		fn username_of(who: AccountId) -> Option<Vec<u8>>;
+	}
+}
@@ -0,0 +1,119 @@
+//! RPC interface for the identity pallet.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+pub use pallet_identity_rpc_runtime_api::{
+	IdentityApi as IdentityRuntimeApi, IdentitySummary, JudgementSummary,
+};
+
+/// Maximum number of accounts resolved by a single call.
+pub const MAX_ACCOUNTS: usize = 1_000;
+
+/// The identity of an account as returned over RPC.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct IdentityDetails<AccountId> {
+	/// The display name, `parent/sub` for sub-identities.
+	pub display: Option<String>,
+	pub judgements: JudgementSummary,
+	pub super_account: Option<AccountId>,
+}
+
+#[rpc(client, server)]
+pub trait IdentityApi<BlockHash, AccountId> {
+	/// The identity of every account of `accounts`, in the same order, `null` for accounts that
+	/// have no identity and are no sub-identity either.
+	#[method(name = "identity_resolveAccounts")]
+	fn resolve_accounts(
+		&self,
+		accounts: Vec<AccountId>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<Option<IdentityDetails<AccountId>>>>;
+}
+
+/// Provides RPC methods to resolve the identities of accounts.
+pub struct Identity<C, P> {
+	/// Shared reference to the client.
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> Identity<C, P> {
+	/// Creates a new instance of the Identity Rpc helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// More accounts than `MAX_ACCOUNTS` were requested.
+	TooManyAccounts,
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::TooManyAccounts => 2,
+		}
+	}
+}
+
+fn into_details<AccountId>(summary: IdentitySummary<AccountId>) -> IdentityDetails<AccountId> {
+	IdentityDetails {
+		display: summary.display.map(|display| String::from_utf8_lossy(&display).into_owned()),
+		judgements: summary.judgements,
+		super_account: summary.super_account,
+	}
+}
+
+#[async_trait]
+impl<C, Block, AccountId> IdentityApiServer<<Block as BlockT>::Hash, AccountId>
+	for Identity<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: IdentityRuntimeApi<Block, AccountId>,
+	AccountId: Codec + Serialize + serde::de::DeserializeOwned + Send + Sync + 'static,
+{
+	fn resolve_accounts(
+		&self,
+		accounts: Vec<AccountId>,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Vec<Option<IdentityDetails<AccountId>>>> {
+		if accounts.len() > MAX_ACCOUNTS {
+			return Err(CallError::Custom(ErrorObject::owned(
+				Error::TooManyAccounts.into(),
+				format!("At most {} accounts can be resolved at once.", MAX_ACCOUNTS),
+				Some(accounts.len()),
+			))
+			.into())
+		}
+
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let identities = api.resolve_accounts(at_hash, accounts).map_err(|e| {
+			CallError::Custom(ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to resolve the accounts.",
+				Some(e.to_string()),
+			))
+		})?;
+		Ok(identities.into_iter().map(|identity| identity.map(into_details)).collect())
+	}
+}
@@ -28,16 +28,24 @@ use frame_system::RawOrigin;
 use sp_runtime::traits::Bounded;
 
 const SEED: u32 = 0;
+// Upper bound of the requests pending with a registrar in the `remove_registrar` benchmark.
+const MAX_PENDING_REQUESTS: u32 = 100;
 
-fn assert_last_event<T: Config>(generic_event: <T as Config>::Event) {
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
 	frame_system::Pallet::<T>::assert_last_event(generic_event.into());
 }
 
+// Gives `who` enough of the native currency for any deposit and fee.
+fn fund<T: Config>(who: &T::AccountId) {
+	let balance = BalanceOf::<T>::max_value() / BalanceOf::<T>::from(1_000_000u32);
+	let _ = T::Currency::deposit(T::NativeCurrencyId::get(), who, balance);
+}
+
 // Adds `r` registrars to the Identity Pallet. These registrars will have set fees and fields.
 fn add_registrars<T: Config>(r: u32) -> Result<(), &'static str> {
 	for i in 0..r {
 		let registrar: T::AccountId = account("registrar", i, SEED);
-		let _ = T::Currency::make_free_balance_be(&registrar, BalanceOf::<T>::max_value());
+		fund::<T>(&registrar);
 		Identity::<T>::add_registrar(RawOrigin::Root.into(), registrar.clone())?;
 		Identity::<T>::set_fee(
 			RawOrigin::Signed(registrar.clone()).into(),
@@ -50,7 +58,9 @@ fn add_registrars<T: Config>(r: u32) -> Result<(), &'static str> {
 					IdentityField::Legal | IdentityField::Web |
 					IdentityField::Riot | IdentityField::Email |
 					IdentityField::PgpFingerprint |
-					IdentityField::Image | IdentityField::Twitter,
+					IdentityField::Image | IdentityField::Twitter |
+					IdentityField::Discord | IdentityField::Steam |
+					IdentityField::Epic | IdentityField::Twitch,
 			);
 		Identity::<T>::set_fields(RawOrigin::Signed(registrar.clone()).into(), i.into(), fields)?;
 	}
@@ -75,7 +85,7 @@ fn create_sub_accounts<T: Config>(
 	}
 
 	// Set identity so `set_subs` does not fail.
-	let _ = T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value());
+	fund::<T>(&who);
 	let info = create_identity_info::<T>(1);
 	Identity::<T>::set_identity(who_origin.clone().into(), Box::new(info))?;
 
@@ -111,11 +121,47 @@ fn create_identity_info<T: Config>(num_fields: u32) -> IdentityInfo<T::MaxAdditi
 		pgp_fingerprint: Some([0; 20]),
 		image: data.clone(),
 		twitter: data.clone(),
+		discord: data.clone(),
+		steam: data.clone(),
+		epic: data.clone(),
+		twitch: data.clone(),
 	};
 
 	return info
 }
 
+// A suffix of the maximum length.
+fn username_authority<T: Config>() -> Suffix<T> {
+	vec![b'a'; T::MaxSuffixLength::get() as usize].try_into().unwrap()
+}
+
+// Adds an authority for `suffix` and returns its account.
+fn add_username_authority<T: Config>(suffix: &Suffix<T>) -> Result<T::AccountId, &'static str> {
+	let authority: T::AccountId = account("authority", 0, SEED);
+	fund::<T>(&authority);
+	Identity::<T>::add_username_authority(
+		RawOrigin::Root.into(),
+		suffix.clone(),
+		T::Lookup::unlookup(authority.clone()),
+		10,
+	)?;
+	Ok(authority)
+}
+
+// A username which still fits with a suffix of the maximum length.
+fn bench_username<T: Config>() -> Username<T> {
+	let len = T::MaxUsernameLength::get().saturating_sub(T::MaxSuffixLength::get() + 1).max(1);
+	vec![b'b'; len as usize].try_into().unwrap()
+}
+
+// Registers `bench_username` for `who` and returns it.
+fn set_bench_username<T: Config>(who: &T::AccountId) -> Result<Username<T>, &'static str> {
+	fund::<T>(who);
+	let username = bench_username::<T>();
+	Identity::<T>::set_username(RawOrigin::Signed(who.clone()).into(), username.clone())?;
+	Ok(username)
+}
+
 benchmarks! {
 	add_registrar {
 		let r in 1 .. T::MaxRegistrars::get() - 1 => add_registrars::<T>(r)?;
@@ -125,6 +171,24 @@ benchmarks! {
 		ensure!(Registrars::<T>::get().len() as u32 == r + 1, "Registrars not added.");
 	}
 
+	remove_registrar {
+		let r in 1 .. T::MaxRegistrars::get() => add_registrars::<T>(r)?;
+		let p in 0 .. MAX_PENDING_REQUESTS;
+		let index = r - 1;
+		for i in 0..p {
+			let requester: T::AccountId = account("requester", i, SEED);
+			let requester_origin: <T as frame_system::Config>::RuntimeOrigin = RawOrigin::Signed(requester.clone()).into();
+			fund::<T>(&requester);
+			Identity::<T>::set_identity(requester_origin.clone(), Box::new(create_identity_info::<T>(1)))?;
+			Identity::<T>::request_judgement(requester_origin, index, 10u32.into())?;
+		}
+		ensure!(PendingRequests::<T>::iter_key_prefix(index).count() as u32 == p, "Requests not set up correctly.");
+	}: _(RawOrigin::Root, index, p)
+	verify {
+		ensure!(Registrars::<T>::get()[index as usize].is_none(), "Registrar not removed.");
+		ensure!(PendingRequests::<T>::iter_key_prefix(index).count() == 0, "Requests not refunded.");
+	}
+
 	set_identity {
 		let r in 1 .. T::MaxRegistrars::get() => add_registrars::<T>(r)?;
 		let x in 1 .. T::MaxAdditionalFields::get();
@@ -132,8 +196,8 @@ benchmarks! {
 			// The target user
 			let caller: T::AccountId = whitelisted_caller();
 			let caller_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(caller.clone());
-			let caller_origin: <T as frame_system::Config>::Origin = RawOrigin::Signed(caller.clone()).into();
-			let _ = T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+			let caller_origin: <T as frame_system::Config>::RuntimeOrigin = RawOrigin::Signed(caller.clone()).into();
+			fund::<T>(&caller);
 
 			// Add an initial identity
 			let initial_info = create_identity_info::<T>(1);
@@ -189,9 +253,9 @@ benchmarks! {
 
 	clear_identity {
 		let caller: T::AccountId = whitelisted_caller();
-		let caller_origin = <T as frame_system::Config>::Origin::from(RawOrigin::Signed(caller.clone()));
+		let caller_origin = <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(caller.clone()));
 		let caller_lookup = <T::Lookup as StaticLookup>::unlookup(caller.clone());
-		let _ = T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		fund::<T>(&caller);
 
 		let r in 1 .. T::MaxRegistrars::get() => add_registrars::<T>(r)?;
 		let s in 1 .. T::MaxSubAccounts::get() => {
@@ -203,7 +267,7 @@ benchmarks! {
 			// Create their main identity with x additional fields
 			let info = create_identity_info::<T>(x);
 			let caller: T::AccountId = whitelisted_caller();
-			let caller_origin = <T as frame_system::Config>::Origin::from(RawOrigin::Signed(caller));
+			let caller_origin = <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(caller));
 			Identity::<T>::set_identity(caller_origin, Box::new(info))?;
 		};
 
@@ -225,14 +289,14 @@ benchmarks! {
 
 	request_judgement {
 		let caller: T::AccountId = whitelisted_caller();
-		let _ = T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		fund::<T>(&caller);
 
 		let r in 1 .. T::MaxRegistrars::get() => add_registrars::<T>(r)?;
 		let x in 1 .. T::MaxAdditionalFields::get() => {
 			// Create their main identity with x additional fields
 			let info = create_identity_info::<T>(x);
 			let caller: T::AccountId = whitelisted_caller();
-			let caller_origin = <T as frame_system::Config>::Origin::from(RawOrigin::Signed(caller));
+			let caller_origin = <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(caller));
 			Identity::<T>::set_identity(caller_origin, Box::new(info))?;
 		};
 	}: _(RawOrigin::Signed(caller.clone()), r - 1, 10u32.into())
@@ -242,15 +306,15 @@ benchmarks! {
 
 	cancel_request {
 		let caller: T::AccountId = whitelisted_caller();
-		let caller_origin = <T as frame_system::Config>::Origin::from(RawOrigin::Signed(caller.clone()));
-		let _ = T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		let caller_origin = <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(caller.clone()));
+		fund::<T>(&caller);
 
 		let r in 1 .. T::MaxRegistrars::get() => add_registrars::<T>(r)?;
 		let x in 1 .. T::MaxAdditionalFields::get() => {
 			// Create their main identity with x additional fields
 			let info = create_identity_info::<T>(x);
 			let caller: T::AccountId = whitelisted_caller();
-			let caller_origin = <T as frame_system::Config>::Origin::from(RawOrigin::Signed(caller));
+			let caller_origin = <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(caller));
 			Identity::<T>::set_identity(caller_origin, Box::new(info))?;
 		};
 
@@ -276,7 +340,7 @@ benchmarks! {
 
 	set_account_id {
 		let caller: T::AccountId = whitelisted_caller();
-		let _ = T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		fund::<T>(&caller);
 
 		let r in 1 .. T::MaxRegistrars::get() - 1 => add_registrars::<T>(r)?;
 
@@ -291,7 +355,7 @@ benchmarks! {
 
 	set_fields {
 		let caller: T::AccountId = whitelisted_caller();
-		let _ = T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		fund::<T>(&caller);
 
 		let r in 1 .. T::MaxRegistrars::get() - 1 => add_registrars::<T>(r)?;
 
@@ -299,6 +363,7 @@ benchmarks! {
 		let fields = IdentityFields(
 			IdentityField::Display | IdentityField::Legal | IdentityField::Web | IdentityField::Riot
 			| IdentityField::Email | IdentityField::PgpFingerprint | IdentityField::Image | IdentityField::Twitter
+			| IdentityField::Discord | IdentityField::Steam | IdentityField::Epic | IdentityField::Twitch
 		);
 		let registrars = Registrars::<T>::get();
 		ensure!(registrars[r as usize].as_ref().unwrap().fields == Default::default(), "fields already set.");
@@ -311,12 +376,12 @@ benchmarks! {
 	provide_judgement {
 		// The user
 		let user: T::AccountId = account("user", r, SEED);
-		let user_origin = <T as frame_system::Config>::Origin::from(RawOrigin::Signed(user.clone()));
+		let user_origin = <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(user.clone()));
 		let user_lookup = <T::Lookup as StaticLookup>::unlookup(user.clone());
-		let _ = T::Currency::make_free_balance_be(&user, BalanceOf::<T>::max_value());
+		fund::<T>(&user);
 
 		let caller: T::AccountId = whitelisted_caller();
-		let _ = T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		fund::<T>(&caller);
 
 		let r in 1 .. T::MaxRegistrars::get() - 1 => add_registrars::<T>(r)?;
 		let x in 1 .. T::MaxAdditionalFields::get() => {
@@ -337,9 +402,9 @@ benchmarks! {
 		let x in 1 .. T::MaxAdditionalFields::get();
 
 		let target: T::AccountId = account("target", 0, SEED);
-		let target_origin: <T as frame_system::Config>::Origin = RawOrigin::Signed(target.clone()).into();
+		let target_origin: <T as frame_system::Config>::RuntimeOrigin = RawOrigin::Signed(target.clone()).into();
 		let target_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(target.clone());
-		let _ = T::Currency::make_free_balance_be(&target, BalanceOf::<T>::max_value());
+		fund::<T>(&target);
 
 		let info = create_identity_info::<T>(x);
 		Identity::<T>::set_identity(target_origin.clone(), Box::new(info))?;
@@ -411,5 +476,237 @@ benchmarks! {
 		ensure!(!SuperOf::<T>::contains_key(&caller), "Sub not removed");
 	}
 
+	add_username_authority {
+		let suffix = username_authority::<T>();
+		let authority: T::AccountId = account("authority", 0, SEED);
+	}: _(RawOrigin::Root, suffix.clone(), T::Lookup::unlookup(authority.clone()), 10)
+	verify {
+		assert_last_event::<T>(Event::<T>::AuthorityAdded { authority, suffix }.into());
+	}
+
+	remove_username_authority {
+		let suffix = username_authority::<T>();
+		add_username_authority::<T>(&suffix)?;
+	}: _(RawOrigin::Root, suffix.clone())
+	verify {
+		ensure!(!UsernameAuthorities::<T>::contains_key(&suffix), "Authority not removed.");
+	}
+
+	set_username {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let username = bench_username::<T>();
+	}: _(RawOrigin::Signed(caller.clone()), username.clone())
+	verify {
+		ensure!(PrimaryUsernameOf::<T>::get(&caller) == Some(username), "Username not set.");
+	}
+
+	grant_username {
+		let suffix = username_authority::<T>();
+		let authority = add_username_authority::<T>(&suffix)?;
+		let who: T::AccountId = account("target", 0, SEED);
+		let username = bench_username::<T>();
+	}: _(RawOrigin::Signed(authority), suffix, T::Lookup::unlookup(who.clone()), username)
+	verify {
+		ensure!(PrimaryUsernameOf::<T>::contains_key(&who), "Username not granted.");
+	}
+
+	renew_username {
+		let caller: T::AccountId = whitelisted_caller();
+		let username = set_bench_username::<T>(&caller)?;
+		frame_system::Pallet::<T>::set_block_number(10u32.into());
+	}: _(RawOrigin::Signed(caller), username.clone())
+	verify {
+		let expiry = frame_system::Pallet::<T>::block_number() + T::UsernameDuration::get();
+		assert_last_event::<T>(Event::<T>::UsernameRenewed { username, expiry }.into());
+	}
+
+	set_primary_username {
+		let caller: T::AccountId = whitelisted_caller();
+		let username = set_bench_username::<T>(&caller)?;
+		PrimaryUsernameOf::<T>::remove(&caller);
+	}: _(RawOrigin::Signed(caller.clone()), username.clone())
+	verify {
+		ensure!(PrimaryUsernameOf::<T>::get(&caller) == Some(username), "Primary not set.");
+	}
+
+	release_username {
+		let caller: T::AccountId = whitelisted_caller();
+		let username = set_bench_username::<T>(&caller)?;
+	}: _(RawOrigin::Signed(caller.clone()), username.clone())
+	verify {
+		ensure!(!AccountOfUsername::<T>::contains_key(&username), "Username not released.");
+		ensure!(!PrimaryUsernameOf::<T>::contains_key(&caller), "Primary not removed.");
+	}
+
+	set_judgement_period {
+		let caller: T::AccountId = whitelisted_caller();
+
+		let r in 1 .. T::MaxRegistrars::get() - 1 => add_registrars::<T>(r)?;
+
+		Identity::<T>::add_registrar(RawOrigin::Root.into(), caller.clone())?;
+		let registrars = Registrars::<T>::get();
+		ensure!(registrars[r as usize].as_ref().unwrap().judgement_period.is_none(), "period already set.");
+	}: _(RawOrigin::Signed(caller), r, Some(T::BlockNumber::one()))
+	verify {
+		let registrars = Registrars::<T>::get();
+		ensure!(registrars[r as usize].as_ref().unwrap().judgement_period.is_some(), "period not set.");
+	}
+
+	expire_judgement {
+		let user: T::AccountId = account("user", 0, SEED);
+		let user_origin = <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(user.clone()));
+		let user_lookup = <T::Lookup as StaticLookup>::unlookup(user.clone());
+		fund::<T>(&user);
+
+		let caller: T::AccountId = whitelisted_caller();
+		let caller_origin: <T as frame_system::Config>::RuntimeOrigin = RawOrigin::Signed(caller.clone()).into();
+
+		let r in 1 .. T::MaxRegistrars::get() - 1 => add_registrars::<T>(r)?;
+		let x in 1 .. T::MaxAdditionalFields::get() => {
+			let info = create_identity_info::<T>(x);
+			Identity::<T>::set_identity(user_origin.clone(), Box::new(info))?;
+		};
+
+		Identity::<T>::add_registrar(RawOrigin::Root.into(), caller.clone())?;
+		Identity::<T>::set_judgement_period(caller_origin.clone(), r, Some(T::BlockNumber::one()))?;
+		Identity::<T>::provide_judgement(caller_origin, r, user_lookup, Judgement::Reasonable)?;
+		let expiry = JudgementExpiries::<T>::get(r, &user).ok_or("Expiry not set.")?;
+		ExpiryCursor::<T>::put(expiry);
+	}: {
+		Identity::<T>::expire_judgements(expiry, Weight::MAX);
+	}
+	verify {
+		assert_last_event::<T>(Event::<T>::JudgementExpired { target: user, registrar_index: r }.into())
+	}
+
+	poke_deposit {
+		let caller: T::AccountId = whitelisted_caller();
+		let caller_origin = <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(caller.clone()));
+		let caller_lookup = <T::Lookup as StaticLookup>::unlookup(caller.clone());
+		fund::<T>(&caller);
+
+		let r in 1 .. T::MaxRegistrars::get() => add_registrars::<T>(r)?;
+		let s in 1 .. T::MaxSubAccounts::get() => {
+			let caller: T::AccountId = whitelisted_caller();
+			let _ = add_sub_accounts::<T>(&caller, s)?;
+		};
+		let x in 1 .. T::MaxAdditionalFields::get() => {
+			let info = create_identity_info::<T>(x);
+			let caller: T::AccountId = whitelisted_caller();
+			let caller_origin = <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(caller));
+			Identity::<T>::set_identity(caller_origin, Box::new(info))?;
+		};
+
+		for i in 0..r {
+			Identity::<T>::request_judgement(caller_origin.clone(), i, 10u32.into())?;
+			Identity::<T>::provide_judgement(
+				RawOrigin::Signed(account("registrar", i, SEED)).into(),
+				i,
+				caller_lookup.clone(),
+				Judgement::Reasonable
+			)?;
+		}
+
+		// Zero the deposits, so the call reserves them in full.
+		let id = IdentityOf::<T>::get(&caller).ok_or("Identity does not exist.")?;
+		let (subs_deposit, subs) = SubsOf::<T>::get(&caller);
+		T::Currency::unreserve(T::NativeCurrencyId::get(), &caller, id.deposit + subs_deposit);
+		IdentityOf::<T>::mutate(&caller, |id| if let Some(id) = id { id.deposit = Zero::zero() });
+		SubsOf::<T>::insert(&caller, (BalanceOf::<T>::zero(), subs));
+	}: _(RawOrigin::Signed(caller.clone()), caller_lookup)
+	verify {
+		ensure!(!IdentityOf::<T>::get(&caller).unwrap().deposit.is_zero(), "Deposit not updated.");
+		ensure!(!SubsOf::<T>::get(&caller).0.is_zero(), "Subs deposit not updated.");
+	}
+
+	set_fee_currency {
+		let r in 1 .. T::MaxRegistrars::get() => add_registrars::<T>(r)?;
+		let index = r - 1;
+		let registrar: T::AccountId = account("registrar", index, SEED);
+	}: _(RawOrigin::Signed(registrar), index, T::NativeCurrencyId::get())
+	verify {
+		let registrars = Registrars::<T>::get();
+		ensure!(registrars[index as usize].as_ref().unwrap().fee_currency == T::NativeCurrencyId::get(), "Fee currency not set.");
+	}
+
+	approve_deposit_currency {
+		let currency = T::NativeCurrencyId::get();
+	}: _(RawOrigin::Root, currency)
+	verify {
+		ensure!(DepositCurrencies::<T>::contains_key(currency), "Currency not approved.");
+	}
+
+	revoke_deposit_currency {
+		let currency = T::NativeCurrencyId::get();
+		Identity::<T>::approve_deposit_currency(RawOrigin::Root.into(), currency)?;
+	}: _(RawOrigin::Root, currency)
+	verify {
+		ensure!(!DepositCurrencies::<T>::contains_key(currency), "Currency not revoked.");
+	}
+
+	set_deposit_currency {
+		let caller: T::AccountId = whitelisted_caller();
+		let caller_origin = <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(caller.clone()));
+		let caller_lookup = <T::Lookup as StaticLookup>::unlookup(caller.clone());
+		fund::<T>(&caller);
+
+		let r in 1 .. T::MaxRegistrars::get() => add_registrars::<T>(r)?;
+		let s in 1 .. T::MaxSubAccounts::get() => {
+			let caller: T::AccountId = whitelisted_caller();
+			let _ = add_sub_accounts::<T>(&caller, s)?;
+		};
+		let x in 1 .. T::MaxAdditionalFields::get() => {
+			let info = create_identity_info::<T>(x);
+			let caller: T::AccountId = whitelisted_caller();
+			let caller_origin = <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(caller));
+			Identity::<T>::set_identity(caller_origin, Box::new(info))?;
+		};
+
+		for i in 0..r {
+			Identity::<T>::request_judgement(caller_origin.clone(), i, 10u32.into())?;
+			Identity::<T>::provide_judgement(
+				RawOrigin::Signed(account("registrar", i, SEED)).into(),
+				i,
+				caller_lookup.clone(),
+				Judgement::Reasonable
+			)?;
+		}
+		let currency = T::NativeCurrencyId::get();
+	}: _(RawOrigin::Signed(caller.clone()), currency)
+	verify {
+		assert_last_event::<T>(Event::<T>::DepositCurrencySet { who: caller, currency }.into())
+	}
+
+	provide_judgement_signed {
+		// The user
+		let user: T::AccountId = account("user", r, SEED);
+		let user_origin = <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(user.clone()));
+		let user_lookup = <T::Lookup as StaticLookup>::unlookup(user.clone());
+		fund::<T>(&user);
+
+		let caller: T::AccountId = whitelisted_caller();
+
+		let r in 1 .. T::MaxRegistrars::get() - 1 => add_registrars::<T>(r)?;
+		let x in 1 .. T::MaxAdditionalFields::get() => {
+			let info = create_identity_info::<T>(x);
+			Identity::<T>::set_identity(user_origin.clone(), Box::new(info))?;
+		};
+
+		let (signer, registrar) = T::BenchmarkHelper::signer();
+		fund::<T>(&registrar);
+		Identity::<T>::add_registrar(RawOrigin::Root.into(), registrar)?;
+		Identity::<T>::request_judgement(user_origin.clone(), r, 10u32.into())?;
+
+		let identity = T::Hashing::hash_of(&IdentityOf::<T>::get(&user).unwrap().info);
+		let expiry = frame_system::Pallet::<T>::block_number();
+		let nonce = JudgementNonces::<T>::get(r, &user);
+		let payload = Identity::<T>::judgement_payload(r, &user, identity, &Judgement::Reasonable, nonce, expiry);
+		let signature = T::BenchmarkHelper::sign(&signer, &payload);
+	}: _(RawOrigin::Signed(caller), r, user_lookup, Judgement::Reasonable, identity, expiry, signature)
+	verify {
+		assert_last_event::<T>(Event::<T>::JudgementGiven { target: user, registrar_index: r }.into())
+	}
+
 	impl_benchmark_test_suite!(Identity, crate::tests::new_test_ext(), crate::tests::Test);
 }
@@ -34,6 +34,9 @@
 //!
 //! A super-user can remove accounts and in doing so, slash the deposit.
 //!
+//! Registrars pick the currency of their fee. Deposits are quoted in the native currency and may
+//! be reserved in any currency approved by governance, converted at the rate of `DepositRate`.
+//!
 //! All accounts may also have a limited number of sub-accounts which may be specified by the owner;
 //! by definition, these have equivalent ownership and each has an individual name.
 //!
@@ -50,6 +53,10 @@
 //! * `clear_identity` - Remove an account's associated identity; the deposit is returned.
 //! * `request_judgement` - Request a judgement from a registrar, paying a fee.
 //! * `cancel_request` - Cancel the previous request for a judgement.
+//! * `poke_deposit` - Reserve or unreserve the difference to the current deposits of an identity
+//!   and its sub-accounts.
+//! * `set_deposit_currency` - Set the currency the deposits of an identity and its sub-accounts
+//!   are reserved in.
 //!
 //! #### For general users with sub-identities
 //! * `set_subs` - Set the sub-accounts of an identity.
@@ -60,12 +67,53 @@
 //!
 //! #### For registrars
 //! * `set_fee` - Set the fee required to be paid for a judgement to be given by the registrar.
-//! * `set_fields` - Set the fields that a registrar cares about in their judgements.
+//! * `set_fee_currency` - Set the currency the fee of the registrar is paid in.
+//! * `set_fields` - Set the fields that a registrar requires of an identity before it can be
+//!   judged.
+//! * `set_judgement_period` - Set the number of blocks after which the judgements of the registrar
+//!   become `OutOfDate`.
 //! * `provide_judgement` - Provide a judgement to an identity.
 //!
+//! #### For anyone on behalf of registrars
+//! * `provide_judgement_signed` - Provide a judgement to an identity signed off-chain by the
+//!   registrar.
+//!
 //! #### For super-users
 //! * `add_registrar` - Add a new registrar to the system.
+//! * `remove_registrar` - Remove a registrar from the system, refunding its pending fees.
+//! * `add_username_authority` - Allow an account to grant usernames with a suffix.
+//! * `remove_username_authority` - Remove the authority granting usernames with a suffix.
 //! * `kill_identity` - Forcibly remove the associated identity; the deposit is lost.
+//! * `approve_deposit_currency` - Allow deposits to be reserved in a currency.
+//! * `revoke_deposit_currency` - Disallow selecting a currency for deposits.
+//!
+//! ### Gaming platforms
+//!
+//! Next to the usual fields, identities have typed fields for Discord, Steam, Epic Games and
+//! Twitch handles. Registrars may require them with `set_fields` like any other field, judgements
+//! are only requested for identities having every field the registrar requires.
+//!
+//! ### Expiring judgements
+//!
+//! Judgements `Reasonable`, `KnownGood` and `LowQuality` given by a registrar with a judgement
+//! period become `OutOfDate` once the period has passed. `on_idle` turns them into `OutOfDate` as
+//! far as the remaining weight of the block allows, `judgements_of` already returns them as
+//! `OutOfDate` before that.
+//!
+//! ### Usernames
+//!
+//! Usernames are unique handles resolving to an account, for a deposit and until they expire.
+//! They are lower-cased and consist of ASCII letters, digits, `-` and `_`. Accounts register
+//! usernames without a suffix themselves, username authorities grant usernames ending in their
+//! suffix, e.g. `name.gamedao`. The first username of an account becomes its primary username,
+//! which resolves back to the account.
+//!
+//! * `set_username` - Register a username for the sender.
+//! * `grant_username` - Grant a username with the suffix of an authority to an account.
+//! * `renew_username` - Extend the expiry of a username.
+//! * `set_primary_username` - Set the username an account resolves to.
+//! * `release_username` - Release a username and return its deposit.
+//! * `remove_expired_username` - Release an expired username.
 //!
 //! [`Call`]: ./enum.Call.html
 //! [`Config`]: ./trait.Config.html
@@ -73,42 +121,126 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 mod benchmarking;
+pub mod migration;
 #[cfg(test)]
 mod tests;
 mod types;
 pub mod weights;
 
-use frame_support::traits::{BalanceStatus, Currency, OnUnbalanced, ReservableCurrency};
-use sp_runtime::traits::{AppendZerosInput, Saturating, StaticLookup, Zero};
+use codec::Encode;
+use frame_support::{
+	ensure,
+	pallet_prelude::{DispatchError, DispatchResult},
+	traits::{BalanceStatus, Get},
+	weights::Weight,
+	BoundedVec,
+};
+use orml_traits::{MultiCurrency, MultiReservableCurrency};
+use sp_runtime::traits::{
+	AppendZerosInput, Hash as HashT, IdentifyAccount, One, Saturating, StaticLookup, Verify, Zero,
+};
 use sp_std::prelude::*;
 pub use weights::WeightInfo;
 
 pub use pallet::*;
 pub use types::{
-	Data, IdentityField, IdentityFields, IdentityInfo, Judgement, RegistrarIndex, RegistrarInfo,
-	Registration,
-	StorageVersion
+	AuthorityProperties, Data, IdentityField, IdentityFields, IdentityInfo, Judgement,
+	RegistrarIndex, RegistrarInfo, Registration, StorageVersion, UsernameInfo,
 };
 
-type BalanceOf<T> =
-	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
-type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as MultiCurrency<<T as frame_system::Config>::AccountId>>::Balance;
+pub type CurrencyIdOf<T> =
+	<<T as Config>::Currency as MultiCurrency<<T as frame_system::Config>::AccountId>>::CurrencyId;
+
+/// Prefix of the payload signed by a registrar for `provide_judgement_signed`.
+pub const JUDGEMENT_CONTEXT: &[u8] = b"identity/judgement";
+
+/// A username, with the suffix of its authority if it was granted by one.
+pub type Username<T> = BoundedVec<u8, <T as Config>::MaxUsernameLength>;
+
+/// The suffix of the usernames granted by an authority.
+pub type Suffix<T> = BoundedVec<u8, <T as Config>::MaxSuffixLength>;
+
+/// Signs judgements in the benchmark of `provide_judgement_signed`.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<Public, AccountId, Signature> {
+	/// A new signing key and the account of its public key.
+	fn signer() -> (Public, AccountId);
+	/// Signs `message` with the key of `signer`.
+	fn sign(signer: &Public, message: &[u8]) -> Signature;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl BenchmarkHelper<sp_runtime::MultiSigner, sp_runtime::AccountId32, sp_runtime::MultiSignature>
+	for ()
+{
+	fn signer() -> (sp_runtime::MultiSigner, sp_runtime::AccountId32) {
+		let public = sp_io::crypto::sr25519_generate(0.into(), None);
+		let account = sp_runtime::MultiSigner::Sr25519(public).into_account();
+		(public.into(), account)
+	}
+
+	fn sign(signer: &sp_runtime::MultiSigner, message: &[u8]) -> sp_runtime::MultiSignature {
+		let public = signer.clone().try_into().expect("the signer is an sr25519 key");
+		sp_runtime::MultiSignature::Sr25519(
+			sp_io::crypto::sr25519_sign(0.into(), &public, message)
+				.expect("the key was generated by `signer`"),
+		)
+	}
+}
+
+type RegistrarInfoOf<T> = RegistrarInfo<
+	BalanceOf<T>,
 	<T as frame_system::Config>::AccountId,
->>::NegativeImbalance;
+	<T as frame_system::Config>::BlockNumber,
+	CurrencyIdOf<T>,
+>;
+
+type UsernameInfoOf<T> = UsernameInfo<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+>;
+
+/// Converts deposits quoted in the native currency into another currency.
+pub trait DepositRate<CurrencyId, Balance> {
+	/// `native_deposit` in `currency_id`, `None` if there is no rate for it.
+	fn convert(currency_id: CurrencyId, native_deposit: Balance) -> Option<Balance>;
+}
+
+impl<CurrencyId, Balance> DepositRate<CurrencyId, Balance> for () {
+	fn convert(_: CurrencyId, _: Balance) -> Option<Balance> {
+		None
+	}
+}
 
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
+	// Not the `StorageVersion` of the prelude.
+	use super::StorageVersion;
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		/// The overarching event type.
-		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The currencies deposits and fees are reserved in.
+		type Currency: MultiReservableCurrency<Self::AccountId>;
 
-		/// The currency trait.
-		type Currency: ReservableCurrency<Self::AccountId>;
+		/// The currency the deposits are quoted in, and reserved in unless the owner of an
+		/// identity picks another one with `set_deposit_currency`.
+		#[pallet::constant]
+		type NativeCurrencyId: Get<CurrencyIdOf<Self>>;
+
+		/// The rate deposits are converted at into the approved deposit currencies.
+		type DepositRate: DepositRate<CurrencyIdOf<Self>, BalanceOf<Self>>;
+
+		/// The origin which may approve or revoke deposit currencies. Root can always do this.
+		type DepositCurrencyOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 
 		/// The amount held on deposit for a registered identity
 		#[pallet::constant]
@@ -138,21 +270,53 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxRegistrars: Get<u32>;
 
-		/// What to do with slashed funds.
-		type Slashed: OnUnbalanced<NegativeImbalanceOf<Self>>;
+		/// The account receiving slashed deposits.
+		type Slashed: Get<Self::AccountId>;
 
 		/// The origin which may forcibly set or remove a name. Root can always do this.
-		type ForceOrigin: EnsureOrigin<Self::Origin>;
+		type ForceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 
 		/// The origin which may add or remove registrars. Root can always do this.
-		type RegistrarOrigin: EnsureOrigin<Self::Origin>;
+		type RegistrarOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Signature of a registrar over a judgement given off-chain, usually `MultiSignature`.
+		type OffchainSignature: Verify<Signer = Self::SigningPublicKey> + Parameter;
+
+		/// Public key of the signer of an `OffchainSignature`.
+		type SigningPublicKey: IdentifyAccount<AccountId = Self::AccountId>;
+
+		/// The origin which may add or remove username authorities. Root can always do this.
+		type UsernameAuthorityOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The amount held on deposit for a username.
+		#[pallet::constant]
+		type UsernameDeposit: Get<BalanceOf<Self>>;
+
+		/// Number of blocks a username resolves after it is set or renewed.
+		#[pallet::constant]
+		type UsernameDuration: Get<Self::BlockNumber>;
+
+		/// Maximum length of a username, including the suffix.
+		#[pallet::constant]
+		type MaxUsernameLength: Get<u32>;
+
+		/// Maximum length of the suffix of a username authority.
+		#[pallet::constant]
+		type MaxSuffixLength: Get<u32>;
+
+		/// Signs judgements in the benchmarks, `()` for `MultiSignature`.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<
+			Self::SigningPublicKey,
+			Self::AccountId,
+			Self::OffchainSignature,
+		>;
 
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::pallet]
-	#[pallet::generate_storage_info]
 	pub struct Pallet<T>(_);
 
 	/// Information that is pertinent to identify the entity behind an account.
@@ -172,7 +336,7 @@ pub mod pallet {
 	/// context. If the account is not some other account's sub-identity, then just `None`.
 	#[pallet::storage]
 	#[pallet::getter(fn super_of)]
-	pub(super) type SuperOf<T: Config> =
+	pub type SuperOf<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::AccountId, (T::AccountId, Data), OptionQuery>;
 
 	/// Alternative "sub" identities of this account.
@@ -182,7 +346,7 @@ pub mod pallet {
 	/// TWOX-NOTE: OK ― `AccountId` is a secure hash.
 	#[pallet::storage]
 	#[pallet::getter(fn subs_of)]
-	pub(super) type SubsOf<T: Config> = StorageMap<
+	pub type SubsOf<T: Config> = StorageMap<
 		_,
 		Twox64Concat,
 		T::AccountId,
@@ -196,17 +360,120 @@ pub mod pallet {
 	/// The index into this can be cast to `RegistrarIndex` to get a valid value.
 	#[pallet::storage]
 	#[pallet::getter(fn registrars)]
-	pub(super) type Registrars<T: Config> = StorageValue<
+	pub(super) type Registrars<T: Config> =
+		StorageValue<_, BoundedVec<Option<RegistrarInfoOf<T>>, T::MaxRegistrars>, ValueQuery>;
+
+	/// Accounts with a pending `FeePaid` judgement, by the index of the registrar it was requested
+	/// from.
+	///
+	/// TWOX-NOTE: OK ― `AccountId` is a secure hash.
+	#[pallet::storage]
+	pub type PendingRequests<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		RegistrarIndex,
+		Twox64Concat,
+		T::AccountId,
+		(),
+		OptionQuery,
+	>;
+
+	/// Number of judgements given by a registrar to an account, the nonce of the next judgement
+	/// signed off-chain.
+	///
+	/// TWOX-NOTE: OK ― `AccountId` is a secure hash.
+	#[pallet::storage]
+	#[pallet::getter(fn judgement_nonce)]
+	pub type JudgementNonces<T: Config> = StorageDoubleMap<
 		_,
-		BoundedVec<Option<RegistrarInfo<BalanceOf<T>, T::AccountId>>, T::MaxRegistrars>,
+		Twox64Concat,
+		RegistrarIndex,
+		Twox64Concat,
+		T::AccountId,
+		u32,
 		ValueQuery,
 	>;
 
+	/// The account and expiry of every username.
+	#[pallet::storage]
+	pub type AccountOfUsername<T: Config> =
+		StorageMap<_, Blake2_128Concat, Username<T>, UsernameInfoOf<T>, OptionQuery>;
+
+	/// The username an account resolves to.
+	///
+	/// TWOX-NOTE: OK ― `AccountId` is a secure hash.
+	#[pallet::storage]
+	pub type PrimaryUsernameOf<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, Username<T>, OptionQuery>;
+
+	/// The authorities granting usernames, by their suffix.
+	#[pallet::storage]
+	pub type UsernameAuthorities<T: Config> =
+		StorageMap<_, Blake2_128Concat, Suffix<T>, AuthorityProperties<T::AccountId>, OptionQuery>;
+
+	/// The block from which the judgement of a registrar about an account is `OutOfDate`.
+	///
+	/// TWOX-NOTE: OK ― `AccountId` is a secure hash.
+	#[pallet::storage]
+	#[pallet::getter(fn judgement_expiry)]
+	pub type JudgementExpiries<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		RegistrarIndex,
+		Twox64Concat,
+		T::AccountId,
+		T::BlockNumber,
+		OptionQuery,
+	>;
+
+	/// The judgements expiring in a block. An entry is stale if `JudgementExpiries` holds another
+	/// block for its judgement.
+	///
+	/// TWOX-NOTE: OK ― `AccountId` is a secure hash.
+	#[pallet::storage]
+	pub type ExpiringJudgements<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		Twox64Concat,
+		(RegistrarIndex, T::AccountId),
+		(),
+		OptionQuery,
+	>;
+
+	/// The next block whose expiring judgements are processed in `on_idle`.
+	#[pallet::storage]
+	pub type ExpiryCursor<T: Config> = StorageValue<_, T::BlockNumber, OptionQuery>;
+
+	/// The currencies other than the native one which deposits may be reserved in.
+	#[pallet::storage]
+	pub type DepositCurrencies<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T>, (), OptionQuery>;
+
+	/// The currency the deposits of an identity and its sub-accounts are reserved in, if it is not
+	/// the native currency.
+	///
+	/// TWOX-NOTE: OK ― `AccountId` is a secure hash.
+	#[pallet::storage]
+	pub type DepositCurrencyOf<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, CurrencyIdOf<T>, OptionQuery>;
+
 	#[pallet::type_value]
 	pub fn DefaultStorageVersion<T: Config>() -> StorageVersion {StorageVersion::V1Initial}
 	#[pallet::storage]
 	pub(super) type PalletVersion<T: Config> = StorageValue<_, StorageVersion, ValueQuery, DefaultStorageVersion<T>>;
 
+	#[pallet::genesis_config]
+	#[derive(Default)]
+	pub struct GenesisConfig {}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			<PalletVersion<T>>::put(StorageVersion::V5MultiCurrency);
+		}
+	}
+
 	#[pallet::error]
 	pub enum Error<T> {
 		/// Too many subs-accounts.
@@ -241,6 +508,42 @@ pub mod pallet {
 		NotSub,
 		/// Sub-account isn't owned by sender.
 		NotOwned,
+		/// The witness is lower than the number of pending requests.
+		BadWitness,
+		/// The signature does not match the judgement or the registrar.
+		InvalidSignature,
+		/// The signed judgement has expired.
+		JudgementExpired,
+		/// The judgement was signed for a different identity of the target.
+		JudgementForDifferentIdentity,
+		/// The username is empty, too long or contains other characters than ASCII letters,
+		/// digits, `-` and `_`.
+		InvalidUsername,
+		/// The suffix is empty or contains other characters than ASCII letters, digits, `-` and
+		/// `_`.
+		InvalidSuffix,
+		/// The username is held by another account and has not expired.
+		UsernameTaken,
+		/// The username does not exist.
+		NoUsername,
+		/// The username is held by another account.
+		NotUsernameOwner,
+		/// The username has expired.
+		UsernameExpired,
+		/// The username has not expired yet.
+		UsernameNotExpired,
+		/// The sender is not the authority of the suffix.
+		NotUsernameAuthority,
+		/// The authority has no usernames left to grant.
+		NoAllocation,
+		/// The judgement period is zero.
+		InvalidJudgementPeriod,
+		/// The identity lacks fields the registrar requires.
+		MissingFields,
+		/// The currency is not approved for deposits or has no rate.
+		UnsupportedCurrency,
+		/// The registrar has pending requests.
+		RequestsPending,
 	}
 
 	#[pallet::event]
@@ -248,9 +551,11 @@ pub mod pallet {
 	pub enum Event<T: Config> {
 		/// A name was set or reset (which will remove all judgements).
 		IdentitySet { who: T::AccountId },
-		/// A name was cleared, and the given balance returned.
+		/// A name was cleared, and the given deposit in the deposit currency of `who` and the fees of
+		/// its pending requests returned.
 		IdentityCleared { who: T::AccountId, deposit: BalanceOf<T> },
-		/// A name was removed and the given balance slashed.
+		/// A name was removed and the given deposit in the deposit currency of `who` and the fees of
+		/// its pending requests slashed.
 		IdentityKilled { who: T::AccountId, deposit: BalanceOf<T> },
 		/// A judgement was asked from a registrar.
 		JudgementRequested { who: T::AccountId, registrar_index: RegistrarIndex },
@@ -260,6 +565,10 @@ pub mod pallet {
 		JudgementGiven { target: T::AccountId, registrar_index: RegistrarIndex },
 		/// A registrar was added.
 		RegistrarAdded { registrar_index: RegistrarIndex },
+		/// A registrar was removed and its pending requests refunded.
+		RegistrarRemoved { registrar_index: RegistrarIndex, refunded: u32 },
+		/// The fee of a pending request was returned as its registrar was removed.
+		JudgementRefunded { who: T::AccountId, registrar_index: RegistrarIndex, fee: BalanceOf<T> },
 		/// A sub-identity was added to an identity and the deposit paid.
 		SubIdentityAdded { sub: T::AccountId, main: T::AccountId, deposit: BalanceOf<T> },
 		/// A sub-identity was removed from an identity and the deposit freed.
@@ -267,6 +576,51 @@ pub mod pallet {
 		/// A sub-identity was cleared, and the given deposit repatriated from the
 		/// main identity account to the sub-identity account.
 		SubIdentityRevoked { sub: T::AccountId, main: T::AccountId, deposit: BalanceOf<T> },
+		/// A username authority was added.
+		AuthorityAdded { authority: T::AccountId, suffix: Suffix<T> },
+		/// A username authority was removed.
+		AuthorityRemoved { authority: T::AccountId, suffix: Suffix<T> },
+		/// A username was set for an account.
+		UsernameSet { who: T::AccountId, username: Username<T>, expiry: T::BlockNumber },
+		/// A username was renewed.
+		UsernameRenewed { username: Username<T>, expiry: T::BlockNumber },
+		/// The primary username of an account was set.
+		PrimaryUsernameSet { who: T::AccountId, username: Username<T> },
+		/// A username was released and its deposit returned.
+		UsernameReleased { who: T::AccountId, username: Username<T> },
+		/// The judgement period of a registrar passed and its judgement became `OutOfDate`.
+		JudgementExpired { target: T::AccountId, registrar_index: RegistrarIndex },
+		/// The deposits of an identity and its sub-accounts were updated to the current deposits.
+		DepositUpdated { who: T::AccountId, identity: BalanceOf<T>, subs: BalanceOf<T> },
+		/// Deposits may be reserved in a currency.
+		DepositCurrencyApproved { currency: CurrencyIdOf<T> },
+		/// New deposits may no longer be reserved in a currency.
+		DepositCurrencyRevoked { currency: CurrencyIdOf<T> },
+		/// The deposits of an identity and its sub-accounts were moved to a currency.
+		DepositCurrencySet { who: T::AccountId, currency: CurrencyIdOf<T> },
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			Self::expire_judgements(now, remaining_weight)
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			migration::migrate_to_v3::<T>()
+				.saturating_add(migration::migrate_to_v4::<T>())
+				.saturating_add(migration::migrate_to_v5::<T>())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			migration::pre_upgrade::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			migration::post_upgrade::<T>(state)
+		}
 	}
 
 	#[pallet::call]
@@ -285,6 +639,7 @@ pub mod pallet {
 		/// - One storage mutation (codec `O(R)`).
 		/// - One event.
 		/// # </weight>
+		#[pallet::call_index(0)]
 		#[pallet::weight(T::WeightInfo::add_registrar(T::MaxRegistrars::get()))]
 		pub fn add_registrar(
 			origin: OriginFor<T>,
@@ -298,7 +653,9 @@ pub mod pallet {
 						.try_push(Some(RegistrarInfo {
 							account,
 							fee: Zero::zero(),
+							fee_currency: T::NativeCurrencyId::get(),
 							fields: Default::default(),
+							judgement_period: None,
 						}))
 						.map_err(|_| Error::<T>::TooManyRegistrars)?;
 					Ok(((registrars.len() - 1) as RegistrarIndex, registrars.len()))
@@ -310,10 +667,90 @@ pub mod pallet {
 			Ok(Some(T::WeightInfo::add_registrar(registrar_count as u32)).into())
 		}
 
+		/// Remove a registrar from the system.
+		///
+		/// The dispatch origin for this call must be `T::RegistrarOrigin`.
+		///
+		/// - `index`: the index of the registrar.
+		/// - `pending`: an upper bound of the number of requests pending with the registrar, as
+		///   listed in `PendingRequests`.
+		///
+		/// The index of the registrar stays empty, so the indices of the other registrars do not
+		/// change. The fee of every pending request is returned to the requester, judgements
+		/// already given are kept.
+		///
+		/// Emits `JudgementRefunded` per pending request and `RegistrarRemoved` if successful.
+		///
+		/// # <weight>
+		/// - `O(R + P)` where `P` pending-requests-count (witness-bounded).
+		/// - One storage mutation (codec `O(R)`).
+		/// - `P` balance-unreserve operations and `2P` storage mutations (codec `O(R + X)`).
+		/// - `P + 1` events.
+		/// # </weight>
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::remove_registrar(T::MaxRegistrars::get(), *pending))]
+		pub fn remove_registrar(
+			origin: OriginFor<T>,
+			#[pallet::compact] index: RegistrarIndex,
+			#[pallet::compact] pending: u32,
+		) -> DispatchResultWithPostInfo {
+			T::RegistrarOrigin::ensure_origin(origin)?;
+
+			let requesters: Vec<T::AccountId> = <PendingRequests<T>>::iter_key_prefix(index)
+				.take(pending as usize + 1)
+				.collect();
+			ensure!(requesters.len() <= pending as usize, Error::<T>::BadWitness);
+
+			let (registrar, registrar_count) = <Registrars<T>>::try_mutate(
+				|registrars| -> Result<(RegistrarInfoOf<T>, usize), DispatchError> {
+					let registrar = registrars
+						.get_mut(index as usize)
+						.and_then(Option::take)
+						.ok_or(Error::<T>::InvalidIndex)?;
+					Ok((registrar, registrars.len()))
+				},
+			)?;
+
+			for who in requesters.iter() {
+				<PendingRequests<T>>::remove(index, who);
+				let fee = <IdentityOf<T>>::mutate(who, |id| {
+					let id = id.as_mut()?;
+					let pos = id.judgements.binary_search_by_key(&index, |x| x.0).ok()?;
+					match id.judgements[pos].1 {
+						Judgement::FeePaid(fee) => {
+							id.judgements.remove(pos);
+							Some(fee)
+						},
+						_ => None,
+					}
+				});
+				if let Some(fee) = fee {
+					let err_amount = T::Currency::unreserve(registrar.fee_currency, who, fee);
+					debug_assert!(err_amount.is_zero());
+					Self::deposit_event(Event::JudgementRefunded {
+						who: who.clone(),
+						registrar_index: index,
+						fee,
+					});
+				}
+			}
+
+			Self::deposit_event(Event::RegistrarRemoved {
+				registrar_index: index,
+				refunded: requesters.len() as u32,
+			});
+
+			Ok(Some(T::WeightInfo::remove_registrar(
+				registrar_count as u32,  // R
+				requesters.len() as u32, // P
+			))
+			.into())
+		}
+
 		/// Set an account's identity information and reserve the appropriate deposit.
 		///
 		/// If the account already has identity information, the deposit is taken as part payment
-		/// for the new deposit.
+		/// for the new deposit. The deposit is reserved in the deposit currency of the account.
 		///
 		/// The dispatch origin for this call must be _Signed_.
 		///
@@ -329,6 +766,7 @@ pub mod pallet {
 		/// - One storage mutation (codec-read `O(X' + R)`, codec-write `O(X + R)`).
 		/// - One event.
 		/// # </weight>
+		#[pallet::call_index(1)]
 		#[pallet::weight( T::WeightInfo::set_identity(
 			T::MaxRegistrars::get().into(), // R
 			T::MaxAdditionalFields::get().into(), // X
@@ -341,6 +779,7 @@ pub mod pallet {
 			let extra_fields = info.additional.len() as u32;
 			ensure!(extra_fields <= T::MaxAdditionalFields::get(), Error::<T>::TooManyFields);
 			let fd = <BalanceOf<T>>::from(extra_fields) * T::FieldDeposit::get();
+			let currency = Self::deposit_currency(&sender);
 
 			let mut id = match <IdentityOf<T>>::get(&sender) {
 				Some(mut id) => {
@@ -357,12 +796,13 @@ pub mod pallet {
 			};
 
 			let old_deposit = id.deposit;
-			id.deposit = T::BasicDeposit::get() + fd;
+			id.deposit = Self::deposit_in(currency, T::BasicDeposit::get() + fd)?;
 			if id.deposit > old_deposit {
-				T::Currency::reserve(&sender, id.deposit - old_deposit)?;
+				T::Currency::reserve(currency, &sender, id.deposit - old_deposit)?;
 			}
 			if old_deposit > id.deposit {
-				let err_amount = T::Currency::unreserve(&sender, old_deposit - id.deposit);
+				let err_amount =
+					T::Currency::unreserve(currency, &sender, old_deposit - id.deposit);
 				debug_assert!(err_amount.is_zero());
 			}
 
@@ -404,6 +844,7 @@ pub mod pallet {
 		// N storage items for N sub accounts. Right now the weight on this function
 		// is a large overestimate due to the fact that it could potentially write
 		// to 2 x T::MaxSubAccounts::get().
+		#[pallet::call_index(2)]
 		#[pallet::weight(T::WeightInfo::set_subs_old(T::MaxSubAccounts::get()) // P: Assume max sub accounts removed.
 			.saturating_add(T::WeightInfo::set_subs_new(subs.len() as u32)) // S: Assume all subs are new.
 		)]
@@ -419,16 +860,21 @@ pub mod pallet {
 			);
 
 			let (old_deposit, old_ids) = <SubsOf<T>>::get(&sender);
-			let new_deposit = T::SubAccountDeposit::get() * <BalanceOf<T>>::from(subs.len() as u32);
+			let currency = Self::deposit_currency(&sender);
+			let new_deposit = Self::deposit_in(
+				currency,
+				T::SubAccountDeposit::get() * <BalanceOf<T>>::from(subs.len() as u32),
+			)?;
 
 			let not_other_sub =
 				subs.iter().filter_map(|i| SuperOf::<T>::get(&i.0)).all(|i| &i.0 == &sender);
 			ensure!(not_other_sub, Error::<T>::AlreadyClaimed);
 
 			if old_deposit < new_deposit {
-				T::Currency::reserve(&sender, new_deposit - old_deposit)?;
+				T::Currency::reserve(currency, &sender, new_deposit - old_deposit)?;
 			} else if old_deposit > new_deposit {
-				let err_amount = T::Currency::unreserve(&sender, old_deposit - new_deposit);
+				let err_amount =
+					T::Currency::unreserve(currency, &sender, old_deposit - new_deposit);
 				debug_assert!(err_amount.is_zero());
 			}
 			// do nothing if they're equal.
@@ -459,7 +905,8 @@ pub mod pallet {
 
 		/// Clear an account's identity info and all sub-accounts and return all deposits.
 		///
-		/// Payment: All reserved balances on the account are returned.
+		/// Payment: The deposits and the fees of pending requests are returned, each in the
+		/// currency it was reserved in.
 		///
 		/// The dispatch origin for this call must be _Signed_ and the sender must have a registered
 		/// identity.
@@ -475,6 +922,7 @@ pub mod pallet {
 		/// - `2` storage reads and `S + 2` storage deletions.
 		/// - One event.
 		/// # </weight>
+		#[pallet::call_index(3)]
 		#[pallet::weight(T::WeightInfo::clear_identity(
 			T::MaxRegistrars::get().into(), // R
 			T::MaxSubAccounts::get().into(), // S
@@ -485,13 +933,12 @@ pub mod pallet {
 
 			let (subs_deposit, sub_ids) = <SubsOf<T>>::take(&sender);
 			let id = <IdentityOf<T>>::take(&sender).ok_or(Error::<T>::NotNamed)?;
-			let deposit = id.total_deposit() + subs_deposit;
+			let deposit = id.deposit + subs_deposit;
 			for sub in sub_ids.iter() {
 				<SuperOf<T>>::remove(sub);
 			}
-
-			let err_amount = T::Currency::unreserve(&sender, deposit.clone());
-			debug_assert!(err_amount.is_zero());
+			Self::forget_requests(&sender, &id);
+			Self::release_deposits(&sender, &id, deposit, false);
 
 			Self::deposit_event(Event::IdentityCleared { who: sender, deposit });
 
@@ -509,7 +956,7 @@ pub mod pallet {
 		/// given.
 		///
 		/// The dispatch origin for this call must be _Signed_ and the sender must have a
-		/// registered identity.
+		/// registered identity with all fields the registrar requires.
 		///
 		/// - `reg_index`: The index of the registrar whose judgement is requested.
 		/// - `max_fee`: The maximum fee that may be paid. This should just be auto-populated as:
@@ -526,6 +973,7 @@ pub mod pallet {
 		/// - Storage: 1 read `O(R)`, 1 mutate `O(X + R)`.
 		/// - One event.
 		/// # </weight>
+		#[pallet::call_index(4)]
 		#[pallet::weight(T::WeightInfo::request_judgement(
 			T::MaxRegistrars::get().into(), // R
 			T::MaxAdditionalFields::get().into(), // X
@@ -543,6 +991,7 @@ pub mod pallet {
 				.ok_or(Error::<T>::EmptyIndex)?;
 			ensure!(max_fee >= registrar.fee, Error::<T>::FeeChanged);
 			let mut id = <IdentityOf<T>>::get(&sender).ok_or(Error::<T>::NoIdentity)?;
+			ensure!(id.info.has_fields(registrar.fields), Error::<T>::MissingFields);
 
 			let item = (reg_index, Judgement::FeePaid(registrar.fee));
 			match id.judgements.binary_search_by_key(&reg_index, |x| x.0) {
@@ -556,11 +1005,12 @@ pub mod pallet {
 					id.judgements.try_insert(i, item).map_err(|_| Error::<T>::TooManyRegistrars)?,
 			}
 
-			T::Currency::reserve(&sender, registrar.fee)?;
+			T::Currency::reserve(registrar.fee_currency, &sender, registrar.fee)?;
 
 			let judgements = id.judgements.len();
 			let extra_fields = id.info.additional.len();
 			<IdentityOf<T>>::insert(&sender, id);
+			<PendingRequests<T>>::insert(reg_index, &sender, ());
 
 			Self::deposit_event(Event::JudgementRequested {
 				who: sender,
@@ -588,6 +1038,7 @@ pub mod pallet {
 		/// - One storage mutation `O(R + X)`.
 		/// - One event
 		/// # </weight>
+		#[pallet::call_index(5)]
 		#[pallet::weight(T::WeightInfo::cancel_request(
 			T::MaxRegistrars::get().into(), // R
 			T::MaxAdditionalFields::get().into(), // X
@@ -609,11 +1060,13 @@ pub mod pallet {
 				Err(Error::<T>::JudgementGiven)?
 			};
 
-			let err_amount = T::Currency::unreserve(&sender, fee);
+			let currency = Self::fee_currency(&<Registrars<T>>::get(), reg_index);
+			let err_amount = T::Currency::unreserve(currency, &sender, fee);
 			debug_assert!(err_amount.is_zero());
 			let judgements = id.judgements.len();
 			let extra_fields = id.info.additional.len();
 			<IdentityOf<T>>::insert(&sender, id);
+			<PendingRequests<T>>::remove(reg_index, &sender);
 
 			Self::deposit_event(Event::JudgementUnrequested {
 				who: sender,
@@ -636,6 +1089,7 @@ pub mod pallet {
 		/// - One storage mutation `O(R)`.
 		/// - Benchmark: 7.315 + R * 0.329 µs (min squares analysis)
 		/// # </weight>
+		#[pallet::call_index(6)]
 		#[pallet::weight(T::WeightInfo::set_fee(T::MaxRegistrars::get()))] // R
 		pub fn set_fee(
 			origin: OriginFor<T>,
@@ -674,6 +1128,7 @@ pub mod pallet {
 		/// - One storage mutation `O(R)`.
 		/// - Benchmark: 8.823 + R * 0.32 µs (min squares analysis)
 		/// # </weight>
+		#[pallet::call_index(7)]
 		#[pallet::weight(T::WeightInfo::set_account_id(T::MaxRegistrars::get()))] // R
 		pub fn set_account_id(
 			origin: OriginFor<T>,
@@ -705,13 +1160,15 @@ pub mod pallet {
 		/// of the registrar whose index is `index`.
 		///
 		/// - `index`: the index of the registrar whose fee is to be set.
-		/// - `fields`: the fields that the registrar concerns themselves with.
+		/// - `fields`: the fields that the registrar concerns themselves with. Judgements can only
+		///   be requested for identities having all of them.
 		///
 		/// # <weight>
 		/// - `O(R)`.
 		/// - One storage mutation `O(R)`.
 		/// - Benchmark: 7.464 + R * 0.325 µs (min squares analysis)
 		/// # </weight>
+		#[pallet::call_index(8)]
 		#[pallet::weight(T::WeightInfo::set_fields(T::MaxRegistrars::get()))] // R
 		pub fn set_fields(
 			origin: OriginFor<T>,
@@ -756,9 +1213,10 @@ pub mod pallet {
 		/// - `O(R + X)`.
 		/// - One balance-transfer operation.
 		/// - Up to one account-lookup operation.
-		/// - Storage: 1 read `O(R)`, 1 mutate `O(R + X)`.
+		/// - Storage: 1 read `O(R)`, 1 mutate `O(R + X)`, 1 mutate of the nonce.
 		/// - One event.
 		/// # </weight>
+		#[pallet::call_index(9)]
 		#[pallet::weight(T::WeightInfo::provide_judgement(
 			T::MaxRegistrars::get().into(), // R
 			T::MaxAdditionalFields::get().into(), // X
@@ -772,46 +1230,98 @@ pub mod pallet {
 			let sender = ensure_signed(origin)?;
 			let target = T::Lookup::lookup(target)?;
 			ensure!(!judgement.has_deposit(), Error::<T>::InvalidJudgement);
-			<Registrars<T>>::get()
+			let registrar = <Registrars<T>>::get()
 				.get(reg_index as usize)
 				.and_then(Option::as_ref)
-				.and_then(|r| if r.account == sender { Some(r) } else { None })
+				.filter(|r| r.account == sender)
+				.cloned()
 				.ok_or(Error::<T>::InvalidIndex)?;
-			let mut id = <IdentityOf<T>>::get(&target).ok_or(Error::<T>::InvalidTarget)?;
+			let id = <IdentityOf<T>>::get(&target).ok_or(Error::<T>::InvalidTarget)?;
 
-			let item = (reg_index, judgement);
-			match id.judgements.binary_search_by_key(&reg_index, |x| x.0) {
-				Ok(position) => {
-					if let Judgement::FeePaid(fee) = id.judgements[position].1 {
-						let _ = T::Currency::repatriate_reserved(
-							&target,
-							&sender,
-							fee,
-							BalanceStatus::Free,
-						);
-					}
-					id.judgements[position] = item
-				},
-				Err(position) => id
-					.judgements
-					.try_insert(position, item)
-					.map_err(|_| Error::<T>::TooManyRegistrars)?,
-			}
+			let (judgements, extra_fields) =
+				Self::do_provide_judgement(&registrar, reg_index, target, id, judgement)?;
 
-			let judgements = id.judgements.len();
-			let extra_fields = id.info.additional.len();
-			<IdentityOf<T>>::insert(&target, id);
-			Self::deposit_event(Event::JudgementGiven { target, registrar_index: reg_index });
+			Ok(Some(T::WeightInfo::provide_judgement(judgements, extra_fields)).into())
+		}
 
-			Ok(Some(T::WeightInfo::provide_judgement(judgements as u32, extra_fields as u32))
-				.into())
+		/// Provide a judgement for an account's identity signed off-chain by a registrar.
+		///
+		/// The dispatch origin for this call must be _Signed_ by any account, the judgement is
+		/// authorized by `signature` of the account of the registrar whose index is `reg_index`
+		/// over `Self::judgement_payload`.
+		///
+		/// - `reg_index`: the index of the registrar whose judgement is being made.
+		/// - `target`: the account whose identity the judgement is upon. This must be an account
+		///   with a registered identity.
+		/// - `judgement`: the judgement of the registrar of index `reg_index` about `target`.
+		/// - `identity`: the hash of the `IdentityInfo` of `target` the judgement is upon.
+		/// - `expiry`: the last block in which the judgement may be provided.
+		/// - `signature`: the signature of the registrar.
+		///
+		/// The payload includes the number of judgements given by the registrar to `target` so
+		/// far, so every signed judgement can be provided once, and not after a later judgement.
+		///
+		/// Emits `JudgementGiven` if successful.
+		///
+		/// # <weight>
+		/// - `O(R + X)`.
+		/// - One signature verification.
+		/// - One balance-transfer operation.
+		/// - Up to one account-lookup operation.
+		/// - Storage: 1 read `O(R)`, 1 mutate `O(R + X)`, 1 mutate of the nonce.
+		/// - One event.
+		/// # </weight>
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::provide_judgement_signed(
+			T::MaxRegistrars::get().into(), // R
+			T::MaxAdditionalFields::get().into(), // X
+		))]
+		pub fn provide_judgement_signed(
+			origin: OriginFor<T>,
+			#[pallet::compact] reg_index: RegistrarIndex,
+			target: <T::Lookup as StaticLookup>::Source,
+			judgement: Judgement<BalanceOf<T>>,
+			identity: T::Hash,
+			expiry: T::BlockNumber,
+			signature: T::OffchainSignature,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let target = T::Lookup::lookup(target)?;
+			ensure!(!judgement.has_deposit(), Error::<T>::InvalidJudgement);
+			ensure!(
+				frame_system::Pallet::<T>::block_number() <= expiry,
+				Error::<T>::JudgementExpired
+			);
+			let registrar = <Registrars<T>>::get()
+				.get(reg_index as usize)
+				.and_then(Option::as_ref)
+				.cloned()
+				.ok_or(Error::<T>::InvalidIndex)?;
+			let id = <IdentityOf<T>>::get(&target).ok_or(Error::<T>::InvalidTarget)?;
+			ensure!(
+				T::Hashing::hash_of(&id.info) == identity,
+				Error::<T>::JudgementForDifferentIdentity
+			);
+
+			let nonce = <JudgementNonces<T>>::get(reg_index, &target);
+			let payload =
+				Self::judgement_payload(reg_index, &target, identity, &judgement, nonce, expiry);
+			ensure!(
+				signature.verify(&payload[..], &registrar.account),
+				Error::<T>::InvalidSignature
+			);
+
+			let (judgements, extra_fields) =
+				Self::do_provide_judgement(&registrar, reg_index, target, id, judgement)?;
+
+			Ok(Some(T::WeightInfo::provide_judgement_signed(judgements, extra_fields)).into())
 		}
 
 		/// Remove an account's identity and sub-account information and slash the deposits.
 		///
-		/// Payment: Reserved balances from `set_subs` and `set_identity` are slashed and handled by
-		/// `Slash`. Verification request deposits are not returned; they should be cancelled
-		/// manually using `cancel_request`.
+		/// Payment: Reserved balances from `set_subs` and `set_identity` and the fees of pending
+		/// requests are moved to `Slashed`. Verification requests should be cancelled manually
+		/// using `cancel_request` beforehand to have their fees returned.
 		///
 		/// The dispatch origin for this call must match `T::ForceOrigin`.
 		///
@@ -826,6 +1336,7 @@ pub mod pallet {
 		/// - `S + 2` storage mutations.
 		/// - One event.
 		/// # </weight>
+		#[pallet::call_index(10)]
 		#[pallet::weight(T::WeightInfo::kill_identity(
 			T::MaxRegistrars::get().into(), // R
 			T::MaxSubAccounts::get().into(), // S
@@ -842,12 +1353,13 @@ pub mod pallet {
 			// Grab their deposit (and check that they have one).
 			let (subs_deposit, sub_ids) = <SubsOf<T>>::take(&target);
 			let id = <IdentityOf<T>>::take(&target).ok_or(Error::<T>::NotNamed)?;
-			let deposit = id.total_deposit() + subs_deposit;
+			let deposit = id.deposit + subs_deposit;
 			for sub in sub_ids.iter() {
 				<SuperOf<T>>::remove(sub);
 			}
+			Self::forget_requests(&target, &id);
 			// Slash their deposit from them.
-			T::Slashed::on_unbalanced(T::Currency::slash_reserved(&target, deposit).0);
+			Self::release_deposits(&target, &id, deposit, true);
 
 			Self::deposit_event(Event::IdentityKilled { who: target, deposit });
 
@@ -866,6 +1378,7 @@ pub mod pallet {
 		///
 		/// The dispatch origin for this call must be _Signed_ and the sender must have a registered
 		/// sub identity of `sub`.
+		#[pallet::call_index(11)]
 		#[pallet::weight(T::WeightInfo::add_sub(T::MaxSubAccounts::get()))]
 		pub fn add_sub(
 			origin: OriginFor<T>,
@@ -885,8 +1398,9 @@ pub mod pallet {
 					sub_ids.len() < T::MaxSubAccounts::get() as usize,
 					Error::<T>::TooManySubAccounts
 				);
-				let deposit = T::SubAccountDeposit::get();
-				T::Currency::reserve(&sender, deposit)?;
+				let currency = Self::deposit_currency(&sender);
+				let deposit = Self::deposit_in(currency, T::SubAccountDeposit::get())?;
+				T::Currency::reserve(currency, &sender, deposit)?;
 
 				SuperOf::<T>::insert(&sub, (sender.clone(), data));
 				sub_ids.try_push(sub.clone()).expect("sub ids length checked above; qed");
@@ -901,6 +1415,7 @@ pub mod pallet {
 		///
 		/// The dispatch origin for this call must be _Signed_ and the sender must have a registered
 		/// sub identity of `sub`.
+		#[pallet::call_index(12)]
 		#[pallet::weight(T::WeightInfo::rename_sub(T::MaxSubAccounts::get()))]
 		pub fn rename_sub(
 			origin: OriginFor<T>,
@@ -922,6 +1437,7 @@ pub mod pallet {
 		///
 		/// The dispatch origin for this call must be _Signed_ and the sender must have a registered
 		/// sub identity of `sub`.
+		#[pallet::call_index(13)]
 		#[pallet::weight(T::WeightInfo::remove_sub(T::MaxSubAccounts::get()))]
 		pub fn remove_sub(
 			origin: OriginFor<T>,
@@ -933,11 +1449,12 @@ pub mod pallet {
 			let (sup, _) = SuperOf::<T>::get(&sub).ok_or(Error::<T>::NotSub)?;
 			ensure!(sup == sender, Error::<T>::NotOwned);
 			SuperOf::<T>::remove(&sub);
+			let currency = Self::deposit_currency(&sup);
 			SubsOf::<T>::mutate(&sup, |(ref mut subs_deposit, ref mut sub_ids)| {
+				let deposit = Self::sub_share(*subs_deposit, sub_ids.len());
 				sub_ids.retain(|x| x != &sub);
-				let deposit = T::SubAccountDeposit::get().min(*subs_deposit);
 				*subs_deposit -= deposit;
-				let err_amount = T::Currency::unreserve(&sender, deposit);
+				let err_amount = T::Currency::unreserve(currency, &sender, deposit);
 				debug_assert!(err_amount.is_zero());
 				Self::deposit_event(Event::SubIdentityRemoved { sub, main: sender, deposit });
 			});
@@ -954,16 +1471,23 @@ pub mod pallet {
 		///
 		/// NOTE: This should not normally be used, but is provided in the case that the non-
 		/// controller of an account is maliciously registered as a sub-account.
+		#[pallet::call_index(14)]
 		#[pallet::weight(T::WeightInfo::quit_sub(T::MaxSubAccounts::get()))]
 		pub fn quit_sub(origin: OriginFor<T>) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 			let (sup, _) = SuperOf::<T>::take(&sender).ok_or(Error::<T>::NotSub)?;
+			let currency = Self::deposit_currency(&sup);
 			SubsOf::<T>::mutate(&sup, |(ref mut subs_deposit, ref mut sub_ids)| {
+				let deposit = Self::sub_share(*subs_deposit, sub_ids.len());
 				sub_ids.retain(|x| x != &sender);
-				let deposit = T::SubAccountDeposit::get().min(*subs_deposit);
 				*subs_deposit -= deposit;
-				let _ =
-					T::Currency::repatriate_reserved(&sup, &sender, deposit, BalanceStatus::Free);
+				let _ = T::Currency::repatriate_reserved(
+					currency,
+					&sup,
+					&sender,
+					deposit,
+					BalanceStatus::Free,
+				);
 				Self::deposit_event(Event::SubIdentityRevoked {
 					sub: sender,
 					main: sup.clone(),
@@ -973,13 +1497,811 @@ pub mod pallet {
 			Ok(())
 		}
 
-		// fn on_runtime_upgrade() -> frame_support::weights::Weight {
-		// 	migrate_to_v2::<T>()
-		// }
+		/// Add an authority granting usernames ending in `.suffix`, or change the authority and
+		/// allocation of `suffix`.
+		///
+		/// The dispatch origin for this call must be `T::UsernameAuthorityOrigin`.
+		///
+		/// - `suffix`: the suffix of the usernames granted by the authority, lower-cased.
+		/// - `authority`: the account of the authority.
+		/// - `allocation`: the number of usernames the authority may grant.
+		///
+		/// Emits `AuthorityAdded` if successful.
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::add_username_authority())]
+		pub fn add_username_authority(
+			origin: OriginFor<T>,
+			suffix: Suffix<T>,
+			authority: <T::Lookup as StaticLookup>::Source,
+			allocation: u32,
+		) -> DispatchResult {
+			T::UsernameAuthorityOrigin::ensure_origin(origin)?;
+			let authority = T::Lookup::lookup(authority)?;
+			let suffix = Self::normalize_suffix(&suffix)?;
+
+			<UsernameAuthorities<T>>::insert(
+				&suffix,
+				AuthorityProperties { account: authority.clone(), allocation },
+			);
+			Self::deposit_event(Event::AuthorityAdded { authority, suffix });
+			Ok(())
+		}
+
+		/// Remove the authority granting usernames ending in `.suffix`. Usernames it granted stay
+		/// until they expire.
+		///
+		/// The dispatch origin for this call must be `T::UsernameAuthorityOrigin`.
+		///
+		/// Emits `AuthorityRemoved` if successful.
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::remove_username_authority())]
+		pub fn remove_username_authority(
+			origin: OriginFor<T>,
+			suffix: Suffix<T>,
+		) -> DispatchResult {
+			T::UsernameAuthorityOrigin::ensure_origin(origin)?;
+			let suffix = Self::normalize_suffix(&suffix)?;
+
+			let authority =
+				<UsernameAuthorities<T>>::take(&suffix).ok_or(Error::<T>::InvalidSuffix)?;
+			Self::deposit_event(Event::AuthorityRemoved { authority: authority.account, suffix });
+			Ok(())
+		}
+
+		/// Register `username` for the sender and reserve `UsernameDeposit`.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `username`: the username, without a suffix. It is lower-cased.
+		///
+		/// An expired username of another account is released and taken over. The username
+		/// becomes the primary username of the sender if it has none.
+		///
+		/// Emits `UsernameSet` if successful.
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::set_username())]
+		pub fn set_username(origin: OriginFor<T>, username: Username<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let username = Self::normalize(&username).ok_or(Error::<T>::InvalidUsername)?;
+
+			Self::insert_username(&sender, username, &sender)
+		}
+
+		/// Grant `username.suffix` to `who`, reserving `UsernameDeposit` from the sender.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the authority of `suffix`, which
+		/// must have allocation left.
+		///
+		/// - `suffix`: the suffix of the authority. It is lower-cased.
+		/// - `who`: the account the username resolves to.
+		/// - `username`: the username, without the suffix. It is lower-cased.
+		///
+		/// Emits `UsernameSet` if successful.
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::grant_username())]
+		pub fn grant_username(
+			origin: OriginFor<T>,
+			suffix: Suffix<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			username: Username<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			let mut username = Self::normalize(&username).ok_or(Error::<T>::InvalidUsername)?;
+			let suffix = Self::normalize_suffix(&suffix)?;
+
+			<UsernameAuthorities<T>>::try_mutate(&suffix, |authority| -> DispatchResult {
+				let authority = authority.as_mut().ok_or(Error::<T>::NotUsernameAuthority)?;
+				ensure!(authority.account == sender, Error::<T>::NotUsernameAuthority);
+				authority.allocation =
+					authority.allocation.checked_sub(1).ok_or(Error::<T>::NoAllocation)?;
+				Ok(())
+			})?;
+			username.push(b'.');
+			username.extend_from_slice(&suffix);
+
+			Self::insert_username(&who, username, &sender)
+		}
+
+		/// Extend the expiry of `username` to `UsernameDuration` blocks from now.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the owner of the username. An
+		/// expired username can be renewed as long as nobody released it.
+		///
+		/// Emits `UsernameRenewed` if successful.
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::renew_username())]
+		pub fn renew_username(origin: OriginFor<T>, username: Username<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let username = Self::normalize_username(&username)?;
+
+			let expiry = <AccountOfUsername<T>>::try_mutate(
+				&username,
+				|info| -> Result<T::BlockNumber, DispatchError> {
+					let info = info.as_mut().ok_or(Error::<T>::NoUsername)?;
+					ensure!(info.owner == sender, Error::<T>::NotUsernameOwner);
+					info.expiry = frame_system::Pallet::<T>::block_number()
+						.saturating_add(T::UsernameDuration::get());
+					Ok(info.expiry)
+				},
+			)?;
+			Self::deposit_event(Event::UsernameRenewed { username, expiry });
+			Ok(())
+		}
+
+		/// Set the username the sender resolves to.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the owner of the username, which
+		/// must not have expired.
+		///
+		/// Emits `PrimaryUsernameSet` if successful.
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::set_primary_username())]
+		pub fn set_primary_username(origin: OriginFor<T>, username: Username<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let username = Self::normalize_username(&username)?;
+			let info = <AccountOfUsername<T>>::get(&username).ok_or(Error::<T>::NoUsername)?;
+			ensure!(info.owner == sender, Error::<T>::NotUsernameOwner);
+			ensure!(!Self::is_expired(&info), Error::<T>::UsernameExpired);
+
+			<PrimaryUsernameOf<T>>::insert(&sender, &username);
+			Self::deposit_event(Event::PrimaryUsernameSet { who: sender, username });
+			Ok(())
+		}
+
+		/// Release `username` and return its deposit to the depositor.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the owner of the username.
+		///
+		/// Emits `UsernameReleased` if successful.
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::release_username())]
+		pub fn release_username(origin: OriginFor<T>, username: Username<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let username = Self::normalize_username(&username)?;
+			let info = <AccountOfUsername<T>>::get(&username).ok_or(Error::<T>::NoUsername)?;
+			ensure!(info.owner == sender, Error::<T>::NotUsernameOwner);
+
+			Self::do_release_username(username, info);
+			Ok(())
+		}
+
+		/// Release the expired `username` and return its deposit to the depositor.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// Emits `UsernameReleased` if successful.
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::release_username())]
+		pub fn remove_expired_username(
+			origin: OriginFor<T>,
+			username: Username<T>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			let username = Self::normalize_username(&username)?;
+			let info = <AccountOfUsername<T>>::get(&username).ok_or(Error::<T>::NoUsername)?;
+			ensure!(Self::is_expired(&info), Error::<T>::UsernameNotExpired);
+
+			Self::do_release_username(username, info);
+			Ok(())
+		}
+
+		/// Set the number of blocks after which the judgements of a registrar become `OutOfDate`.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must be the account
+		/// of the registrar whose index is `index`.
+		///
+		/// - `index`: the index of the registrar whose judgement period is to be set.
+		/// - `period`: the new judgement period, `None` if the judgements do not expire. It applies
+		///   to the judgements given afterwards.
+		///
+		/// # <weight>
+		/// - `O(R)`.
+		/// - One storage mutation `O(R)`.
+		/// # </weight>
+		#[pallet::call_index(25)]
+		#[pallet::weight(T::WeightInfo::set_judgement_period(T::MaxRegistrars::get()))] // R
+		pub fn set_judgement_period(
+			origin: OriginFor<T>,
+			#[pallet::compact] index: RegistrarIndex,
+			period: Option<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!period.map_or(false, |p| p.is_zero()), Error::<T>::InvalidJudgementPeriod);
+
+			let registrars = <Registrars<T>>::mutate(|rs| -> Result<usize, DispatchError> {
+				rs.get_mut(index as usize)
+					.and_then(|x| x.as_mut())
+					.and_then(|r| {
+						if r.account == who {
+							r.judgement_period = period;
+							Some(())
+						} else {
+							None
+						}
+					})
+					.ok_or_else(|| DispatchError::from(Error::<T>::InvalidIndex))?;
+				Ok(rs.len())
+			})?;
+			Ok(Some(T::WeightInfo::set_judgement_period(registrars as u32)).into()) // R
+		}
+
+		/// Update the deposits of an identity and its sub-accounts to the current `BasicDeposit`,
+		/// `FieldDeposit` and `SubAccountDeposit`, reserving or unreserving the difference.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `target`: the account whose deposits are updated. This must be an account with a
+		///   registered identity.
+		///
+		/// The call is free if the deposits changed.
+		///
+		/// Emits `DepositUpdated` if the deposits changed.
+		///
+		/// # <weight>
+		/// - `O(R + S + X)`.
+		/// - One balance-reserve operation.
+		/// - Storage: 2 reads and up to 2 writes, `O(R + S + X)`.
+		/// - Up to one event.
+		/// # </weight>
+		#[pallet::call_index(26)]
+		#[pallet::weight(T::WeightInfo::poke_deposit(
+			T::MaxRegistrars::get().into(), // R
+			T::MaxSubAccounts::get().into(), // S
+			T::MaxAdditionalFields::get().into(), // X
+		))]
+		pub fn poke_deposit(
+			origin: OriginFor<T>,
+			target: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let target = T::Lookup::lookup(target)?;
+
+			let (changed, weight) = Self::do_poke_deposit(&target)?;
+			Ok((Some(weight), if changed { Pays::No } else { Pays::Yes }).into())
+		}
+
+		/// Set the currency the fee of a registrar is paid in.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must be the account
+		/// of the registrar whose index is `index`.
+		///
+		/// - `index`: the index of the registrar whose fee currency is to be set.
+		/// - `currency`: the new fee currency.
+		///
+		/// Fails while requests are pending with the registrar, their fees are reserved in the
+		/// previous currency.
+		///
+		/// # <weight>
+		/// - `O(R)`.
+		/// - One storage read of `PendingRequests`.
+		/// - One storage mutation `O(R)`.
+		/// # </weight>
+		#[pallet::call_index(27)]
+		#[pallet::weight(T::WeightInfo::set_fee_currency(T::MaxRegistrars::get()))] // R
+		pub fn set_fee_currency(
+			origin: OriginFor<T>,
+			#[pallet::compact] index: RegistrarIndex,
+			currency: CurrencyIdOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let registrars = <Registrars<T>>::mutate(|rs| -> Result<usize, DispatchError> {
+				let registrar = rs
+					.get_mut(index as usize)
+					.and_then(|x| x.as_mut())
+					.filter(|r| r.account == who)
+					.ok_or(Error::<T>::InvalidIndex)?;
+				ensure!(
+					<PendingRequests<T>>::iter_key_prefix(index).next().is_none(),
+					Error::<T>::RequestsPending
+				);
+				registrar.fee_currency = currency;
+				Ok(rs.len())
+			})?;
+			Ok(Some(T::WeightInfo::set_fee_currency(registrars as u32)).into()) // R
+		}
+
+		/// Allow deposits to be reserved in `currency`, converted at the rate of `DepositRate`.
+		///
+		/// The dispatch origin for this call must be `T::DepositCurrencyOrigin`.
+		///
+		/// Emits `DepositCurrencyApproved` if successful.
+		#[pallet::call_index(28)]
+		#[pallet::weight(T::WeightInfo::approve_deposit_currency())]
+		pub fn approve_deposit_currency(
+			origin: OriginFor<T>,
+			currency: CurrencyIdOf<T>,
+		) -> DispatchResult {
+			T::DepositCurrencyOrigin::ensure_origin(origin)?;
+
+			<DepositCurrencies<T>>::insert(currency, ());
+			Self::deposit_event(Event::DepositCurrencyApproved { currency });
+			Ok(())
+		}
+
+		/// Disallow selecting `currency` for deposits.
+		///
+		/// The dispatch origin for this call must be `T::DepositCurrencyOrigin`.
+		///
+		/// Accounts that already selected `currency` keep reserving their deposits in it, including
+		/// for new fields and sub-accounts, until they move them to another currency with
+		/// `set_deposit_currency` or clear the identity.
+		///
+		/// Emits `DepositCurrencyRevoked` if successful.
+		#[pallet::call_index(29)]
+		#[pallet::weight(T::WeightInfo::revoke_deposit_currency())]
+		pub fn revoke_deposit_currency(
+			origin: OriginFor<T>,
+			currency: CurrencyIdOf<T>,
+		) -> DispatchResult {
+			T::DepositCurrencyOrigin::ensure_origin(origin)?;
+
+			ensure!(<DepositCurrencies<T>>::take(currency).is_some(), Error::<T>::NotFound);
+			Self::deposit_event(Event::DepositCurrencyRevoked { currency });
+			Ok(())
+		}
+
+		/// Set the currency the deposits of the sender's identity and sub-accounts are reserved
+		/// in.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `currency`: the native currency or a currency approved with
+		///   `approve_deposit_currency`.
+		///
+		/// If the sender has an identity, its deposits are returned and the current deposits are
+		/// reserved in `currency`, converted at the rate of `DepositRate`.
+		///
+		/// Emits `DepositCurrencySet` if successful.
+		///
+		/// # <weight>
+		/// - `O(R + S + X)`.
+		/// - One balance-unreserve and one balance-reserve operation.
+		/// - Storage: 3 reads and up to 3 writes, `O(R + S + X)`.
+		/// - One event.
+		/// # </weight>
+		#[pallet::call_index(30)]
+		#[pallet::weight(T::WeightInfo::set_deposit_currency(
+			T::MaxRegistrars::get().into(), // R
+			T::MaxSubAccounts::get().into(), // S
+			T::MaxAdditionalFields::get().into(), // X
+		))]
+		pub fn set_deposit_currency(
+			origin: OriginFor<T>,
+			currency: CurrencyIdOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			ensure!(Self::is_deposit_currency(currency), Error::<T>::UnsupportedCurrency);
+
+			let mut weight = T::WeightInfo::set_deposit_currency(0, 0, 0);
+			if let Some(mut id) = <IdentityOf<T>>::get(&sender) {
+				let (old_subs_deposit, subs) = <SubsOf<T>>::get(&sender);
+				let extra_fields = id.info.additional.len() as u32;
+				weight = T::WeightInfo::set_deposit_currency(
+					id.judgements.len() as u32, // R
+					subs.len() as u32,          // S
+					extra_fields,               // X
+				);
+
+				let (identity_deposit, subs_deposit) =
+					Self::deposits_in(currency, extra_fields, subs.len() as u32)?;
+				let err_amount = T::Currency::unreserve(
+					Self::deposit_currency(&sender),
+					&sender,
+					id.deposit.saturating_add(old_subs_deposit),
+				);
+				debug_assert!(err_amount.is_zero());
+				T::Currency::reserve(
+					currency,
+					&sender,
+					identity_deposit.saturating_add(subs_deposit),
+				)?;
+
+				id.deposit = identity_deposit;
+				<IdentityOf<T>>::insert(&sender, id);
+				if !subs.is_empty() {
+					<SubsOf<T>>::insert(&sender, (subs_deposit, subs));
+				}
+			}
+
+			if currency == T::NativeCurrencyId::get() {
+				<DepositCurrencyOf<T>>::remove(&sender);
+			} else {
+				<DepositCurrencyOf<T>>::insert(&sender, currency);
+			}
+			Self::deposit_event(Event::DepositCurrencySet { who: sender, currency });
+
+			Ok(Some(weight).into())
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
+	/// The account `username` resolves to, `None` if it does not exist or has expired. The
+	/// username is lower-cased before the lookup.
+	pub fn account_of_username(username: &[u8]) -> Option<T::AccountId> {
+		let username: Username<T> = username.to_ascii_lowercase().try_into().ok()?;
+		<AccountOfUsername<T>>::get(&username)
+			.filter(|info| !Self::is_expired(info))
+			.map(|info| info.owner)
+	}
+
+	/// The primary username of `who`, `None` if it has none or it has expired.
+	pub fn username_of(who: &T::AccountId) -> Option<Vec<u8>> {
+		let username = <PrimaryUsernameOf<T>>::get(who)?;
+		Self::account_of_username(&username)?;
+		Some(username.into_inner())
+	}
+
+	/// Lower-cases `name`, `None` if it is empty or contains other characters than ASCII letters,
+	/// digits, `-` and `_`.
+	fn normalize(name: &[u8]) -> Option<Vec<u8>> {
+		let valid = |c: &u8| c.is_ascii_alphanumeric() || *c == b'-' || *c == b'_';
+		if name.is_empty() || !name.iter().all(valid) {
+			return None
+		}
+		Some(name.to_ascii_lowercase())
+	}
+
+	/// `suffix` lower-cased, checked like a username.
+	fn normalize_suffix(suffix: &[u8]) -> Result<Suffix<T>, Error<T>> {
+		Self::normalize(suffix)
+			.and_then(|suffix| suffix.try_into().ok())
+			.ok_or(Error::<T>::InvalidSuffix)
+	}
+
+	/// A full username, optionally followed by `.` and the suffix it was granted under,
+	/// lower-cased like `set_username` and `grant_username` store it.
+	fn normalize_username(username: &[u8]) -> Result<Username<T>, Error<T>> {
+		let mut parts = username.split(|c| *c == b'.');
+		let valid = parts.clone().count() <= 2 && parts.all(|part| Self::normalize(part).is_some());
+		ensure!(valid, Error::<T>::InvalidUsername);
+		username
+			.to_ascii_lowercase()
+			.try_into()
+			.map_err(|_| Error::<T>::InvalidUsername)
+	}
+
+	fn is_expired(info: &UsernameInfoOf<T>) -> bool {
+		info.expiry < frame_system::Pallet::<T>::block_number()
+	}
+
+	/// Set `username` for `who` with the deposit reserved from `depositor`, releasing the
+	/// username first if it has expired.
+	fn insert_username(
+		who: &T::AccountId,
+		username: Vec<u8>,
+		depositor: &T::AccountId,
+	) -> DispatchResult {
+		let username: Username<T> = username.try_into().map_err(|_| Error::<T>::InvalidUsername)?;
+		if let Some(info) = <AccountOfUsername<T>>::get(&username) {
+			ensure!(Self::is_expired(&info), Error::<T>::UsernameTaken);
+			Self::do_release_username(username.clone(), info);
+		}
+
+		let deposit = T::UsernameDeposit::get();
+		T::Currency::reserve(T::NativeCurrencyId::get(), depositor, deposit)?;
+		let expiry =
+			frame_system::Pallet::<T>::block_number().saturating_add(T::UsernameDuration::get());
+		<AccountOfUsername<T>>::insert(
+			&username,
+			UsernameInfo { owner: who.clone(), depositor: depositor.clone(), deposit, expiry },
+		);
+		Self::deposit_event(Event::UsernameSet {
+			who: who.clone(),
+			username: username.clone(),
+			expiry,
+		});
+
+		if Self::username_of(who).is_none() {
+			<PrimaryUsernameOf<T>>::insert(who, &username);
+			Self::deposit_event(Event::PrimaryUsernameSet { who: who.clone(), username });
+		}
+		Ok(())
+	}
+
+	fn do_release_username(username: Username<T>, info: UsernameInfoOf<T>) {
+		let err_amount =
+			T::Currency::unreserve(T::NativeCurrencyId::get(), &info.depositor, info.deposit);
+		debug_assert!(err_amount.is_zero());
+		<AccountOfUsername<T>>::remove(&username);
+		if <PrimaryUsernameOf<T>>::get(&info.owner).as_ref() == Some(&username) {
+			<PrimaryUsernameOf<T>>::remove(&info.owner);
+		}
+		Self::deposit_event(Event::UsernameReleased { who: info.owner, username });
+	}
+
+	/// The payload a registrar signs for `provide_judgement_signed`: the SCALE encoding of
+	/// `JUDGEMENT_CONTEXT`, the genesis hash, `reg_index`, `target`, the hash of the identity,
+	/// `judgement`, the nonce from `JudgementNonces` and `expiry`. The genesis hash keeps a
+	/// judgement from being provided on another chain the registrar judges on.
+	pub fn judgement_payload(
+		reg_index: RegistrarIndex,
+		target: &T::AccountId,
+		identity: T::Hash,
+		judgement: &Judgement<BalanceOf<T>>,
+		nonce: u32,
+		expiry: T::BlockNumber,
+	) -> Vec<u8> {
+		let genesis = frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+		(JUDGEMENT_CONTEXT, genesis, reg_index, target, identity, judgement, nonce, expiry).encode()
+	}
+
+	/// Set the judgement of `registrar` on the identity `id` of `target`, paying out the fee of a
+	/// pending request. Returns the number of judgements and of additional fields of `id`.
+	fn do_provide_judgement(
+		registrar: &RegistrarInfoOf<T>,
+		reg_index: RegistrarIndex,
+		target: T::AccountId,
+		mut id: Registration<BalanceOf<T>, T::MaxRegistrars, T::MaxAdditionalFields>,
+		judgement: Judgement<BalanceOf<T>>,
+	) -> Result<(u32, u32), DispatchError> {
+		let item = (reg_index, judgement);
+		match id.judgements.binary_search_by_key(&reg_index, |x| x.0) {
+			Ok(position) => {
+				if let Judgement::FeePaid(fee) = id.judgements[position].1 {
+					let _ = T::Currency::repatriate_reserved(
+						registrar.fee_currency,
+						&target,
+						&registrar.account,
+						fee,
+						BalanceStatus::Free,
+					);
+					<PendingRequests<T>>::remove(reg_index, &target);
+				}
+				id.judgements[position] = item
+			},
+			Err(position) => id
+				.judgements
+				.try_insert(position, item)
+				.map_err(|_| Error::<T>::TooManyRegistrars)?,
+		}
+
+		let judgements = id.judgements.len() as u32;
+		let extra_fields = id.info.additional.len() as u32;
+		<IdentityOf<T>>::insert(&target, id);
+		<JudgementNonces<T>>::mutate(reg_index, &target, |nonce| *nonce = nonce.wrapping_add(1));
+		<JudgementExpiries<T>>::remove(reg_index, &target);
+		if let Some(period) = registrar.judgement_period.filter(|_| judgement.expires()) {
+			let expiry = frame_system::Pallet::<T>::block_number().saturating_add(period);
+			<JudgementExpiries<T>>::insert(reg_index, &target, expiry);
+			<ExpiringJudgements<T>>::insert(expiry, (reg_index, target.clone()), ());
+		}
+		Self::deposit_event(Event::JudgementGiven { target, registrar_index: reg_index });
+
+		Ok((judgements, extra_fields))
+	}
+
+	/// The currency the deposits of `who` are reserved in.
+	pub fn deposit_currency(who: &T::AccountId) -> CurrencyIdOf<T> {
+		<DepositCurrencyOf<T>>::get(who).unwrap_or_else(T::NativeCurrencyId::get)
+	}
+
+	/// Whether `currency` may be selected for deposits.
+	fn is_deposit_currency(currency: CurrencyIdOf<T>) -> bool {
+		currency == T::NativeCurrencyId::get() || <DepositCurrencies<T>>::contains_key(currency)
+	}
+
+	/// `native_deposit` converted into `currency`.
+	///
+	/// `currency` may have been revoked since its holders selected it, they keep topping up and
+	/// releasing their deposits in it.
+	fn deposit_in(
+		currency: CurrencyIdOf<T>,
+		native_deposit: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		if currency == T::NativeCurrencyId::get() {
+			return Ok(native_deposit)
+		}
+		T::DepositRate::convert(currency, native_deposit)
+			.ok_or_else(|| Error::<T>::UnsupportedCurrency.into())
+	}
+
+	/// The current deposits of an identity with `extra_fields` additional fields and of its `subs`
+	/// sub-accounts, in `currency`.
+	fn deposits_in(
+		currency: CurrencyIdOf<T>,
+		extra_fields: u32,
+		subs: u32,
+	) -> Result<(BalanceOf<T>, BalanceOf<T>), DispatchError> {
+		let identity_deposit =
+			T::BasicDeposit::get() + <BalanceOf<T>>::from(extra_fields) * T::FieldDeposit::get();
+		let subs_deposit = T::SubAccountDeposit::get() * <BalanceOf<T>>::from(subs);
+		Ok((
+			Self::deposit_in(currency, identity_deposit)?,
+			Self::deposit_in(currency, subs_deposit)?,
+		))
+	}
+
+	/// The share of one of `subs` sub-accounts in their deposit `subs_deposit`.
+	fn sub_share(subs_deposit: BalanceOf<T>, subs: usize) -> BalanceOf<T> {
+		subs_deposit / <BalanceOf<T>>::from(subs.max(1) as u32)
+	}
+
+	/// The currency the fees of the registrar `index` are reserved in, the native currency if
+	/// the registrar was removed.
+	fn fee_currency(
+		registrars: &[Option<RegistrarInfoOf<T>>],
+		index: RegistrarIndex,
+	) -> CurrencyIdOf<T> {
+		registrars
+			.get(index as usize)
+			.and_then(Option::as_ref)
+			.map_or_else(T::NativeCurrencyId::get, |r| r.fee_currency)
+	}
+
+	/// Unreserve `deposit` in the deposit currency of `who` and the fees of the pending requests of
+	/// its identity `id` in the currencies of their registrars, or with `slash` move them to
+	/// `Slashed`.
+	fn release_deposits(
+		who: &T::AccountId,
+		id: &Registration<BalanceOf<T>, T::MaxRegistrars, T::MaxAdditionalFields>,
+		deposit: BalanceOf<T>,
+		slash: bool,
+	) {
+		let release = |currency, amount| {
+			if slash {
+				let _ = T::Currency::repatriate_reserved(
+					currency,
+					who,
+					&T::Slashed::get(),
+					amount,
+					BalanceStatus::Free,
+				);
+			} else {
+				let err_amount = T::Currency::unreserve(currency, who, amount);
+				debug_assert!(err_amount.is_zero());
+			}
+		};
+		release(Self::deposit_currency(who), deposit);
+		if id.fees().next().is_some() {
+			let registrars = <Registrars<T>>::get();
+			for (index, fee) in id.fees() {
+				release(Self::fee_currency(&registrars, index), fee);
+			}
+		}
+	}
+
+	/// Remove the pending requests of the identity of `who` from `PendingRequests` and the expiry
+	/// of its judgements from `JudgementExpiries`.
+	fn forget_requests(
+		who: &T::AccountId,
+		id: &Registration<BalanceOf<T>, T::MaxRegistrars, T::MaxAdditionalFields>,
+	) {
+		for (index, judgement) in id.judgements.iter() {
+			if judgement.has_deposit() {
+				<PendingRequests<T>>::remove(index, who);
+			}
+			<JudgementExpiries<T>>::remove(index, who);
+		}
+	}
+
+	/// Update the deposits of the identity of `who` and its sub-accounts to the current deposits.
+	///
+	/// Returns whether the deposits changed and the weight used.
+	pub(crate) fn do_poke_deposit(who: &T::AccountId) -> Result<(bool, Weight), DispatchError> {
+		let mut id = <IdentityOf<T>>::get(who).ok_or(Error::<T>::NoIdentity)?;
+		let (old_subs_deposit, subs) = <SubsOf<T>>::get(who);
+
+		let judgements = id.judgements.len() as u32;
+		let extra_fields = id.info.additional.len() as u32;
+		let weight = T::WeightInfo::poke_deposit(judgements, subs.len() as u32, extra_fields);
+
+		let currency = Self::deposit_currency(who);
+		let (identity_deposit, subs_deposit) =
+			Self::deposits_in(currency, extra_fields, subs.len() as u32)?;
+		if id.deposit == identity_deposit && old_subs_deposit == subs_deposit {
+			return Ok((false, weight))
+		}
+
+		let old_deposit = id.deposit.saturating_add(old_subs_deposit);
+		let new_deposit = identity_deposit.saturating_add(subs_deposit);
+		if new_deposit > old_deposit {
+			T::Currency::reserve(currency, who, new_deposit - old_deposit)?;
+		}
+		if old_deposit > new_deposit {
+			let err_amount = T::Currency::unreserve(currency, who, old_deposit - new_deposit);
+			debug_assert!(err_amount.is_zero());
+		}
+
+		id.deposit = identity_deposit;
+		<IdentityOf<T>>::insert(who, id);
+		if old_subs_deposit != subs_deposit {
+			<SubsOf<T>>::insert(who, (subs_deposit, subs));
+		}
+		Self::deposit_event(Event::DepositUpdated {
+			who: who.clone(),
+			identity: identity_deposit,
+			subs: subs_deposit,
+		});
+		Ok((true, weight))
+	}
+
+	/// The judgements of the identity of `who`, with the judgements whose period has passed as
+	/// `OutOfDate` even if `on_idle` has not processed them yet.
+	pub fn judgements_of(who: &T::AccountId) -> Vec<(RegistrarIndex, Judgement<BalanceOf<T>>)> {
+		let now = frame_system::Pallet::<T>::block_number();
+		<IdentityOf<T>>::get(who)
+			.map(|id| {
+				id.judgements
+					.into_iter()
+					.map(|(index, judgement)| {
+						let expired = judgement.expires() &&
+							<JudgementExpiries<T>>::get(index, who)
+								.map_or(false, |expiry| expiry <= now);
+						(index, if expired { Judgement::OutOfDate } else { judgement })
+					})
+					.collect()
+			})
+			.unwrap_or_default()
+	}
+
+	/// Turn the judgements expiring up to block `now` into `OutOfDate`, as far as
+	/// `remaining_weight` allows, and return the weight used.
+	///
+	/// Blocks are processed in order from `ExpiryCursor`, which is left at the first block with
+	/// expiring judgements left.
+	pub(crate) fn expire_judgements(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+		let per_judgement = T::WeightInfo::expire_judgement(
+			T::MaxRegistrars::get(),       // R
+			T::MaxAdditionalFields::get(), // X
+		);
+		let mut used = T::DbWeight::get().reads_writes(1, 1);
+		if used.saturating_add(per_judgement).any_gt(remaining_weight) {
+			return Weight::zero()
+		}
+
+		let mut block = <ExpiryCursor<T>>::get().unwrap_or(now);
+		while block <= now {
+			used = used.saturating_add(T::DbWeight::get().reads(1));
+			let mut done = true;
+			for key in <ExpiringJudgements<T>>::iter_key_prefix(block) {
+				if used.saturating_add(per_judgement).any_gt(remaining_weight) {
+					done = false;
+					break
+				}
+				<ExpiringJudgements<T>>::remove(block, &key);
+				let (reg_index, who) = key;
+				Self::expire_judgement(block, reg_index, who);
+				used = used.saturating_add(per_judgement);
+			}
+			if !done {
+				break
+			}
+			block = block.saturating_add(One::one());
+			if used.saturating_add(per_judgement).any_gt(remaining_weight) {
+				break
+			}
+		}
+		<ExpiryCursor<T>>::put(block);
+		used
+	}
+
+	/// Turn the judgement of the registrar `reg_index` about `who` into `OutOfDate` if it expires
+	/// in `block`.
+	fn expire_judgement(block: T::BlockNumber, reg_index: RegistrarIndex, who: T::AccountId) {
+		if <JudgementExpiries<T>>::get(reg_index, &who) != Some(block) {
+			return
+		}
+		<JudgementExpiries<T>>::remove(reg_index, &who);
+
+		let mut id = match <IdentityOf<T>>::get(&who) {
+			Some(id) => id,
+			None => return,
+		};
+		if let Ok(position) = id.judgements.binary_search_by_key(&reg_index, |x| x.0) {
+			if id.judgements[position].1.expires() {
+				id.judgements[position].1 = Judgement::OutOfDate;
+				<IdentityOf<T>>::insert(&who, id);
+				Self::deposit_event(Event::JudgementExpired {
+					target: who,
+					registrar_index: reg_index,
+				});
+			}
+		}
+	}
+
 	/// Get the subs of an account.
 	pub fn subs(who: &T::AccountId) -> Vec<(T::AccountId, Data)> {
 		SubsOf::<T>::get(who)
@@ -0,0 +1,333 @@
+//! Storage migrations of the identity pallet.
+
+use super::*;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	storage::{unhashed, StoragePrefixedMap},
+	traits::Get,
+};
+use sp_std::fmt::Debug;
+
+/// `RegistrarInfo` of upstream `pallet-identity`, before judgements could expire.
+#[derive(Decode)]
+struct RegistrarInfoV1<Balance, AccountId> {
+	account: AccountId,
+	fee: Balance,
+	fields: IdentityFields,
+}
+
+/// `RegistrarInfo` before registrars picked the currency of their fee.
+#[derive(Encode, Decode)]
+struct RegistrarInfoV3<Balance, AccountId, BlockNumber> {
+	account: AccountId,
+	fee: Balance,
+	fields: IdentityFields,
+	judgement_period: Option<BlockNumber>,
+}
+
+/// `IdentityInfo` of upstream `pallet-identity`, before the typed gaming platform fields.
+#[derive(Encode, Decode)]
+pub(crate) struct IdentityInfoV3<FieldLimit: Get<u32>> {
+	pub(crate) additional: BoundedVec<(Data, Data), FieldLimit>,
+	pub(crate) display: Data,
+	pub(crate) legal: Data,
+	pub(crate) web: Data,
+	pub(crate) riot: Data,
+	pub(crate) email: Data,
+	pub(crate) pgp_fingerprint: Option<[u8; 20]>,
+	pub(crate) image: Data,
+	pub(crate) twitter: Data,
+}
+
+/// `Registration` of upstream `pallet-identity`, before the typed gaming platform fields.
+#[derive(Encode, Decode)]
+pub(crate) struct RegistrationV3<
+	Balance: Encode + Decode + MaxEncodedLen + Copy + Clone + Debug + Eq + PartialEq,
+	MaxJudgements: Get<u32>,
+	MaxAdditionalFields: Get<u32>,
+> {
+	pub(crate) judgements: BoundedVec<(RegistrarIndex, Judgement<Balance>), MaxJudgements>,
+	pub(crate) deposit: Balance,
+	pub(crate) info: IdentityInfoV3<MaxAdditionalFields>,
+}
+
+type RegistrationV3Of<T> =
+	RegistrationV3<BalanceOf<T>, <T as Config>::MaxRegistrars, <T as Config>::MaxAdditionalFields>;
+type JudgementsOf<T> = Vec<(RegistrarIndex, Judgement<BalanceOf<T>>)>;
+
+type RegistrarsV1<T> =
+	Vec<Option<RegistrarInfoV1<BalanceOf<T>, <T as frame_system::Config>::AccountId>>>;
+type RegistrarsV3<T> = Vec<
+	Option<
+		RegistrarInfoV3<
+			BalanceOf<T>,
+			<T as frame_system::Config>::AccountId,
+			<T as frame_system::Config>::BlockNumber,
+		>,
+	>,
+>;
+
+/// Converts the storage of upstream `pallet-identity` into the layout of this pallet:
+/// - adds the judgement period to the registrars, so existing judgements stay valid until the
+///   registrars set one and judge again,
+/// - records the pending `FeePaid` judgements in `PendingRequests`, see
+///   `backfill_pending_requests`,
+/// - starts expiring judgements from the current block.
+///
+/// Identities, sub-identities and their deposits are stored the same way and kept as they are.
+/// Reads every identity once, which is fine for the number of identities on our chains.
+pub fn migrate_to_v3<T: Config>() -> Weight {
+	if <PalletVersion<T>>::get() >= StorageVersion::V3ExpiringJudgements {
+		return T::DbWeight::get().reads(1)
+	}
+
+	let key = <Registrars<T>>::hashed_key();
+	if let Some(registrars) = unhashed::get::<RegistrarsV1<T>>(&key) {
+		let registrars: RegistrarsV3<T> = registrars
+			.into_iter()
+			.map(|registrar| {
+				registrar.map(|r| RegistrarInfoV3 {
+					account: r.account,
+					fee: r.fee,
+					fields: r.fields,
+					judgement_period: None,
+				})
+			})
+			.collect();
+		unhashed::put(&key, &registrars);
+	}
+
+	let (identities, requests) = backfill_pending_requests::<T>(false);
+
+	<ExpiryCursor<T>>::put(frame_system::Pallet::<T>::block_number());
+	<PalletVersion<T>>::put(StorageVersion::V3ExpiringJudgements);
+
+	T::DbWeight::get().reads_writes(2 + identities, 3 + requests)
+}
+
+/// Records every pending `FeePaid` judgement in `PendingRequests`, which did not exist before
+/// `remove_registrar`. Without an entry, removing the registrar would not refund the fee.
+///
+/// Identities are decoded with the layout before the typed gaming fields unless `typed_fields`.
+/// Returns the number of identities read and of requests written.
+pub fn backfill_pending_requests<T: Config>(typed_fields: bool) -> (u64, u64) {
+	let mut identities = 0u64;
+	let mut requests = 0u64;
+	for who in <IdentityOf<T>>::iter_keys() {
+		identities += 1;
+		for (index, judgement) in judgements_of::<T>(&who, typed_fields).unwrap_or_default() {
+			if judgement.has_deposit() {
+				<PendingRequests<T>>::insert(index, &who, ());
+				requests += 1;
+			}
+		}
+	}
+	(identities, requests)
+}
+
+/// Moves the gaming platform handles out of the additional fields into the typed fields: an
+/// additional field keyed `discord`, `steam`, `epic` or `twitch`, in any case, becomes the value
+/// of the typed field unless that one is already set.
+///
+/// Every identity is re-encoded with the typed fields. Deposits are kept as they are, the field
+/// deposits of moved handles are returned by `poke_deposit`.
+pub fn migrate_to_v4<T: Config>() -> Weight {
+	if <PalletVersion<T>>::get() >= StorageVersion::V4GamingFields {
+		return T::DbWeight::get().reads(1)
+	}
+
+	let mut identities = 0u64;
+	<IdentityOf<T>>::translate_values(|old: RegistrationV3Of<T>| {
+		identities += 1;
+		let mut info = IdentityInfo {
+			additional: Default::default(),
+			display: old.info.display,
+			legal: old.info.legal,
+			web: old.info.web,
+			riot: old.info.riot,
+			email: old.info.email,
+			pgp_fingerprint: old.info.pgp_fingerprint,
+			image: old.info.image,
+			twitter: old.info.twitter,
+			discord: Data::None,
+			steam: Data::None,
+			epic: Data::None,
+			twitch: Data::None,
+		};
+		for (key, value) in old.info.additional {
+			match info.platform_field(&key) {
+				Some(field) if *field == Data::None => *field = value,
+				// Cannot fail, the fields were taken out of a vector with the same bound.
+				_ => {
+					let _ = info.additional.try_push((key, value));
+				},
+			}
+		}
+		Some(Registration { judgements: old.judgements, deposit: old.deposit, info })
+	});
+	<PalletVersion<T>>::put(StorageVersion::V4GamingFields);
+
+	T::DbWeight::get().reads_writes(1 + identities, 1 + identities)
+}
+
+/// Adds the fee currency to the registrars, the native currency, in which their fees were paid so
+/// far. Deposits stay reserved in the native currency, which is the deposit currency of accounts
+/// without an entry in `DepositCurrencyOf`.
+pub fn migrate_to_v5<T: Config>() -> Weight {
+	if <PalletVersion<T>>::get() >= StorageVersion::V5MultiCurrency {
+		return T::DbWeight::get().reads(1)
+	}
+
+	let _ = <Registrars<T>>::translate::<RegistrarsV3<T>, _>(|registrars| {
+		registrars.and_then(|registrars| {
+			registrars
+				.into_iter()
+				.map(|registrar| {
+					registrar.map(|r| RegistrarInfo {
+						account: r.account,
+						fee: r.fee,
+						fee_currency: T::NativeCurrencyId::get(),
+						fields: r.fields,
+						judgement_period: r.judgement_period,
+					})
+				})
+				.collect::<Vec<_>>()
+				.try_into()
+				.ok()
+		})
+	});
+	<PalletVersion<T>>::put(StorageVersion::V5MultiCurrency);
+
+	T::DbWeight::get().reads_writes(2, 2)
+}
+
+/// The judgements of the identity of `who`, decoded with the layout before the typed gaming fields
+/// unless `typed_fields`. `None` if there is no identity or it does not decode.
+fn judgements_of<T: Config>(who: &T::AccountId, typed_fields: bool) -> Option<JudgementsOf<T>> {
+	let key = <IdentityOf<T>>::hashed_key_for(who);
+	if typed_fields {
+		unhashed::get::<Registration<BalanceOf<T>, T::MaxRegistrars, T::MaxAdditionalFields>>(&key)
+			.map(|id| id.judgements.into_inner())
+	} else {
+		unhashed::get::<RegistrationV3Of<T>>(&key).map(|id| id.judgements.into_inner())
+	}
+}
+
+/// Number of identities, registrars, sub-identities and `FeePaid` judgements, which the migration
+/// must not change.
+#[cfg(feature = "try-runtime")]
+#[derive(Encode, Decode)]
+struct Counts {
+	identities: u32,
+	registrars: u32,
+	subs: u32,
+	super_of: u32,
+	fee_paid: u32,
+}
+
+/// The identities, sub-identities and fee-paid judgements in storage, with `registrars`.
+///
+/// Identities are counted by their keys, so identities which do not decode are counted too.
+#[cfg(feature = "try-runtime")]
+fn counts<T: Config>(registrars: u32) -> Counts {
+	let typed_fields = <PalletVersion<T>>::get() >= StorageVersion::V4GamingFields;
+	let (mut identities, mut fee_paid) = (0u32, 0u32);
+	for who in <IdentityOf<T>>::iter_keys() {
+		identities += 1;
+		let judgements = judgements_of::<T>(&who, typed_fields).unwrap_or_default();
+		fee_paid += judgements.iter().filter(|(_, j)| j.has_deposit()).count() as u32;
+	}
+	Counts {
+		identities,
+		registrars,
+		subs: <SubsOf<T>>::iter_values().map(|(_, subs)| subs.len() as u32).sum(),
+		super_of: <SuperOf<T>>::iter_keys().count() as u32,
+		fee_paid,
+	}
+}
+
+/// Counts the identities, registrars and sub-identities before the migration.
+#[cfg(feature = "try-runtime")]
+pub fn pre_upgrade<T: Config>() -> Result<Vec<u8>, &'static str> {
+	let key = <Registrars<T>>::hashed_key();
+	let version = <PalletVersion<T>>::get();
+	let registrars = if version >= StorageVersion::V5MultiCurrency {
+		<Registrars<T>>::get().iter().flatten().count()
+	} else if version >= StorageVersion::V3ExpiringJudgements {
+		let registrars = unhashed::get::<RegistrarsV3<T>>(&key).unwrap_or_default();
+		registrars.iter().flatten().count()
+	} else {
+		let registrars = unhashed::get::<RegistrarsV1<T>>(&key).unwrap_or_default();
+		registrars.iter().flatten().count()
+	};
+	Ok(counts::<T>(registrars as u32).encode())
+}
+
+/// Checks that no identity, registrar or sub-identity was lost, that every registrar and identity
+/// decodes, that every pending `FeePaid` judgement is recorded in `PendingRequests` and that no
+/// gaming platform handle with an empty typed field is left in the additional fields.
+#[cfg(feature = "try-runtime")]
+pub fn post_upgrade<T: Config>(state: Vec<u8>) -> Result<(), &'static str> {
+	let before = Counts::decode(&mut &state[..]).map_err(|_| "cannot decode the counts")?;
+	frame_support::ensure!(
+		<PalletVersion<T>>::get() == StorageVersion::V5MultiCurrency,
+		"the storage version was not updated"
+	);
+	let registrars = if unhashed::exists(&<Registrars<T>>::hashed_key()) {
+		<Registrars<T>>::try_get().map_err(|_| "the registrars do not decode")?
+	} else {
+		Default::default()
+	};
+
+	let after = counts::<T>(registrars.iter().flatten().count() as u32);
+	frame_support::ensure!(after.identities == before.identities, "identities were lost");
+	frame_support::ensure!(after.registrars == before.registrars, "registrars were lost");
+	frame_support::ensure!(
+		after.subs == before.subs && after.super_of == before.super_of,
+		"sub-identities were lost"
+	);
+	frame_support::ensure!(
+		<PendingRequests<T>>::iter_keys().count() as u32 == after.fee_paid,
+		"pending requests do not match the FeePaid judgements"
+	);
+	for who in <IdentityOf<T>>::iter_keys() {
+		let mut id = <IdentityOf<T>>::try_get(&who).map_err(|_| "an identity does not decode")?;
+		let keys: Vec<Data> = id.info.additional.iter().map(|(key, _)| key.clone()).collect();
+		frame_support::ensure!(
+			keys.iter().all(|key| id.info.platform_field(key).map_or(true, |f| *f != Data::None)),
+			"a gaming platform handle was left in the additional fields"
+		);
+	}
+	Ok(())
+}
+
+/// Updates the deposits of up to `limit` identities to the current deposits, see `poke_deposit`.
+///
+/// Identities are processed in storage order after `start`, the last key returned by the
+/// previous batch. Returns the last key of this batch, even if it is too short for an account or
+/// did not decode to one, `None` once every identity is updated, and the weight used. Identities
+/// whose owner cannot reserve an increase keep their deposits.
+pub fn poke_deposits<T: Config>(start: Option<Vec<u8>>, limit: u32) -> (Option<Vec<u8>>, Weight) {
+	let prefix = <IdentityOf<T>>::final_prefix();
+	let mut key = start.unwrap_or_else(|| prefix.to_vec());
+	let mut weight = Weight::zero();
+
+	for _ in 0..limit {
+		let next = match sp_io::storage::next_key(&key).filter(|next| next.starts_with(&prefix)) {
+			Some(next) => next,
+			None => return (None, weight),
+		};
+		// Keys are the prefix, the `Twox64Concat` hash and the account.
+		let who = next
+			.get(prefix.len() + 8..)
+			.and_then(|mut raw| T::AccountId::decode(&mut raw).ok());
+		if let Some(who) = who {
+			weight = weight.saturating_add(match Pallet::<T>::do_poke_deposit(&who) {
+				Ok((_, weight)) => weight,
+				Err(_) => T::DbWeight::get().reads(2),
+			});
+		}
+		key = next;
+	}
+	(Some(key), weight)
+}
@@ -21,12 +21,20 @@ use super::*;
 use crate as pallet_identity;
 
 use codec::{Decode, Encode};
-use frame_support::{assert_noop, assert_ok, ord_parameter_types, parameter_types, BoundedVec};
-use frame_system::{EnsureOneOf, EnsureRoot, EnsureSignedBy};
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::Pays,
+	ord_parameter_types, parameter_types,
+	storage::StoragePrefixedMap,
+	traits::{EitherOfDiverse, Everything, Hooks},
+	BoundedVec,
+};
+use frame_system::{EnsureRoot, EnsureSignedBy};
+use orml_traits::parameter_type_with_key;
 use sp_core::H256;
 use sp_runtime::{
-	testing::Header,
-	traits::{BadOrigin, BlakeTwo256, IdentityLookup},
+	testing::{Header, TestSignature, UintAuthorityId},
+	traits::{BadOrigin, BlakeTwo256, Hash, IdentityLookup},
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -40,6 +48,8 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Tokens: orml_tokens::{Pallet, Storage, Config<T>, Event<T>},
+		Currencies: orml_currencies::{Pallet, Call},
 		Identity: pallet_identity::{Pallet, Call, Storage, Event<T>},
 	}
 );
@@ -47,22 +57,22 @@ frame_support::construct_runtime!(
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
 	pub BlockWeights: frame_system::limits::BlockWeights =
-		frame_system::limits::BlockWeights::simple_max(1024);
+		frame_system::limits::BlockWeights::simple_max(Weight::from_parts(1024, 0));
 }
 impl frame_system::Config for Test {
 	type BaseCallFilter = frame_support::traits::Everything;
 	type BlockWeights = ();
 	type BlockLength = ();
-	type Origin = Origin;
+	type RuntimeOrigin = RuntimeOrigin;
 	type Index = u64;
 	type BlockNumber = u64;
 	type Hash = H256;
-	type Call = Call;
+	type RuntimeCall = RuntimeCall;
 	type Hashing = BlakeTwo256;
 	type AccountId = u64;
 	type Lookup = IdentityLookup<Self::AccountId>;
 	type Header = Header;
-	type Event = Event;
+	type RuntimeEvent = RuntimeEvent;
 	type BlockHashCount = BlockHashCount;
 	type DbWeight = ();
 	type Version = ();
@@ -73,13 +83,14 @@ impl frame_system::Config for Test {
 	type SystemWeightInfo = ();
 	type SS58Prefix = ();
 	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 parameter_types! {
 	pub const ExistentialDeposit: u64 = 1;
 }
 impl pallet_balances::Config for Test {
 	type Balance = u64;
-	type Event = Event;
+	type RuntimeEvent = RuntimeEvent;
 	type DustRemoval = ();
 	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = System;
@@ -88,6 +99,48 @@ impl pallet_balances::Config for Test {
 	type ReserveIdentifier = [u8; 8];
 	type WeightInfo = ();
 }
+pub const NATIVE: u32 = 0;
+pub const PLAY: u32 = 1;
+pub const TREASURY: u64 = 99;
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: u32| -> u64 {
+		1
+	};
+}
+impl orml_tokens::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = u64;
+	type Amount = i64;
+	type CurrencyId = u32;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type CurrencyHooks = ();
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type DustRemovalWhitelist = Everything;
+}
+
+parameter_types! {
+	pub const GetNativeCurrencyId: u32 = NATIVE;
+	pub const TreasuryAccount: u64 = TREASURY;
+}
+impl orml_currencies::Config for Test {
+	type MultiCurrency = Tokens;
+	type NativeCurrency = orml_currencies::BasicCurrencyAdapter<Test, Balances, i64, u64>;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type WeightInfo = ();
+}
+
+/// PLAY deposits are twice the native deposits, no other currency has a rate.
+pub struct DoubleForPlay;
+impl DepositRate<u32, u64> for DoubleForPlay {
+	fn convert(currency_id: u32, native_deposit: u64) -> Option<u64> {
+		(currency_id == PLAY).then(|| native_deposit * 2)
+	}
+}
+
 parameter_types! {
 	pub const BasicDeposit: u64 = 10;
 	pub const FieldDeposit: u64 = 10;
@@ -95,17 +148,37 @@ parameter_types! {
 	pub const MaxSubAccounts: u32 = 2;
 	pub const MaxAdditionalFields: u32 = 2;
 	pub const MaxRegistrars: u32 = 20;
+	pub const UsernameDeposit: u64 = 5;
+	pub const UsernameDuration: u64 = 100;
+	pub const MaxUsernameLength: u32 = 16;
+	pub const MaxSuffixLength: u32 = 8;
 }
 ord_parameter_types! {
 	pub const One: u64 = 1;
 	pub const Two: u64 = 2;
 }
-type EnsureOneOrRoot = EnsureOneOf<u64, EnsureRoot<u64>, EnsureSignedBy<One, u64>>;
-type EnsureTwoOrRoot = EnsureOneOf<u64, EnsureRoot<u64>, EnsureSignedBy<Two, u64>>;
+type EnsureOneOrRoot = EitherOfDiverse<EnsureRoot<u64>, EnsureSignedBy<One, u64>>;
+type EnsureTwoOrRoot = EitherOfDiverse<EnsureRoot<u64>, EnsureSignedBy<Two, u64>>;
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct MockSigner;
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_identity::BenchmarkHelper<UintAuthorityId, u64, TestSignature> for MockSigner {
+	fn signer() -> (UintAuthorityId, u64) {
+		(UintAuthorityId(1_000), 1_000)
+	}
+
+	fn sign(signer: &UintAuthorityId, message: &[u8]) -> TestSignature {
+		TestSignature(signer.0, message.to_vec())
+	}
+}
 impl pallet_identity::Config for Test {
-	type Event = Event;
-	type Currency = Balances;
-	type Slashed = ();
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Currencies;
+	type NativeCurrencyId = GetNativeCurrencyId;
+	type DepositRate = DoubleForPlay;
+	type DepositCurrencyOrigin = EnsureOneOrRoot;
+	type Slashed = TreasuryAccount;
 	type BasicDeposit = BasicDeposit;
 	type FieldDeposit = FieldDeposit;
 	type SubAccountDeposit = SubAccountDeposit;
@@ -114,6 +187,15 @@ impl pallet_identity::Config for Test {
 	type MaxRegistrars = MaxRegistrars;
 	type RegistrarOrigin = EnsureOneOrRoot;
 	type ForceOrigin = EnsureTwoOrRoot;
+	type OffchainSignature = TestSignature;
+	type SigningPublicKey = UintAuthorityId;
+	type UsernameAuthorityOrigin = EnsureOneOrRoot;
+	type UsernameDeposit = UsernameDeposit;
+	type UsernameDuration = UsernameDuration;
+	type MaxUsernameLength = MaxUsernameLength;
+	type MaxSuffixLength = MaxSuffixLength;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = MockSigner;
 	type WeightInfo = ();
 }
 
@@ -124,6 +206,9 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 	}
 	.assimilate_storage(&mut t)
 	.unwrap();
+	orml_tokens::GenesisConfig::<Test> { balances: vec![(10, PLAY, 100), (20, PLAY, 100)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
 	t.into()
 }
 
@@ -135,6 +220,27 @@ fn ten() -> IdentityInfo<MaxAdditionalFields> {
 	}
 }
 
+/// Stores `id` as the identity of `who` in the layout before the typed gaming fields.
+fn put_v3_identity(who: u64, id: Registration<u64, MaxRegistrars, MaxAdditionalFields>) {
+	let info = id.info;
+	let old = migration::RegistrationV3::<u64, MaxRegistrars, MaxAdditionalFields> {
+		judgements: id.judgements,
+		deposit: id.deposit,
+		info: migration::IdentityInfoV3 {
+			additional: info.additional,
+			display: info.display,
+			legal: info.legal,
+			web: info.web,
+			riot: info.riot,
+			email: info.email,
+			pgp_fingerprint: info.pgp_fingerprint,
+			image: info.image,
+			twitter: info.twitter,
+		},
+	};
+	frame_support::storage::unhashed::put(&IdentityOf::<Test>::hashed_key_for(who), &old);
+}
+
 fn twenty() -> IdentityInfo<MaxAdditionalFields> {
 	IdentityInfo {
 		display: Data::Raw(b"twenty".to_vec().try_into().unwrap()),
@@ -148,41 +254,44 @@ fn editing_subaccounts_should_work() {
 	new_test_ext().execute_with(|| {
 		let data = |x| Data::Raw(vec![x; 1].try_into().unwrap());
 
-		assert_noop!(Identity::add_sub(Origin::signed(10), 20, data(1)), Error::<Test>::NoIdentity);
+		assert_noop!(
+			Identity::add_sub(RuntimeOrigin::signed(10), 20, data(1)),
+			Error::<Test>::NoIdentity
+		);
 
-		assert_ok!(Identity::set_identity(Origin::signed(10), Box::new(ten())));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(10), Box::new(ten())));
 
 		// first sub account
-		assert_ok!(Identity::add_sub(Origin::signed(10), 1, data(1)));
+		assert_ok!(Identity::add_sub(RuntimeOrigin::signed(10), 1, data(1)));
 		assert_eq!(SuperOf::<Test>::get(1), Some((10, data(1))));
 		assert_eq!(Balances::free_balance(10), 80);
 
 		// second sub account
-		assert_ok!(Identity::add_sub(Origin::signed(10), 2, data(2)));
+		assert_ok!(Identity::add_sub(RuntimeOrigin::signed(10), 2, data(2)));
 		assert_eq!(SuperOf::<Test>::get(1), Some((10, data(1))));
 		assert_eq!(SuperOf::<Test>::get(2), Some((10, data(2))));
 		assert_eq!(Balances::free_balance(10), 70);
 
 		// third sub account is too many
 		assert_noop!(
-			Identity::add_sub(Origin::signed(10), 3, data(3)),
+			Identity::add_sub(RuntimeOrigin::signed(10), 3, data(3)),
 			Error::<Test>::TooManySubAccounts
 		);
 
 		// rename first sub account
-		assert_ok!(Identity::rename_sub(Origin::signed(10), 1, data(11)));
+		assert_ok!(Identity::rename_sub(RuntimeOrigin::signed(10), 1, data(11)));
 		assert_eq!(SuperOf::<Test>::get(1), Some((10, data(11))));
 		assert_eq!(SuperOf::<Test>::get(2), Some((10, data(2))));
 		assert_eq!(Balances::free_balance(10), 70);
 
 		// remove first sub account
-		assert_ok!(Identity::remove_sub(Origin::signed(10), 1));
+		assert_ok!(Identity::remove_sub(RuntimeOrigin::signed(10), 1));
 		assert_eq!(SuperOf::<Test>::get(1), None);
 		assert_eq!(SuperOf::<Test>::get(2), Some((10, data(2))));
 		assert_eq!(Balances::free_balance(10), 80);
 
 		// add third sub account
-		assert_ok!(Identity::add_sub(Origin::signed(10), 3, data(3)));
+		assert_ok!(Identity::add_sub(RuntimeOrigin::signed(10), 3, data(3)));
 		assert_eq!(SuperOf::<Test>::get(1), None);
 		assert_eq!(SuperOf::<Test>::get(2), Some((10, data(2))));
 		assert_eq!(SuperOf::<Test>::get(3), Some((10, data(3))));
@@ -195,27 +304,27 @@ fn resolving_subaccount_ownership_works() {
 	new_test_ext().execute_with(|| {
 		let data = |x| Data::Raw(vec![x; 1].try_into().unwrap());
 
-		assert_ok!(Identity::set_identity(Origin::signed(10), Box::new(ten())));
-		assert_ok!(Identity::set_identity(Origin::signed(20), Box::new(twenty())));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(10), Box::new(ten())));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(20), Box::new(twenty())));
 
 		// 10 claims 1 as a subaccount
-		assert_ok!(Identity::add_sub(Origin::signed(10), 1, data(1)));
+		assert_ok!(Identity::add_sub(RuntimeOrigin::signed(10), 1, data(1)));
 		assert_eq!(Balances::free_balance(1), 10);
 		assert_eq!(Balances::free_balance(10), 80);
 		assert_eq!(Balances::reserved_balance(10), 20);
 		// 20 cannot claim 1 now
 		assert_noop!(
-			Identity::add_sub(Origin::signed(20), 1, data(1)),
+			Identity::add_sub(RuntimeOrigin::signed(20), 1, data(1)),
 			Error::<Test>::AlreadyClaimed
 		);
 		// 1 wants to be with 20 so it quits from 10
-		assert_ok!(Identity::quit_sub(Origin::signed(1)));
+		assert_ok!(Identity::quit_sub(RuntimeOrigin::signed(1)));
 		// 1 gets the 10 that 10 paid.
 		assert_eq!(Balances::free_balance(1), 20);
 		assert_eq!(Balances::free_balance(10), 80);
 		assert_eq!(Balances::reserved_balance(10), 10);
 		// 20 can claim 1 now
-		assert_ok!(Identity::add_sub(Origin::signed(20), 1, data(1)));
+		assert_ok!(Identity::add_sub(RuntimeOrigin::signed(20), 1, data(1)));
 	});
 }
 
@@ -232,13 +341,50 @@ fn trailing_zeros_decodes_into_default_data() {
 #[test]
 fn adding_registrar_should_work() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(Identity::add_registrar(Origin::signed(1), 3));
-		assert_ok!(Identity::set_fee(Origin::signed(3), 0, 10));
+		assert_ok!(Identity::add_registrar(RuntimeOrigin::signed(1), 3));
+		assert_ok!(Identity::set_fee(RuntimeOrigin::signed(3), 0, 10));
 		let fields = IdentityFields(IdentityField::Display | IdentityField::Legal);
-		assert_ok!(Identity::set_fields(Origin::signed(3), 0, fields));
+		assert_ok!(Identity::set_fields(RuntimeOrigin::signed(3), 0, fields));
 		assert_eq!(
 			Identity::registrars(),
-			vec![Some(RegistrarInfo { account: 3, fee: 10, fields })]
+			vec![Some(RegistrarInfo {
+				account: 3,
+				fee: 10,
+				fee_currency: NATIVE,
+				fields,
+				judgement_period: None,
+			})]
+		);
+	});
+}
+
+#[test]
+fn registrars_should_require_gaming_fields() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Identity::add_registrar(RuntimeOrigin::signed(1), 3));
+		let fields = IdentityFields(IdentityField::Display | IdentityField::Discord);
+		assert_ok!(Identity::set_fields(RuntimeOrigin::signed(3), 0, fields));
+
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(10), Box::new(ten())));
+		assert!(!Identity::identity(10).unwrap().info.has_fields(fields));
+		assert_noop!(
+			Identity::request_judgement(RuntimeOrigin::signed(10), 0, 10),
+			Error::<Test>::MissingFields
+		);
+
+		let info =
+			IdentityInfo { discord: Data::Raw(b"ten#0010".to_vec().try_into().unwrap()), ..ten() };
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(10), Box::new(info)));
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(10), 0, 10));
+		assert_ok!(Identity::provide_judgement(
+			RuntimeOrigin::signed(3),
+			0,
+			10,
+			Judgement::Reasonable
+		));
+		assert_eq!(
+			Identity::identity(10).unwrap().judgements.into_inner(),
+			vec![(0, Judgement::Reasonable)]
 		);
 	});
 }
@@ -247,31 +393,117 @@ fn adding_registrar_should_work() {
 fn amount_of_registrars_is_limited() {
 	new_test_ext().execute_with(|| {
 		for i in 1..MaxRegistrars::get() + 1 {
-			assert_ok!(Identity::add_registrar(Origin::signed(1), i as u64));
+			assert_ok!(Identity::add_registrar(RuntimeOrigin::signed(1), i as u64));
 		}
 		let last_registrar = MaxRegistrars::get() as u64 + 1;
 		assert_noop!(
-			Identity::add_registrar(Origin::signed(1), last_registrar),
+			Identity::add_registrar(RuntimeOrigin::signed(1), last_registrar),
 			Error::<Test>::TooManyRegistrars
 		);
 	});
 }
 
+#[test]
+fn removing_registrar_should_refund_pending_requests() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Identity::add_registrar(RuntimeOrigin::signed(1), 3));
+		assert_ok!(Identity::set_fee(RuntimeOrigin::signed(3), 0, 10));
+		assert_ok!(Identity::add_registrar(RuntimeOrigin::signed(1), 4));
+		assert_ok!(Identity::set_fee(RuntimeOrigin::signed(4), 1, 10));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(10), Box::new(ten())));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(20), Box::new(twenty())));
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(20), 0, 10));
+		assert_ok!(Identity::provide_judgement(
+			RuntimeOrigin::signed(3),
+			0,
+			20,
+			Judgement::Reasonable
+		));
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(10), 0, 10));
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(10), 1, 10));
+		assert_eq!(Balances::free_balance(10), 70);
+
+		assert_noop!(Identity::remove_registrar(RuntimeOrigin::signed(3), 0, 1), BadOrigin);
+		assert_noop!(
+			Identity::remove_registrar(RuntimeOrigin::signed(1), 0, 0),
+			Error::<Test>::BadWitness
+		);
+		assert_ok!(Identity::remove_registrar(RuntimeOrigin::signed(1), 0, 1));
+		System::assert_last_event(
+			pallet_identity::Event::RegistrarRemoved { registrar_index: 0, refunded: 1 }.into(),
+		);
+
+		// The pending fee is returned, the judgement already given is kept.
+		assert_eq!(Balances::free_balance(10), 80);
+		assert_eq!(Identity::identity(10).unwrap().judgements, vec![(1, Judgement::FeePaid(10))]);
+		assert_eq!(Identity::identity(20).unwrap().judgements, vec![(0, Judgement::Reasonable)]);
+		assert_eq!(PendingRequests::<Test>::iter_key_prefix(0).count(), 0);
+
+		// The other indices are stable.
+		assert_eq!(
+			Identity::registrars(),
+			vec![
+				None,
+				Some(RegistrarInfo {
+					account: 4,
+					fee: 10,
+					fee_currency: NATIVE,
+					fields: Default::default(),
+					judgement_period: None,
+				})
+			]
+		);
+		assert_noop!(
+			Identity::request_judgement(RuntimeOrigin::signed(10), 0, 10),
+			Error::<Test>::EmptyIndex
+		);
+		assert_noop!(
+			Identity::remove_registrar(RuntimeOrigin::signed(1), 0, 0),
+			Error::<Test>::InvalidIndex
+		);
+		assert_ok!(Identity::add_registrar(RuntimeOrigin::signed(1), 5));
+		System::assert_last_event(
+			pallet_identity::Event::RegistrarAdded { registrar_index: 2 }.into(),
+		);
+	});
+}
+
+#[test]
+fn backfilled_requests_should_be_refunded_by_remove_registrar() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Identity::add_registrar(RuntimeOrigin::signed(1), 3));
+		assert_ok!(Identity::set_fee(RuntimeOrigin::signed(3), 0, 10));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(10), Box::new(ten())));
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(10), 0, 10));
+		assert_eq!(Balances::free_balance(10), 80);
+
+		// Requests made before `PendingRequests` existed are not listed.
+		let _ = PendingRequests::<Test>::clear(u32::MAX, None);
+		assert_eq!(migration::backfill_pending_requests::<Test>(true), (1, 1));
+		assert!(PendingRequests::<Test>::contains_key(0, 10));
+
+		assert_ok!(Identity::remove_registrar(RuntimeOrigin::signed(1), 0, 1));
+		assert_eq!(Balances::free_balance(10), 90);
+		assert!(Identity::identity(10).unwrap().judgements.is_empty());
+	});
+}
+
 #[test]
 fn registration_should_work() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(Identity::add_registrar(Origin::signed(1), 3));
-		assert_ok!(Identity::set_fee(Origin::signed(3), 0, 10));
+		assert_ok!(Identity::add_registrar(RuntimeOrigin::signed(1), 3));
+		assert_ok!(Identity::set_fee(RuntimeOrigin::signed(3), 0, 10));
 		let mut three_fields = ten();
 		three_fields.additional.try_push(Default::default()).unwrap();
 		three_fields.additional.try_push(Default::default()).unwrap();
 		assert_eq!(three_fields.additional.try_push(Default::default()), Err(()));
-		assert_ok!(Identity::set_identity(Origin::signed(10), Box::new(ten())));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(10), Box::new(ten())));
 		assert_eq!(Identity::identity(10).unwrap().info, ten());
 		assert_eq!(Balances::free_balance(10), 90);
-		assert_ok!(Identity::clear_identity(Origin::signed(10)));
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(10)));
 		assert_eq!(Balances::free_balance(10), 100);
-		assert_noop!(Identity::clear_identity(Origin::signed(10)), Error::<Test>::NotNamed);
+		assert_noop!(Identity::clear_identity(RuntimeOrigin::signed(10)), Error::<Test>::NotNamed);
 	});
 }
 
@@ -279,38 +511,214 @@ fn registration_should_work() {
 fn uninvited_judgement_should_work() {
 	new_test_ext().execute_with(|| {
 		assert_noop!(
-			Identity::provide_judgement(Origin::signed(3), 0, 10, Judgement::Reasonable),
+			Identity::provide_judgement(RuntimeOrigin::signed(3), 0, 10, Judgement::Reasonable),
 			Error::<Test>::InvalidIndex
 		);
 
-		assert_ok!(Identity::add_registrar(Origin::signed(1), 3));
+		assert_ok!(Identity::add_registrar(RuntimeOrigin::signed(1), 3));
 		assert_noop!(
-			Identity::provide_judgement(Origin::signed(3), 0, 10, Judgement::Reasonable),
+			Identity::provide_judgement(RuntimeOrigin::signed(3), 0, 10, Judgement::Reasonable),
 			Error::<Test>::InvalidTarget
 		);
 
-		assert_ok!(Identity::set_identity(Origin::signed(10), Box::new(ten())));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(10), Box::new(ten())));
 		assert_noop!(
-			Identity::provide_judgement(Origin::signed(10), 0, 10, Judgement::Reasonable),
+			Identity::provide_judgement(RuntimeOrigin::signed(10), 0, 10, Judgement::Reasonable),
 			Error::<Test>::InvalidIndex
 		);
 		assert_noop!(
-			Identity::provide_judgement(Origin::signed(3), 0, 10, Judgement::FeePaid(1)),
+			Identity::provide_judgement(RuntimeOrigin::signed(3), 0, 10, Judgement::FeePaid(1)),
+			Error::<Test>::InvalidJudgement
+		);
+
+		assert_ok!(Identity::provide_judgement(
+			RuntimeOrigin::signed(3),
+			0,
+			10,
+			Judgement::Reasonable
+		));
+		assert_eq!(Identity::identity(10).unwrap().judgements, vec![(0, Judgement::Reasonable)]);
+	});
+}
+
+fn sign_judgement(
+	registrar: u64,
+	reg_index: RegistrarIndex,
+	target: u64,
+	judgement: &Judgement<u64>,
+	expiry: u64,
+) -> TestSignature {
+	let identity = BlakeTwo256::hash_of(&Identity::identity(target).unwrap().info);
+	let nonce = Identity::judgement_nonce(reg_index, target);
+	let payload =
+		Identity::judgement_payload(reg_index, &target, identity, judgement, nonce, expiry);
+	TestSignature(registrar, payload)
+}
+
+#[test]
+fn signed_judgement_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Identity::add_registrar(RuntimeOrigin::signed(1), 3));
+		assert_ok!(Identity::set_fee(RuntimeOrigin::signed(3), 0, 10));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(10), Box::new(ten())));
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(10), 0, 10));
+		let identity = BlakeTwo256::hash_of(&ten());
+
+		let signature = sign_judgement(3, 0, 10, &Judgement::Reasonable, 5);
+		assert_noop!(
+			Identity::provide_judgement_signed(
+				RuntimeOrigin::signed(20),
+				0,
+				10,
+				Judgement::KnownGood,
+				identity,
+				5,
+				signature.clone()
+			),
+			Error::<Test>::InvalidSignature
+		);
+		assert_noop!(
+			Identity::provide_judgement_signed(
+				RuntimeOrigin::signed(20),
+				0,
+				10,
+				Judgement::Reasonable,
+				BlakeTwo256::hash_of(&twenty()),
+				5,
+				signature.clone()
+			),
+			Error::<Test>::JudgementForDifferentIdentity
+		);
+		let forged = sign_judgement(20, 0, 10, &Judgement::Reasonable, 5);
+		assert_noop!(
+			Identity::provide_judgement_signed(
+				RuntimeOrigin::signed(20),
+				0,
+				10,
+				Judgement::Reasonable,
+				identity,
+				5,
+				forged
+			),
+			Error::<Test>::InvalidSignature
+		);
+		let fee_paid = sign_judgement(3, 0, 10, &Judgement::FeePaid(1), 5);
+		assert_noop!(
+			Identity::provide_judgement_signed(
+				RuntimeOrigin::signed(20),
+				0,
+				10,
+				Judgement::FeePaid(1),
+				identity,
+				5,
+				fee_paid
+			),
 			Error::<Test>::InvalidJudgement
 		);
 
-		assert_ok!(Identity::provide_judgement(Origin::signed(3), 0, 10, Judgement::Reasonable));
+		// Anyone may submit the signed judgement, the fee goes to the registrar.
+		assert_ok!(Identity::provide_judgement_signed(
+			RuntimeOrigin::signed(20),
+			0,
+			10,
+			Judgement::Reasonable,
+			identity,
+			5,
+			signature
+		));
 		assert_eq!(Identity::identity(10).unwrap().judgements, vec![(0, Judgement::Reasonable)]);
+		assert_eq!(Balances::free_balance(3), 20);
+		assert_eq!(Balances::free_balance(20), 100);
+	});
+}
+
+#[test]
+fn signed_judgement_should_be_bound_to_the_chain() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Identity::add_registrar(RuntimeOrigin::signed(1), 3));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(10), Box::new(ten())));
+		let signature = sign_judgement(3, 0, 10, &Judgement::Reasonable, 5);
+
+		// Another chain with the same registrars and identities.
+		frame_system::BlockHash::<Test>::insert(0, H256::repeat_byte(1));
+		assert_noop!(
+			Identity::provide_judgement_signed(
+				RuntimeOrigin::signed(20),
+				0,
+				10,
+				Judgement::Reasonable,
+				BlakeTwo256::hash_of(&ten()),
+				5,
+				signature
+			),
+			Error::<Test>::InvalidSignature
+		);
+	});
+}
+
+#[test]
+fn signed_judgement_cannot_be_replayed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Identity::add_registrar(RuntimeOrigin::signed(1), 3));
+		assert_ok!(Identity::set_fee(RuntimeOrigin::signed(3), 0, 10));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(10), Box::new(ten())));
+		let identity = BlakeTwo256::hash_of(&ten());
+		let provide = |judgement: Judgement<u64>, signature: TestSignature| {
+			Identity::provide_judgement_signed(
+				RuntimeOrigin::signed(20),
+				0,
+				10,
+				judgement,
+				identity,
+				5,
+				signature,
+			)
+		};
+
+		let reasonable = sign_judgement(3, 0, 10, &Judgement::Reasonable, 5);
+		assert_ok!(provide(Judgement::Reasonable, reasonable.clone()));
+		assert_noop!(
+			provide(Judgement::Reasonable, reasonable.clone()),
+			Error::<Test>::InvalidSignature
+		);
+
+		// A new request cannot be answered with the old signature.
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(10), 0, 10));
+		assert_noop!(
+			provide(Judgement::Reasonable, reasonable.clone()),
+			Error::<Test>::InvalidSignature
+		);
+
+		// A signature is invalidated by a later judgement given directly.
+		let erroneous = sign_judgement(3, 0, 10, &Judgement::Erroneous, 5);
+		assert_ok!(Identity::provide_judgement(
+			RuntimeOrigin::signed(3),
+			0,
+			10,
+			Judgement::KnownGood
+		));
+		assert_noop!(provide(Judgement::Erroneous, erroneous), Error::<Test>::InvalidSignature);
+		assert_eq!(Identity::identity(10).unwrap().judgements, vec![(0, Judgement::KnownGood)]);
+
+		// Nor can a signature be used after its expiry.
+		let outdated = sign_judgement(3, 0, 10, &Judgement::OutOfDate, 5);
+		System::set_block_number(6);
+		assert_noop!(provide(Judgement::OutOfDate, outdated), Error::<Test>::JudgementExpired);
 	});
 }
 
 #[test]
 fn clearing_judgement_should_work() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(Identity::add_registrar(Origin::signed(1), 3));
-		assert_ok!(Identity::set_identity(Origin::signed(10), Box::new(ten())));
-		assert_ok!(Identity::provide_judgement(Origin::signed(3), 0, 10, Judgement::Reasonable));
-		assert_ok!(Identity::clear_identity(Origin::signed(10)));
+		assert_ok!(Identity::add_registrar(RuntimeOrigin::signed(1), 3));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(10), Box::new(ten())));
+		assert_ok!(Identity::provide_judgement(
+			RuntimeOrigin::signed(3),
+			0,
+			10,
+			Judgement::Reasonable
+		));
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(10)));
 		assert_eq!(Identity::identity(10), None);
 	});
 }
@@ -318,12 +726,15 @@ fn clearing_judgement_should_work() {
 #[test]
 fn killing_slashing_should_work() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(Identity::set_identity(Origin::signed(10), Box::new(ten())));
-		assert_noop!(Identity::kill_identity(Origin::signed(1), 10), BadOrigin);
-		assert_ok!(Identity::kill_identity(Origin::signed(2), 10));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(10), Box::new(ten())));
+		assert_noop!(Identity::kill_identity(RuntimeOrigin::signed(1), 10), BadOrigin);
+		assert_ok!(Identity::kill_identity(RuntimeOrigin::signed(2), 10));
 		assert_eq!(Identity::identity(10), None);
 		assert_eq!(Balances::free_balance(10), 90);
-		assert_noop!(Identity::kill_identity(Origin::signed(2), 10), Error::<Test>::NotNamed);
+		assert_noop!(
+			Identity::kill_identity(RuntimeOrigin::signed(2), 10),
+			Error::<Test>::NotNamed
+		);
 	});
 }
 
@@ -331,17 +742,20 @@ fn killing_slashing_should_work() {
 fn setting_subaccounts_should_work() {
 	new_test_ext().execute_with(|| {
 		let mut subs = vec![(20, Data::Raw(vec![40; 1].try_into().unwrap()))];
-		assert_noop!(Identity::set_subs(Origin::signed(10), subs.clone()), Error::<Test>::NotFound);
+		assert_noop!(
+			Identity::set_subs(RuntimeOrigin::signed(10), subs.clone()),
+			Error::<Test>::NotFound
+		);
 
-		assert_ok!(Identity::set_identity(Origin::signed(10), Box::new(ten())));
-		assert_ok!(Identity::set_subs(Origin::signed(10), subs.clone()));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(10), Box::new(ten())));
+		assert_ok!(Identity::set_subs(RuntimeOrigin::signed(10), subs.clone()));
 		assert_eq!(Balances::free_balance(10), 80);
 		assert_eq!(Identity::subs_of(10), (10, vec![20].try_into().unwrap()));
 		assert_eq!(Identity::super_of(20), Some((10, Data::Raw(vec![40; 1].try_into().unwrap()))));
 
 		// push another item and re-set it.
 		subs.push((30, Data::Raw(vec![50; 1].try_into().unwrap())));
-		assert_ok!(Identity::set_subs(Origin::signed(10), subs.clone()));
+		assert_ok!(Identity::set_subs(RuntimeOrigin::signed(10), subs.clone()));
 		assert_eq!(Balances::free_balance(10), 70);
 		assert_eq!(Identity::subs_of(10), (20, vec![20, 30].try_into().unwrap()));
 		assert_eq!(Identity::super_of(20), Some((10, Data::Raw(vec![40; 1].try_into().unwrap()))));
@@ -349,7 +763,7 @@ fn setting_subaccounts_should_work() {
 
 		// switch out one of the items and re-set.
 		subs[0] = (40, Data::Raw(vec![60; 1].try_into().unwrap()));
-		assert_ok!(Identity::set_subs(Origin::signed(10), subs.clone()));
+		assert_ok!(Identity::set_subs(RuntimeOrigin::signed(10), subs.clone()));
 		assert_eq!(Balances::free_balance(10), 70); // no change in the balance
 		assert_eq!(Identity::subs_of(10), (20, vec![40, 30].try_into().unwrap()));
 		assert_eq!(Identity::super_of(20), None);
@@ -357,7 +771,7 @@ fn setting_subaccounts_should_work() {
 		assert_eq!(Identity::super_of(40), Some((10, Data::Raw(vec![60; 1].try_into().unwrap()))));
 
 		// clear
-		assert_ok!(Identity::set_subs(Origin::signed(10), vec![]));
+		assert_ok!(Identity::set_subs(RuntimeOrigin::signed(10), vec![]));
 		assert_eq!(Balances::free_balance(10), 90);
 		assert_eq!(Identity::subs_of(10), (0, BoundedVec::default()));
 		assert_eq!(Identity::super_of(30), None);
@@ -365,7 +779,7 @@ fn setting_subaccounts_should_work() {
 
 		subs.push((20, Data::Raw(vec![40; 1].try_into().unwrap())));
 		assert_noop!(
-			Identity::set_subs(Origin::signed(10), subs.clone()),
+			Identity::set_subs(RuntimeOrigin::signed(10), subs.clone()),
 			Error::<Test>::TooManySubAccounts
 		);
 	});
@@ -374,12 +788,12 @@ fn setting_subaccounts_should_work() {
 #[test]
 fn clearing_account_should_remove_subaccounts_and_refund() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(Identity::set_identity(Origin::signed(10), Box::new(ten())));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(10), Box::new(ten())));
 		assert_ok!(Identity::set_subs(
-			Origin::signed(10),
+			RuntimeOrigin::signed(10),
 			vec![(20, Data::Raw(vec![40; 1].try_into().unwrap()))]
 		));
-		assert_ok!(Identity::clear_identity(Origin::signed(10)));
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(10)));
 		assert_eq!(Balances::free_balance(10), 100);
 		assert!(Identity::super_of(20).is_none());
 	});
@@ -388,12 +802,12 @@ fn clearing_account_should_remove_subaccounts_and_refund() {
 #[test]
 fn killing_account_should_remove_subaccounts_and_not_refund() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(Identity::set_identity(Origin::signed(10), Box::new(ten())));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(10), Box::new(ten())));
 		assert_ok!(Identity::set_subs(
-			Origin::signed(10),
+			RuntimeOrigin::signed(10),
 			vec![(20, Data::Raw(vec![40; 1].try_into().unwrap()))]
 		));
-		assert_ok!(Identity::kill_identity(Origin::signed(2), 10));
+		assert_ok!(Identity::kill_identity(RuntimeOrigin::signed(2), 10));
 		assert_eq!(Balances::free_balance(10), 80);
 		assert!(Identity::super_of(20).is_none());
 	});
@@ -402,18 +816,29 @@ fn killing_account_should_remove_subaccounts_and_not_refund() {
 #[test]
 fn cancelling_requested_judgement_should_work() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(Identity::add_registrar(Origin::signed(1), 3));
-		assert_ok!(Identity::set_fee(Origin::signed(3), 0, 10));
-		assert_noop!(Identity::cancel_request(Origin::signed(10), 0), Error::<Test>::NoIdentity);
-		assert_ok!(Identity::set_identity(Origin::signed(10), Box::new(ten())));
-		assert_ok!(Identity::request_judgement(Origin::signed(10), 0, 10));
-		assert_ok!(Identity::cancel_request(Origin::signed(10), 0));
+		assert_ok!(Identity::add_registrar(RuntimeOrigin::signed(1), 3));
+		assert_ok!(Identity::set_fee(RuntimeOrigin::signed(3), 0, 10));
+		assert_noop!(
+			Identity::cancel_request(RuntimeOrigin::signed(10), 0),
+			Error::<Test>::NoIdentity
+		);
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(10), Box::new(ten())));
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(10), 0, 10));
+		assert_ok!(Identity::cancel_request(RuntimeOrigin::signed(10), 0));
 		assert_eq!(Balances::free_balance(10), 90);
-		assert_noop!(Identity::cancel_request(Origin::signed(10), 0), Error::<Test>::NotFound);
+		assert_noop!(
+			Identity::cancel_request(RuntimeOrigin::signed(10), 0),
+			Error::<Test>::NotFound
+		);
 
-		assert_ok!(Identity::provide_judgement(Origin::signed(3), 0, 10, Judgement::Reasonable));
+		assert_ok!(Identity::provide_judgement(
+			RuntimeOrigin::signed(3),
+			0,
+			10,
+			Judgement::Reasonable
+		));
 		assert_noop!(
-			Identity::cancel_request(Origin::signed(10), 0),
+			Identity::cancel_request(RuntimeOrigin::signed(10), 0),
 			Error::<Test>::JudgementGiven
 		);
 	});
@@ -422,49 +847,59 @@ fn cancelling_requested_judgement_should_work() {
 #[test]
 fn requesting_judgement_should_work() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(Identity::add_registrar(Origin::signed(1), 3));
-		assert_ok!(Identity::set_fee(Origin::signed(3), 0, 10));
-		assert_ok!(Identity::set_identity(Origin::signed(10), Box::new(ten())));
+		assert_ok!(Identity::add_registrar(RuntimeOrigin::signed(1), 3));
+		assert_ok!(Identity::set_fee(RuntimeOrigin::signed(3), 0, 10));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(10), Box::new(ten())));
 		assert_noop!(
-			Identity::request_judgement(Origin::signed(10), 0, 9),
+			Identity::request_judgement(RuntimeOrigin::signed(10), 0, 9),
 			Error::<Test>::FeeChanged
 		);
-		assert_ok!(Identity::request_judgement(Origin::signed(10), 0, 10));
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(10), 0, 10));
 		// 10 for the judgement request, 10 for the identity.
 		assert_eq!(Balances::free_balance(10), 80);
 
 		// Re-requesting won't work as we already paid.
 		assert_noop!(
-			Identity::request_judgement(Origin::signed(10), 0, 10),
+			Identity::request_judgement(RuntimeOrigin::signed(10), 0, 10),
 			Error::<Test>::StickyJudgement
 		);
-		assert_ok!(Identity::provide_judgement(Origin::signed(3), 0, 10, Judgement::Erroneous));
+		assert_ok!(Identity::provide_judgement(
+			RuntimeOrigin::signed(3),
+			0,
+			10,
+			Judgement::Erroneous
+		));
 		// Registrar got their payment now.
 		assert_eq!(Balances::free_balance(3), 20);
 
 		// Re-requesting still won't work as it's erroneous.
 		assert_noop!(
-			Identity::request_judgement(Origin::signed(10), 0, 10),
+			Identity::request_judgement(RuntimeOrigin::signed(10), 0, 10),
 			Error::<Test>::StickyJudgement
 		);
 
 		// Requesting from a second registrar still works.
-		assert_ok!(Identity::add_registrar(Origin::signed(1), 4));
-		assert_ok!(Identity::request_judgement(Origin::signed(10), 1, 10));
+		assert_ok!(Identity::add_registrar(RuntimeOrigin::signed(1), 4));
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(10), 1, 10));
 
 		// Re-requesting after the judgement has been reduced works.
-		assert_ok!(Identity::provide_judgement(Origin::signed(3), 0, 10, Judgement::OutOfDate));
-		assert_ok!(Identity::request_judgement(Origin::signed(10), 0, 10));
+		assert_ok!(Identity::provide_judgement(
+			RuntimeOrigin::signed(3),
+			0,
+			10,
+			Judgement::OutOfDate
+		));
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(10), 0, 10));
 	});
 }
 
 #[test]
 fn field_deposit_should_work() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(Identity::add_registrar(Origin::signed(1), 3));
-		assert_ok!(Identity::set_fee(Origin::signed(3), 0, 10));
+		assert_ok!(Identity::add_registrar(RuntimeOrigin::signed(1), 3));
+		assert_ok!(Identity::set_fee(RuntimeOrigin::signed(3), 0, 10));
 		assert_ok!(Identity::set_identity(
-			Origin::signed(10),
+			RuntimeOrigin::signed(10),
 			Box::new(IdentityInfo {
 				additional: vec![
 					(
@@ -488,15 +923,647 @@ fn field_deposit_should_work() {
 #[test]
 fn setting_account_id_should_work() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(Identity::add_registrar(Origin::signed(1), 3));
+		assert_ok!(Identity::add_registrar(RuntimeOrigin::signed(1), 3));
 		// account 4 cannot change the first registrar's identity since it's owned by 3.
 		assert_noop!(
-			Identity::set_account_id(Origin::signed(4), 0, 3),
+			Identity::set_account_id(RuntimeOrigin::signed(4), 0, 3),
 			Error::<Test>::InvalidIndex
 		);
 		// account 3 can, because that's the registrar's current account.
-		assert_ok!(Identity::set_account_id(Origin::signed(3), 0, 4));
+		assert_ok!(Identity::set_account_id(RuntimeOrigin::signed(3), 0, 4));
 		// account 4 can now, because that's their new ID.
-		assert_ok!(Identity::set_account_id(Origin::signed(4), 0, 3));
+		assert_ok!(Identity::set_account_id(RuntimeOrigin::signed(4), 0, 3));
+	});
+}
+
+#[test]
+fn judgements_should_expire_after_judgement_period() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Identity::add_registrar(RuntimeOrigin::signed(1), 3));
+		assert_noop!(
+			Identity::set_judgement_period(RuntimeOrigin::signed(3), 0, Some(0)),
+			Error::<Test>::InvalidJudgementPeriod
+		);
+		assert_noop!(
+			Identity::set_judgement_period(RuntimeOrigin::signed(10), 0, Some(10)),
+			Error::<Test>::InvalidIndex
+		);
+		assert_ok!(Identity::set_judgement_period(RuntimeOrigin::signed(3), 0, Some(10)));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(10), Box::new(ten())));
+		assert_ok!(Identity::provide_judgement(
+			RuntimeOrigin::signed(3),
+			0,
+			10,
+			Judgement::KnownGood
+		));
+		assert_eq!(Identity::judgement_expiry(0, 10), Some(11));
+
+		System::set_block_number(10);
+		Identity::on_idle(10, Weight::MAX);
+		assert_eq!(Identity::judgements_of(&10), vec![(0, Judgement::KnownGood)]);
+
+		// Expired judgements read as `OutOfDate` before `on_idle` processes them.
+		System::set_block_number(11);
+		assert_eq!(Identity::judgements_of(&10), vec![(0, Judgement::OutOfDate)]);
+		assert_eq!(Identity::identity(10).unwrap().judgements, vec![(0, Judgement::KnownGood)]);
+
+		// Not enough weight left to expire a judgement.
+		assert_eq!(Identity::on_idle(11, Weight::zero()), Weight::zero());
+		assert_eq!(Identity::identity(10).unwrap().judgements, vec![(0, Judgement::KnownGood)]);
+
+		Identity::on_idle(11, Weight::MAX);
+		assert_eq!(Identity::identity(10).unwrap().judgements, vec![(0, Judgement::OutOfDate)]);
+		assert_eq!(Identity::judgement_expiry(0, 10), None);
+		assert_eq!(ExpiryCursor::<Test>::get(), Some(12));
+		System::assert_last_event(
+			pallet_identity::Event::JudgementExpired { target: 10, registrar_index: 0 }.into(),
+		);
+	});
+}
+
+#[test]
+fn later_judgements_should_replace_expiry() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Identity::add_registrar(RuntimeOrigin::signed(1), 3));
+		assert_ok!(Identity::set_judgement_period(RuntimeOrigin::signed(3), 0, Some(10)));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(10), Box::new(ten())));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(20), Box::new(twenty())));
+		assert_ok!(Identity::provide_judgement(
+			RuntimeOrigin::signed(3),
+			0,
+			10,
+			Judgement::Reasonable
+		));
+		assert_ok!(Identity::provide_judgement(
+			RuntimeOrigin::signed(3),
+			0,
+			20,
+			Judgement::Reasonable
+		));
+
+		// A new judgement restarts the period, `Erroneous` does not expire at all.
+		System::set_block_number(5);
+		assert_ok!(Identity::provide_judgement(
+			RuntimeOrigin::signed(3),
+			0,
+			10,
+			Judgement::KnownGood
+		));
+		assert_ok!(Identity::provide_judgement(
+			RuntimeOrigin::signed(3),
+			0,
+			20,
+			Judgement::Erroneous
+		));
+		assert_eq!(Identity::judgement_expiry(0, 10), Some(15));
+		assert_eq!(Identity::judgement_expiry(0, 20), None);
+
+		System::set_block_number(11);
+		Identity::on_idle(11, Weight::MAX);
+		assert_eq!(Identity::identity(10).unwrap().judgements, vec![(0, Judgement::KnownGood)]);
+		assert_eq!(Identity::identity(20).unwrap().judgements, vec![(0, Judgement::Erroneous)]);
+
+		// Without a judgement period judgements do not expire.
+		assert_ok!(Identity::set_judgement_period(RuntimeOrigin::signed(3), 0, None));
+		assert_ok!(Identity::provide_judgement(
+			RuntimeOrigin::signed(3),
+			0,
+			10,
+			Judgement::KnownGood
+		));
+		assert_eq!(Identity::judgement_expiry(0, 10), None);
+		System::set_block_number(15);
+		Identity::on_idle(15, Weight::MAX);
+		assert_eq!(Identity::identity(10).unwrap().judgements, vec![(0, Judgement::KnownGood)]);
+	});
+}
+
+#[test]
+fn migration_to_v3_should_keep_judgements_valid() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(7);
+		let old = vec![None, Some((3u64, 10u64, IdentityFields(IdentityField::Display.into())))];
+		frame_support::storage::unhashed::put(&Registrars::<Test>::hashed_key(), &old);
+
+		Identity::on_runtime_upgrade();
+		assert_eq!(
+			Identity::registrars(),
+			vec![
+				None,
+				Some(RegistrarInfo {
+					account: 3,
+					fee: 10,
+					fee_currency: NATIVE,
+					fields: IdentityFields(IdentityField::Display.into()),
+					judgement_period: None,
+				})
+			]
+		);
+		assert_eq!(ExpiryCursor::<Test>::get(), Some(7));
+		assert_eq!(PalletVersion::<Test>::get(), StorageVersion::V5MultiCurrency);
+	});
+}
+
+#[test]
+fn migration_from_upstream_should_record_pending_requests() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Identity::add_registrar(RuntimeOrigin::signed(1), 3));
+		assert_ok!(Identity::set_fee(RuntimeOrigin::signed(3), 0, 10));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(10), Box::new(ten())));
+		assert_ok!(Identity::set_subs(
+			RuntimeOrigin::signed(10),
+			vec![(20, Data::Raw(vec![40; 1].try_into().unwrap()))]
+		));
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(10), 0, 10));
+
+		// Upstream `pallet-identity` keeps neither the requests, the typed gaming fields nor the
+		// version.
+		put_v3_identity(10, Identity::identity(10).unwrap());
+		let old = vec![Some((3u64, 10u64, IdentityFields::default()))];
+		frame_support::storage::unhashed::put(&Registrars::<Test>::hashed_key(), &old);
+		let _ = PendingRequests::<Test>::clear(u32::MAX, None);
+		PalletVersion::<Test>::kill();
+
+		Identity::on_runtime_upgrade();
+		assert!(PendingRequests::<Test>::contains_key(0, 10));
+		assert_eq!(
+			Identity::identity(10).unwrap().judgements.into_inner(),
+			vec![(0, Judgement::FeePaid(10))]
+		);
+		assert_eq!(Identity::super_of(20), Some((10, Data::Raw(vec![40; 1].try_into().unwrap()))));
+		assert_eq!(Balances::reserved_balance(10), 30);
+	});
+}
+
+#[test]
+fn migration_to_v4_should_move_gaming_handles() {
+	new_test_ext().execute_with(|| {
+		let raw = |bytes: &[u8]| Data::Raw(bytes.to_vec().try_into().unwrap());
+		let info = IdentityInfo {
+			additional: vec![(raw(b"Discord"), raw(b"ten#0010")), (raw(b"guild"), raw(b"tenners"))]
+				.try_into()
+				.unwrap(),
+			..ten()
+		};
+		let judgements = vec![(0, Judgement::FeePaid(10))].try_into().unwrap();
+		put_v3_identity(10, Registration { judgements, deposit: 30, info });
+		let info = IdentityInfo {
+			additional: vec![
+				(raw(b"steam"), raw(b"76561197960287930")),
+				(raw(b"STEAM"), raw(b"20")),
+			]
+			.try_into()
+			.unwrap(),
+			..twenty()
+		};
+		put_v3_identity(20, Registration { judgements: Default::default(), deposit: 10, info });
+		PalletVersion::<Test>::put(StorageVersion::V3ExpiringJudgements);
+
+		migration::migrate_to_v4::<Test>();
+		let id = Identity::identity(10).unwrap();
+		assert_eq!((id.info.display, id.info.legal), (ten().display, ten().legal));
+		assert_eq!(id.info.discord, raw(b"ten#0010"));
+		assert_eq!(
+			(id.info.steam, id.info.epic, id.info.twitch),
+			(Data::None, Data::None, Data::None)
+		);
+		assert_eq!(id.info.additional.into_inner(), vec![(raw(b"guild"), raw(b"tenners"))]);
+		assert_eq!(id.judgements.into_inner(), vec![(0, Judgement::FeePaid(10))]);
+		assert_eq!(id.deposit, 30);
+
+		// A handle already moved into the typed field stays in the additional fields.
+		let id = Identity::identity(20).unwrap();
+		assert_eq!(id.info.steam, raw(b"76561197960287930"));
+		assert_eq!(id.info.additional.into_inner(), vec![(raw(b"STEAM"), raw(b"20"))]);
+		assert_eq!(id.deposit, 10);
+		assert_eq!(PalletVersion::<Test>::get(), StorageVersion::V4GamingFields);
+	});
+}
+
+#[test]
+fn poking_deposit_should_update_deposits() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			Identity::poke_deposit(RuntimeOrigin::signed(20), 10),
+			Error::<Test>::NoIdentity
+		);
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(10), Box::new(ten())));
+		assert_ok!(Identity::set_subs(
+			RuntimeOrigin::signed(10),
+			vec![(20, Data::Raw(vec![40; 1].try_into().unwrap()))]
+		));
+		assert_eq!(Balances::reserved_balance(10), 20);
+
+		// Deposits taken when `BasicDeposit` was higher and `SubAccountDeposit` lower.
+		assert_ok!(Balances::reserve(&10, 15));
+		IdentityOf::<Test>::mutate(&10, |id| id.as_mut().unwrap().deposit = 30);
+		SubsOf::<Test>::mutate(&10, |(deposit, _)| *deposit = 5);
+		assert_eq!(Balances::reserved_balance(10), 35);
+
+		let info = Identity::poke_deposit(RuntimeOrigin::signed(20), 10).unwrap();
+		assert_eq!(info.pays_fee, Pays::No);
+		assert_eq!(Balances::reserved_balance(10), 20);
+		assert_eq!(Identity::identity(10).unwrap().deposit, 10);
+		assert_eq!(Identity::subs_of(10).0, 10);
+		System::assert_last_event(
+			pallet_identity::Event::DepositUpdated { who: 10, identity: 10, subs: 10 }.into(),
+		);
+
+		// Nothing to update.
+		let info = Identity::poke_deposit(RuntimeOrigin::signed(20), 10).unwrap();
+		assert_eq!(info.pays_fee, Pays::Yes);
+		assert_eq!(Balances::reserved_balance(10), 20);
+
+		// An increase needs enough free balance.
+		IdentityOf::<Test>::mutate(&10, |id| id.as_mut().unwrap().deposit = 0);
+		assert_eq!(Balances::unreserve(&10, 10), 0);
+		Balances::make_free_balance_be(&10, 5);
+		assert_noop!(
+			Identity::poke_deposit(RuntimeOrigin::signed(20), 10),
+			pallet_balances::Error::<Test, _>::InsufficientBalance
+		);
+		Balances::make_free_balance_be(&10, 100);
+		assert_ok!(Identity::poke_deposit(RuntimeOrigin::signed(20), 10));
+		assert_eq!(Balances::reserved_balance(10), 20);
+	});
+}
+
+#[test]
+fn poking_deposits_should_work_in_batches() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(10), Box::new(ten())));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(20), Box::new(twenty())));
+		for who in [10, 20] {
+			assert_ok!(Balances::reserve(&who, 5));
+			IdentityOf::<Test>::mutate(&who, |id| id.as_mut().unwrap().deposit = 15);
+		}
+
+		// Keys too short for or not decoding to an account are skipped, the batch still ends on
+		// them.
+		let mut short = IdentityOf::<Test>::final_prefix().to_vec();
+		short.extend_from_slice(&[0; 3]);
+		frame_support::storage::unhashed::put_raw(&short, &[0]);
+		let mut broken = IdentityOf::<Test>::final_prefix().to_vec();
+		broken.extend_from_slice(&[0; 11]);
+		frame_support::storage::unhashed::put_raw(&broken, &[0]);
+		let (last, _) = migration::poke_deposits::<Test>(None, 1);
+		assert_eq!(last, Some(short));
+		let (last, _) = migration::poke_deposits::<Test>(last, 1);
+		assert_eq!(last, Some(broken));
+
+		let (first, _) = migration::poke_deposits::<Test>(last, 1);
+		let first = first.unwrap();
+		let (second, _) = migration::poke_deposits::<Test>(Some(first.clone()), 1);
+		let second = second.unwrap();
+		assert_ne!(first, second);
+		assert_eq!(migration::poke_deposits::<Test>(Some(second), 1).0, None);
+
+		for who in [10, 20] {
+			assert_eq!(Identity::identity(who).unwrap().deposit, 10);
+			assert_eq!(Balances::reserved_balance(who), 10);
+		}
+	});
+}
+
+#[test]
+fn fees_should_be_paid_in_the_registrar_currency() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Identity::add_registrar(RuntimeOrigin::signed(1), 3));
+		assert_ok!(Identity::set_fee(RuntimeOrigin::signed(3), 0, 10));
+		assert_noop!(
+			Identity::set_fee_currency(RuntimeOrigin::signed(4), 0, PLAY),
+			Error::<Test>::InvalidIndex
+		);
+		assert_ok!(Identity::set_fee_currency(RuntimeOrigin::signed(3), 0, PLAY));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(10), Box::new(ten())));
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(10), 0, 10));
+		// The deposit is native, the fee in PLAY.
+		assert_eq!(Balances::reserved_balance(10), 10);
+		assert_eq!(Tokens::reserved_balance(PLAY, &10), 10);
+
+		// The currency is fixed while requests are pending.
+		assert_noop!(
+			Identity::set_fee_currency(RuntimeOrigin::signed(3), 0, NATIVE),
+			Error::<Test>::RequestsPending
+		);
+		assert_ok!(Identity::cancel_request(RuntimeOrigin::signed(10), 0));
+		assert_eq!(Tokens::free_balance(PLAY, &10), 100);
+
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(10), 0, 10));
+		assert_ok!(Identity::provide_judgement(
+			RuntimeOrigin::signed(3),
+			0,
+			10,
+			Judgement::Reasonable
+		));
+		assert_eq!(Tokens::free_balance(PLAY, &3), 10);
+		assert_eq!(Tokens::total_balance(PLAY, &10), 90);
+		assert_eq!(Balances::free_balance(3), 10);
+
+		// Clearing returns the fees of pending requests in their currency.
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(10), 0, 10));
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(10)));
+		assert_eq!(Tokens::free_balance(PLAY, &10), 90);
+		assert_eq!(Balances::free_balance(10), 100);
+	});
+}
+
+#[test]
+fn deposits_should_be_reserved_in_the_deposit_currency() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let data = |x| Data::Raw(vec![x; 1].try_into().unwrap());
+		assert_noop!(
+			Identity::set_deposit_currency(RuntimeOrigin::signed(10), PLAY),
+			Error::<Test>::UnsupportedCurrency
+		);
+		assert_noop!(Identity::approve_deposit_currency(RuntimeOrigin::signed(2), PLAY), BadOrigin);
+		assert_ok!(Identity::approve_deposit_currency(RuntimeOrigin::signed(1), PLAY));
+		System::assert_last_event(
+			pallet_identity::Event::DepositCurrencyApproved { currency: PLAY }.into(),
+		);
+
+		// Existing deposits move to the new currency at its rate.
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(10), Box::new(ten())));
+		assert_ok!(Identity::add_sub(RuntimeOrigin::signed(10), 1, data(1)));
+		assert_eq!(Balances::reserved_balance(10), 20);
+		assert_ok!(Identity::set_deposit_currency(RuntimeOrigin::signed(10), PLAY));
+		System::assert_last_event(
+			pallet_identity::Event::DepositCurrencySet { who: 10, currency: PLAY }.into(),
+		);
+		assert_eq!(Balances::reserved_balance(10), 0);
+		assert_eq!(Tokens::reserved_balance(PLAY, &10), 40);
+		assert_eq!(Identity::identity(10).unwrap().deposit, 20);
+		assert_eq!(Identity::subs_of(10).0, 20);
+
+		// New deposits are taken in it as well.
+		assert_ok!(Identity::add_sub(RuntimeOrigin::signed(10), 2, data(2)));
+		assert_eq!(Tokens::reserved_balance(PLAY, &10), 60);
+		assert_ok!(Identity::remove_sub(RuntimeOrigin::signed(10), 1));
+		assert_eq!(Tokens::reserved_balance(PLAY, &10), 40);
+		assert_ok!(Identity::quit_sub(RuntimeOrigin::signed(2)));
+		assert_eq!(Tokens::free_balance(PLAY, &2), 20);
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(10)));
+		assert_eq!(Tokens::free_balance(PLAY, &10), 80);
+		assert_eq!(Balances::free_balance(10), 100);
+
+		// Revoked currencies cannot be selected anymore, but their holders keep using them.
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(10), Box::new(ten())));
+		assert_ok!(Identity::revoke_deposit_currency(RuntimeOrigin::signed(1), PLAY));
+		assert_eq!(Tokens::reserved_balance(PLAY, &10), 20);
+		assert_noop!(
+			Identity::set_deposit_currency(RuntimeOrigin::signed(20), PLAY),
+			Error::<Test>::UnsupportedCurrency
+		);
+		assert_noop!(
+			Identity::revoke_deposit_currency(RuntimeOrigin::signed(1), PLAY),
+			Error::<Test>::NotFound
+		);
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(10), Box::new(twenty())));
+		assert_ok!(Identity::add_sub(RuntimeOrigin::signed(10), 1, data(1)));
+		assert_eq!(Tokens::reserved_balance(PLAY, &10), 40);
+		assert_ok!(Identity::remove_sub(RuntimeOrigin::signed(10), 1));
+		assert_ok!(Identity::set_subs(RuntimeOrigin::signed(10), vec![(1, data(1))]));
+		assert_eq!(Tokens::reserved_balance(PLAY, &10), 40);
+		assert_ok!(Identity::poke_deposit(RuntimeOrigin::signed(20), 10));
+
+		// Killing moves the deposit to the treasury.
+		assert_ok!(Identity::kill_identity(RuntimeOrigin::signed(2), 10));
+		assert_eq!(Tokens::free_balance(PLAY, &TREASURY), 40);
+		assert_eq!(Tokens::total_balance(PLAY, &10), 40);
+
+		// Back to the native currency.
+		assert_ok!(Identity::set_deposit_currency(RuntimeOrigin::signed(10), NATIVE));
+		assert!(DepositCurrencyOf::<Test>::get(10).is_none());
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(10), Box::new(ten())));
+		assert_eq!(Balances::reserved_balance(10), 10);
+	});
+}
+
+#[test]
+fn migration_to_v5_should_add_fee_currency() {
+	new_test_ext().execute_with(|| {
+		let old = vec![Some((3u64, 10u64, IdentityFields::default(), Some(5u64)))];
+		frame_support::storage::unhashed::put(&Registrars::<Test>::hashed_key(), &old);
+		PalletVersion::<Test>::put(StorageVersion::V4GamingFields);
+
+		migration::migrate_to_v5::<Test>();
+		assert_eq!(
+			Identity::registrars(),
+			vec![Some(RegistrarInfo {
+				account: 3,
+				fee: 10,
+				fee_currency: NATIVE,
+				fields: Default::default(),
+				judgement_period: Some(5),
+			})]
+		);
+		assert_eq!(PalletVersion::<Test>::get(), StorageVersion::V5MultiCurrency);
+	});
+}
+
+fn username(name: &str) -> Username<Test> {
+	name.as_bytes().to_vec().try_into().unwrap()
+}
+
+fn suffix(name: &str) -> Suffix<Test> {
+	name.as_bytes().to_vec().try_into().unwrap()
+}
+
+#[test]
+fn usernames_should_resolve_both_ways() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Identity::set_username(RuntimeOrigin::signed(10), username("Alice")));
+		assert_eq!(Balances::reserved_balance(10), 5);
+		assert_eq!(Identity::account_of_username(b"ALICE"), Some(10));
+		assert_eq!(Identity::username_of(&10), Some(b"alice".to_vec()));
+
+		assert_noop!(
+			Identity::set_username(RuntimeOrigin::signed(20), username("alice")),
+			Error::<Test>::UsernameTaken
+		);
+		assert_noop!(
+			Identity::set_username(RuntimeOrigin::signed(20), username("al ice")),
+			Error::<Test>::InvalidUsername
+		);
+		assert_noop!(
+			Identity::set_username(RuntimeOrigin::signed(20), username("bob.gamedao")),
+			Error::<Test>::InvalidUsername
+		);
+		assert_noop!(
+			Identity::set_username(RuntimeOrigin::signed(20), username("")),
+			Error::<Test>::InvalidUsername
+		);
+
+		// The first username stays the primary one until another is chosen.
+		assert_ok!(Identity::set_username(RuntimeOrigin::signed(10), username("alice2")));
+		assert_eq!(Identity::username_of(&10), Some(b"alice".to_vec()));
+		assert_noop!(
+			Identity::set_primary_username(RuntimeOrigin::signed(20), username("alice2")),
+			Error::<Test>::NotUsernameOwner
+		);
+		assert_ok!(Identity::set_primary_username(RuntimeOrigin::signed(10), username("alice2")));
+		assert_eq!(Identity::username_of(&10), Some(b"alice2".to_vec()));
+
+		assert_ok!(Identity::release_username(RuntimeOrigin::signed(10), username("alice2")));
+		assert_eq!(Identity::account_of_username(b"alice2"), None);
+		assert_eq!(Identity::username_of(&10), None);
+		assert_eq!(Balances::reserved_balance(10), 5);
+	});
+}
+
+#[test]
+fn username_authorities_should_grant_suffixed_usernames() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Identity::add_username_authority(RuntimeOrigin::signed(3), suffix("gamedao"), 3, 1),
+			BadOrigin
+		);
+		assert_noop!(
+			Identity::add_username_authority(RuntimeOrigin::signed(1), suffix("game.dao"), 3, 1),
+			Error::<Test>::InvalidSuffix
+		);
+		assert_ok!(Identity::add_username_authority(
+			RuntimeOrigin::signed(1),
+			suffix("GameDAO"),
+			3,
+			1
+		));
+
+		assert_noop!(
+			Identity::grant_username(
+				RuntimeOrigin::signed(10),
+				suffix("gamedao"),
+				20,
+				username("bob")
+			),
+			Error::<Test>::NotUsernameAuthority
+		);
+		assert_noop!(
+			Identity::grant_username(
+				RuntimeOrigin::signed(3),
+				suffix("game.dao"),
+				20,
+				username("bob")
+			),
+			Error::<Test>::InvalidSuffix
+		);
+		assert_ok!(Identity::grant_username(
+			RuntimeOrigin::signed(3),
+			suffix("GameDAO"),
+			20,
+			username("Bob")
+		));
+		assert_eq!(Identity::account_of_username(b"bob.gamedao"), Some(20));
+		assert_eq!(Identity::username_of(&20), Some(b"bob.gamedao".to_vec()));
+		// The authority pays the deposit.
+		assert_eq!(Balances::reserved_balance(3), 5);
+		assert_eq!(Balances::reserved_balance(20), 0);
+
+		assert_noop!(
+			Identity::grant_username(
+				RuntimeOrigin::signed(3),
+				suffix("gamedao"),
+				30,
+				username("carl")
+			),
+			Error::<Test>::NoAllocation
+		);
+
+		// Granted usernames stay when the authority is removed.
+		assert_ok!(Identity::remove_username_authority(
+			RuntimeOrigin::signed(1),
+			suffix("gamedao")
+		));
+		assert_eq!(Identity::account_of_username(b"bob.gamedao"), Some(20));
+		assert_ok!(Identity::release_username(RuntimeOrigin::signed(20), username("bob.gamedao")));
+		assert_eq!(Balances::reserved_balance(3), 0);
+	});
+}
+
+#[test]
+fn expired_usernames_should_be_released() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Identity::set_username(RuntimeOrigin::signed(10), username("alice")));
+		assert_noop!(
+			Identity::remove_expired_username(RuntimeOrigin::signed(20), username("alice")),
+			Error::<Test>::UsernameNotExpired
+		);
+
+		System::set_block_number(101);
+		assert_eq!(Identity::account_of_username(b"alice"), Some(10));
+		System::set_block_number(102);
+		assert_eq!(Identity::account_of_username(b"alice"), None);
+		assert_eq!(Identity::username_of(&10), None);
+
+		// The owner may renew it as long as nobody released it.
+		assert_ok!(Identity::renew_username(RuntimeOrigin::signed(10), username("alice")));
+		assert_eq!(Identity::account_of_username(b"alice"), Some(10));
+
+		// Once expired again, another account takes it over and the deposit is returned.
+		System::set_block_number(203);
+		assert_ok!(Identity::set_username(RuntimeOrigin::signed(20), username("alice")));
+		assert_eq!(Identity::account_of_username(b"alice"), Some(20));
+		assert_eq!(Balances::reserved_balance(10), 0);
+		System::assert_has_event(
+			pallet_identity::Event::UsernameReleased { who: 10, username: username("alice") }
+				.into(),
+		);
+	});
+}
+
+#[test]
+fn username_calls_should_ignore_case() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Identity::set_username(RuntimeOrigin::signed(10), username("alice")));
+		assert_ok!(Identity::set_username(RuntimeOrigin::signed(10), username("alice2")));
+		assert_ok!(Identity::set_primary_username(RuntimeOrigin::signed(10), username("ALICE2")));
+		assert_eq!(Identity::username_of(&10), Some(b"alice2".to_vec()));
+		assert_ok!(Identity::release_username(RuntimeOrigin::signed(10), username("Alice2")));
+		assert_eq!(Identity::account_of_username(b"alice2"), None);
+
+		System::set_block_number(102);
+		assert_ok!(Identity::renew_username(RuntimeOrigin::signed(10), username("AlIcE")));
+		assert_eq!(Identity::account_of_username(b"alice"), Some(10));
+		System::set_block_number(203);
+		assert_ok!(Identity::remove_expired_username(RuntimeOrigin::signed(20), username("ALICE")));
+		assert_eq!(Identity::account_of_username(b"alice"), None);
+		assert_eq!(Balances::reserved_balance(10), 0);
+
+		assert_ok!(Identity::add_username_authority(
+			RuntimeOrigin::signed(1),
+			suffix("gamedao"),
+			3,
+			1
+		));
+		assert_ok!(Identity::grant_username(
+			RuntimeOrigin::signed(3),
+			suffix("gamedao"),
+			20,
+			username("bob")
+		));
+		assert_ok!(Identity::renew_username(RuntimeOrigin::signed(20), username("Bob.GameDAO")));
+		assert_ok!(Identity::remove_username_authority(
+			RuntimeOrigin::signed(1),
+			suffix("GameDAO")
+		));
+		assert_noop!(
+			Identity::grant_username(
+				RuntimeOrigin::signed(3),
+				suffix("gamedao"),
+				30,
+				username("carl")
+			),
+			Error::<Test>::NotUsernameAuthority
+		);
+		assert_noop!(
+			Identity::release_username(RuntimeOrigin::signed(20), username("bob.game.dao")),
+			Error::<Test>::InvalidUsername
+		);
 	});
 }
@@ -29,10 +29,15 @@ use scale_info::{
 use sp_runtime::{traits::Zero, RuntimeDebug};
 use sp_std::{fmt::Debug, iter::once, ops::Add, prelude::*};
 
-#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, TypeInfo, MaxEncodedLen)]
+#[derive(
+    Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, PartialOrd, Ord, TypeInfo, MaxEncodedLen,
+)]
 pub enum StorageVersion {
     V1Initial,
     V2Imported,
+    V3ExpiringJudgements,
+    V4GamingFields,
+    V5MultiCurrency,
 }
 
 /// Either underlying data blob if it is at most 32 bytes, or a hash of it. If the data is greater
@@ -233,6 +238,15 @@ impl<Balance: Encode + Decode + MaxEncodedLen + Copy + Clone + Debug + Eq + Part
 			_ => false,
 		}
 	}
+
+	/// Returns `true` if this judgement becomes `OutOfDate` once the judgement period of its
+	/// registrar has passed.
+	pub(crate) fn expires(&self) -> bool {
+		match self {
+			Judgement::Reasonable | Judgement::KnownGood | Judgement::LowQuality => true,
+			_ => false,
+		}
+	}
 }
 
 /// The fields that we use to identify the owner of an account with. Each corresponds to a field
@@ -248,6 +262,10 @@ pub enum IdentityField {
 	PgpFingerprint = 0b0000000000000000000000000000000000000000000000000000000000100000,
 	Image = 0b0000000000000000000000000000000000000000000000000000000001000000,
 	Twitter = 0b0000000000000000000000000000000000000000000000000000000010000000,
+	Discord = 0b0000000000000000000000000000000000000000000000000000000100000000,
+	Steam = 0b0000000000000000000000000000000000000000000000000000001000000000,
+	Epic = 0b0000000000000000000000000000000000000000000000000000010000000000,
+	Twitch = 0b0000000000000000000000000000000000000000000000000000100000000000,
 }
 
 /// Wrapper type for `BitFlags<IdentityField>` that implements `Codec`.
@@ -337,6 +355,67 @@ pub struct IdentityInfo<FieldLimit: Get<u32>> {
 
 	/// The Twitter identity. The leading `@` character may be elided.
 	pub twitter: Data,
+
+	/// The Discord username, with the `#` discriminator if it has one.
+	///
+	/// Stored as UTF-8.
+	pub discord: Data,
+
+	/// The Steam account, either the SteamID64 or the custom URL name.
+	///
+	/// Stored as UTF-8.
+	pub steam: Data,
+
+	/// The Epic Games display name.
+	///
+	/// Stored as UTF-8.
+	pub epic: Data,
+
+	/// The Twitch username.
+	///
+	/// Stored as UTF-8.
+	pub twitch: Data,
+}
+
+impl<FieldLimit: Get<u32>> IdentityInfo<FieldLimit> {
+	/// Whether `field` is set.
+	pub fn has_field(&self, field: IdentityField) -> bool {
+		match field {
+			IdentityField::Display => self.display != Data::None,
+			IdentityField::Legal => self.legal != Data::None,
+			IdentityField::Web => self.web != Data::None,
+			IdentityField::Riot => self.riot != Data::None,
+			IdentityField::Email => self.email != Data::None,
+			IdentityField::PgpFingerprint => self.pgp_fingerprint.is_some(),
+			IdentityField::Image => self.image != Data::None,
+			IdentityField::Twitter => self.twitter != Data::None,
+			IdentityField::Discord => self.discord != Data::None,
+			IdentityField::Steam => self.steam != Data::None,
+			IdentityField::Epic => self.epic != Data::None,
+			IdentityField::Twitch => self.twitch != Data::None,
+		}
+	}
+
+	/// Whether every field of `fields` is set.
+	pub fn has_fields(&self, fields: IdentityFields) -> bool {
+		fields.0.iter().all(|field| self.has_field(field))
+	}
+
+	/// The typed field for the gaming platform `key` of an additional field, matched
+	/// case-insensitively.
+	pub(crate) fn platform_field(&mut self, key: &Data) -> Option<&mut Data> {
+		let key = match key {
+			Data::Raw(key) => key.to_ascii_lowercase(),
+			_ => return None,
+		};
+		match &key[..] {
+			b"discord" => Some(&mut self.discord),
+			b"steam" => Some(&mut self.steam),
+			b"epic" => Some(&mut self.epic),
+			b"twitch" => Some(&mut self.twitch),
+			_ => None,
+		}
+	}
 }
 
 /// Information concerning the identity of the controller of an account.
@@ -369,17 +448,17 @@ pub struct Registration<
 }
 
 impl<
-		Balance: Encode + Decode + MaxEncodedLen + Copy + Clone + Debug + Eq + PartialEq + Zero + Add,
+		Balance: Encode + Decode + MaxEncodedLen + Copy + Clone + Debug + Eq + PartialEq,
 		MaxJudgements: Get<u32>,
 		MaxAdditionalFields: Get<u32>,
 	> Registration<Balance, MaxJudgements, MaxAdditionalFields>
 {
-	pub(crate) fn total_deposit(&self) -> Balance {
-		self.deposit +
-			self.judgements
-				.iter()
-				.map(|(_, ref j)| if let Judgement::FeePaid(fee) = j { *fee } else { Zero::zero() })
-				.fold(Zero::zero(), |a, i| a + i)
+	/// The fees reserved for pending requests, by the index of their registrar.
+	pub(crate) fn fees(&self) -> impl Iterator<Item = (RegistrarIndex, Balance)> + '_ {
+		self.judgements.iter().filter_map(|(index, judgement)| match judgement {
+			Judgement::FeePaid(fee) => Some((*index, *fee)),
+			_ => None,
+		})
 	}
 }
 
@@ -400,6 +479,8 @@ impl<
 pub struct RegistrarInfo<
 	Balance: Encode + Decode + Clone + Debug + Eq + PartialEq,
 	AccountId: Encode + Decode + Clone + Debug + Eq + PartialEq,
+	BlockNumber: Encode + Decode + Clone + Debug + Eq + PartialEq,
+	CurrencyId: Encode + Decode + Clone + Debug + Eq + PartialEq,
 > {
 	/// The account of the registrar.
 	pub account: AccountId,
@@ -407,9 +488,46 @@ pub struct RegistrarInfo<
 	/// Amount required to be given to the registrar for them to provide judgement.
 	pub fee: Balance,
 
+	/// The currency `fee` is paid in.
+	pub fee_currency: CurrencyId,
+
 	/// Relevant fields for this registrar. Registrar judgements are limited to attestations on
 	/// these fields.
 	pub fields: IdentityFields,
+
+	/// Number of blocks after which the judgements of this registrar become `OutOfDate`, `None`
+	/// if they do not expire.
+	pub judgement_period: Option<BlockNumber>,
+}
+
+/// A username held by an account.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct UsernameInfo<
+	AccountId: Encode + Decode + Clone + Debug + Eq + PartialEq,
+	Balance: Encode + Decode + Clone + Debug + Eq + PartialEq,
+	BlockNumber: Encode + Decode + Clone + Debug + Eq + PartialEq,
+> {
+	/// The account the username resolves to.
+	pub owner: AccountId,
+
+	/// The account which reserved the deposit, the owner or the authority granting the username.
+	pub depositor: AccountId,
+
+	/// Amount held on deposit by `depositor`.
+	pub deposit: Balance,
+
+	/// Last block in which the username resolves. After it, anyone may release it.
+	pub expiry: BlockNumber,
+}
+
+/// A username authority, granting usernames with its suffix.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct AuthorityProperties<AccountId: Encode + Decode + Clone + Debug + Eq + PartialEq> {
+	/// The account of the authority.
+	pub account: AccountId,
+
+	/// Number of usernames the authority may still grant.
+	pub allocation: u32,
 }
 
 #[cfg(test)]
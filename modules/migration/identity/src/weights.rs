@@ -45,6 +45,7 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for pallet_identity.
 pub trait WeightInfo {
 	fn add_registrar(r: u32, ) -> Weight;
+	fn remove_registrar(r: u32, p: u32, ) -> Weight;
 	fn set_identity(r: u32, x: u32, ) -> Weight;
 	fn set_subs_new(s: u32, ) -> Weight;
 	fn set_subs_old(p: u32, ) -> Weight;
@@ -60,6 +61,21 @@ pub trait WeightInfo {
 	fn rename_sub(s: u32, ) -> Weight;
 	fn remove_sub(s: u32, ) -> Weight;
 	fn quit_sub(s: u32, ) -> Weight;
+	fn add_username_authority() -> Weight;
+	fn remove_username_authority() -> Weight;
+	fn set_username() -> Weight;
+	fn grant_username() -> Weight;
+	fn renew_username() -> Weight;
+	fn set_primary_username() -> Weight;
+	fn release_username() -> Weight;
+	fn set_judgement_period(r: u32, ) -> Weight;
+	fn expire_judgement(r: u32, x: u32, ) -> Weight;
+	fn poke_deposit(r: u32, s: u32, x: u32, ) -> Weight;
+	fn set_fee_currency(r: u32, ) -> Weight;
+	fn approve_deposit_currency() -> Weight;
+	fn revoke_deposit_currency() -> Weight;
+	fn set_deposit_currency(r: u32, s: u32, x: u32, ) -> Weight;
+	fn provide_judgement_signed(r: u32, x: u32, ) -> Weight;
 }
 
 /// Weights for pallet_identity using the Substrate node and recommended hardware.
@@ -67,167 +83,333 @@ pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	// Storage: Identity Registrars (r:1 w:1)
 	fn add_registrar(r: u32, ) -> Weight {
-		(19_176_000 as Weight)
+		Weight::from_parts(19_176_000, 0)
 			// Standard Error: 5_000
-			.saturating_add((313_000 as Weight).saturating_mul(r as Weight))
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(Weight::from_parts(313_000, 0).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: Identity PendingRequests (r:1 w:1)
+	// Storage: Identity Registrars (r:1 w:1)
+	// Storage: Identity IdentityOf (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Estimated, not benchmarked yet.
+	fn remove_registrar(r: u32, p: u32, ) -> Weight {
+		Weight::from_parts(21_503_000, 0)
+			.saturating_add(Weight::from_parts(297_000, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(24_620_000, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(p.into())))
 	}
 	// Storage: Identity IdentityOf (r:1 w:1)
 	fn set_identity(r: u32, x: u32, ) -> Weight {
-		(44_668_000 as Weight)
+		Weight::from_parts(44_668_000, 0)
 			// Standard Error: 12_000
-			.saturating_add((244_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(Weight::from_parts(244_000, 0).saturating_mul(r.into()))
 			// Standard Error: 1_000
-			.saturating_add((811_000 as Weight).saturating_mul(x as Weight))
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(Weight::from_parts(811_000, 0).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
 	// Storage: Identity IdentityOf (r:1 w:0)
 	// Storage: Identity SubsOf (r:1 w:1)
 	// Storage: Identity SuperOf (r:1 w:1)
 	fn set_subs_new(s: u32, ) -> Weight {
-		(38_917_000 as Weight)
+		Weight::from_parts(38_917_000, 0)
 			// Standard Error: 3_000
-			.saturating_add((5_331_000 as Weight).saturating_mul(s as Weight))
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(Weight::from_parts(5_331_000, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(s.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(s.into())))
 	}
 	// Storage: Identity IdentityOf (r:1 w:0)
 	// Storage: Identity SubsOf (r:1 w:1)
 	// Storage: Identity SuperOf (r:0 w:1)
 	fn set_subs_old(p: u32, ) -> Weight {
-		(36_057_000 as Weight)
+		Weight::from_parts(36_057_000, 0)
 			// Standard Error: 1_000
-			.saturating_add((1_756_000 as Weight).saturating_mul(p as Weight))
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(Weight::from_parts(1_756_000, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(p.into())))
 	}
 	// Storage: Identity SubsOf (r:1 w:1)
 	// Storage: Identity IdentityOf (r:1 w:1)
+	// Storage: Identity Registrars (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Tokens Accounts (r:20 w:20)
 	// Storage: Identity SuperOf (r:0 w:100)
+	// Storage: Identity PendingRequests (r:0 w:20)
+	// Storage: Identity JudgementExpiries (r:0 w:20)
+	// Estimated, not benchmarked yet.
 	fn clear_identity(r: u32, s: u32, x: u32, ) -> Weight {
-		(44_348_000 as Weight)
-			// Standard Error: 9_000
-			.saturating_add((183_000 as Weight).saturating_mul(r as Weight))
-			// Standard Error: 1_000
-			.saturating_add((1_724_000 as Weight).saturating_mul(s as Weight))
-			// Standard Error: 1_000
-			.saturating_add((439_000 as Weight).saturating_mul(x as Weight))
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(s as Weight)))
+		Weight::from_parts(44_348_000, 0)
+			.saturating_add(Weight::from_parts(183_000, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(1_724_000, 0).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(439_000, 0).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(s.into())))
 	}
 	// Storage: Identity Registrars (r:1 w:0)
 	// Storage: Identity IdentityOf (r:1 w:1)
+	// Storage: Tokens Accounts (r:1 w:1)
+	// Storage: Identity PendingRequests (r:0 w:1)
+	// Estimated, not benchmarked yet.
 	fn request_judgement(r: u32, x: u32, ) -> Weight {
-		(46_592_000 as Weight)
-			// Standard Error: 5_000
-			.saturating_add((321_000 as Weight).saturating_mul(r as Weight))
-			// Standard Error: 0
-			.saturating_add((858_000 as Weight).saturating_mul(x as Weight))
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+		Weight::from_parts(46_592_000, 0)
+			.saturating_add(Weight::from_parts(321_000, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(858_000, 0).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
 	}
 	// Storage: Identity IdentityOf (r:1 w:1)
+	// Storage: Identity Registrars (r:1 w:0)
+	// Storage: Tokens Accounts (r:1 w:1)
+	// Storage: Identity PendingRequests (r:0 w:1)
+	// Estimated, not benchmarked yet.
 	fn cancel_request(r: u32, x: u32, ) -> Weight {
-		(43_556_000 as Weight)
-			// Standard Error: 6_000
-			.saturating_add((174_000 as Weight).saturating_mul(r as Weight))
-			// Standard Error: 0
-			.saturating_add((850_000 as Weight).saturating_mul(x as Weight))
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+		Weight::from_parts(43_556_000, 0)
+			.saturating_add(Weight::from_parts(174_000, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(850_000, 0).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
 	}
 	// Storage: Identity Registrars (r:1 w:1)
 	fn set_fee(r: u32, ) -> Weight {
-		(7_971_000 as Weight)
+		Weight::from_parts(7_971_000, 0)
 			// Standard Error: 4_000
-			.saturating_add((283_000 as Weight).saturating_mul(r as Weight))
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(Weight::from_parts(283_000, 0).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
 	// Storage: Identity Registrars (r:1 w:1)
 	fn set_account_id(r: u32, ) -> Weight {
-		(8_234_000 as Weight)
+		Weight::from_parts(8_234_000, 0)
 			// Standard Error: 4_000
-			.saturating_add((280_000 as Weight).saturating_mul(r as Weight))
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(Weight::from_parts(280_000, 0).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
 	// Storage: Identity Registrars (r:1 w:1)
 	fn set_fields(r: u32, ) -> Weight {
-		(8_126_000 as Weight)
+		Weight::from_parts(8_126_000, 0)
 			// Standard Error: 4_000
-			.saturating_add((275_000 as Weight).saturating_mul(r as Weight))
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(Weight::from_parts(275_000, 0).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
 	// Storage: Identity Registrars (r:1 w:0)
 	// Storage: Identity IdentityOf (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: Identity PendingRequests (r:0 w:1)
+	// Storage: Identity JudgementNonces (r:1 w:1)
+	// Storage: Identity JudgementExpiries (r:0 w:1)
+	// Storage: Identity ExpiringJudgements (r:0 w:1)
+	// Estimated, not benchmarked yet.
 	fn provide_judgement(r: u32, x: u32, ) -> Weight {
-		(30_949_000 as Weight)
-			// Standard Error: 5_000
-			.saturating_add((286_000 as Weight).saturating_mul(r as Weight))
-			// Standard Error: 0
-			.saturating_add((856_000 as Weight).saturating_mul(x as Weight))
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+		Weight::from_parts(30_949_000, 0)
+			.saturating_add(Weight::from_parts(286_000, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(856_000, 0).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
 	}
 	// Storage: Identity SubsOf (r:1 w:1)
 	// Storage: Identity IdentityOf (r:1 w:1)
-	// Storage: System Account (r:1 w:1)
+	// Storage: Identity Registrars (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Tokens Accounts (r:40 w:40)
 	// Storage: Identity SuperOf (r:0 w:100)
+	// Storage: Identity PendingRequests (r:0 w:20)
+	// Storage: Identity JudgementExpiries (r:0 w:20)
+	// Estimated, not benchmarked yet.
 	fn kill_identity(r: u32, s: u32, _x: u32, ) -> Weight {
-		(63_792_000 as Weight)
-			// Standard Error: 11_000
-			.saturating_add((242_000 as Weight).saturating_mul(r as Weight))
-			// Standard Error: 1_000
-			.saturating_add((1_738_000 as Weight).saturating_mul(s as Weight))
-			.saturating_add(T::DbWeight::get().reads(3 as Weight))
-			.saturating_add(T::DbWeight::get().writes(3 as Weight))
-			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(s as Weight)))
+		Weight::from_parts(63_792_000, 0)
+			.saturating_add(Weight::from_parts(242_000, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(1_738_000, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(s.into())))
 	}
 	// Storage: Identity IdentityOf (r:1 w:0)
 	// Storage: Identity SuperOf (r:1 w:1)
 	// Storage: Identity SubsOf (r:1 w:1)
 	fn add_sub(s: u32, ) -> Weight {
-		(48_751_000 as Weight)
+		Weight::from_parts(48_751_000, 0)
 			// Standard Error: 1_000
-			.saturating_add((193_000 as Weight).saturating_mul(s as Weight))
-			.saturating_add(T::DbWeight::get().reads(3 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(Weight::from_parts(193_000, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
 	// Storage: Identity IdentityOf (r:1 w:0)
 	// Storage: Identity SuperOf (r:1 w:1)
 	fn rename_sub(s: u32, ) -> Weight {
-		(15_892_000 as Weight)
+		Weight::from_parts(15_892_000, 0)
 			// Standard Error: 0
-			.saturating_add((49_000 as Weight).saturating_mul(s as Weight))
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(Weight::from_parts(49_000, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
 	// Storage: Identity IdentityOf (r:1 w:0)
 	// Storage: Identity SuperOf (r:1 w:1)
 	// Storage: Identity SubsOf (r:1 w:1)
 	fn remove_sub(s: u32, ) -> Weight {
-		(49_746_000 as Weight)
+		Weight::from_parts(49_746_000, 0)
 			// Standard Error: 1_000
-			.saturating_add((181_000 as Weight).saturating_mul(s as Weight))
-			.saturating_add(T::DbWeight::get().reads(3 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(Weight::from_parts(181_000, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
 	// Storage: Identity SuperOf (r:1 w:1)
 	// Storage: Identity SubsOf (r:1 w:1)
 	fn quit_sub(s: u32, ) -> Weight {
-		(32_286_000 as Weight)
+		Weight::from_parts(32_286_000, 0)
 			// Standard Error: 1_000
-			.saturating_add((166_000 as Weight).saturating_mul(s as Weight))
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(Weight::from_parts(166_000, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	// Storage: Identity UsernameAuthorities (r:0 w:1)
+	// Estimated, not benchmarked yet.
+	fn add_username_authority() -> Weight {
+		Weight::from_parts(13_240_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: Identity UsernameAuthorities (r:1 w:1)
+	// Estimated, not benchmarked yet.
+	fn remove_username_authority() -> Weight {
+		Weight::from_parts(15_518_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: Identity AccountOfUsername (r:2 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Identity PrimaryUsernameOf (r:1 w:1)
+	// Estimated, not benchmarked yet.
+	fn set_username() -> Weight {
+		Weight::from_parts(42_107_000, 0)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	// Storage: Identity UsernameAuthorities (r:1 w:1)
+	// Storage: Identity AccountOfUsername (r:2 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Identity PrimaryUsernameOf (r:1 w:1)
+	// Estimated, not benchmarked yet.
+	fn grant_username() -> Weight {
+		Weight::from_parts(47_832_000, 0)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	// Storage: Identity AccountOfUsername (r:1 w:1)
+	// Estimated, not benchmarked yet.
+	fn renew_username() -> Weight {
+		Weight::from_parts(18_905_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: Identity AccountOfUsername (r:1 w:0)
+	// Storage: Identity PrimaryUsernameOf (r:0 w:1)
+	// Estimated, not benchmarked yet.
+	fn set_primary_username() -> Weight {
+		Weight::from_parts(17_361_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: Identity AccountOfUsername (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Identity PrimaryUsernameOf (r:1 w:1)
+	// Estimated, not benchmarked yet.
+	fn release_username() -> Weight {
+		Weight::from_parts(35_264_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	// Storage: Identity Registrars (r:1 w:1)
+	// Estimated, not benchmarked yet.
+	fn set_judgement_period(r: u32, ) -> Weight {
+		Weight::from_parts(8_102_000, 0)
+			.saturating_add(Weight::from_parts(279_000, 0).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: Identity ExpiringJudgements (r:1 w:1)
+	// Storage: Identity JudgementExpiries (r:1 w:1)
+	// Storage: Identity IdentityOf (r:1 w:1)
+	// Estimated, not benchmarked yet.
+	fn expire_judgement(r: u32, x: u32, ) -> Weight {
+		Weight::from_parts(27_416_000, 0)
+			.saturating_add(Weight::from_parts(262_000, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(851_000, 0).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	// Storage: Identity IdentityOf (r:1 w:1)
+	// Storage: Identity SubsOf (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Estimated, not benchmarked yet.
+	fn poke_deposit(r: u32, s: u32, x: u32, ) -> Weight {
+		Weight::from_parts(38_204_000, 0)
+			.saturating_add(Weight::from_parts(231_000, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(94_000, 0).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(853_000, 0).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	// Storage: Identity Registrars (r:1 w:1)
+	// Storage: Identity PendingRequests (r:1 w:0)
+	// Estimated, not benchmarked yet.
+	fn set_fee_currency(r: u32, ) -> Weight {
+		Weight::from_parts(9_873_000, 0)
+			.saturating_add(Weight::from_parts(285_000, 0).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: Identity DepositCurrencies (r:0 w:1)
+	// Estimated, not benchmarked yet.
+	fn approve_deposit_currency() -> Weight {
+		Weight::from_parts(6_412_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: Identity DepositCurrencies (r:1 w:1)
+	// Estimated, not benchmarked yet.
+	fn revoke_deposit_currency() -> Weight {
+		Weight::from_parts(9_107_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: Identity DepositCurrencies (r:1 w:0)
+	// Storage: Identity IdentityOf (r:1 w:1)
+	// Storage: Identity SubsOf (r:1 w:1)
+	// Storage: Identity DepositCurrencyOf (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Estimated, not benchmarked yet.
+	fn set_deposit_currency(r: u32, s: u32, x: u32, ) -> Weight {
+		Weight::from_parts(45_618_000, 0)
+			.saturating_add(Weight::from_parts(236_000, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(97_000, 0).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(857_000, 0).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	// Storage: Identity Registrars (r:1 w:0)
+	// Storage: Identity IdentityOf (r:1 w:1)
+	// Storage: Identity JudgementNonces (r:1 w:1)
+	// Storage: System Account (r:0 w:2)
+	// Estimated, not benchmarked yet.
+	fn provide_judgement_signed(r: u32, x: u32, ) -> Weight {
+		Weight::from_parts(80_949_000, 0)
+			.saturating_add(Weight::from_parts(286_000, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(856_000, 0).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
 }
 
@@ -235,166 +417,332 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 impl WeightInfo for () {
 	// Storage: Identity Registrars (r:1 w:1)
 	fn add_registrar(r: u32, ) -> Weight {
-		(19_176_000 as Weight)
+		Weight::from_parts(19_176_000, 0)
 			// Standard Error: 5_000
-			.saturating_add((313_000 as Weight).saturating_mul(r as Weight))
-			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(Weight::from_parts(313_000, 0).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	// Storage: Identity PendingRequests (r:1 w:1)
+	// Storage: Identity Registrars (r:1 w:1)
+	// Storage: Identity IdentityOf (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Estimated, not benchmarked yet.
+	fn remove_registrar(r: u32, p: u32, ) -> Weight {
+		Weight::from_parts(21_503_000, 0)
+			.saturating_add(Weight::from_parts(297_000, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(24_620_000, 0).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(p.into())))
 	}
 	// Storage: Identity IdentityOf (r:1 w:1)
 	fn set_identity(r: u32, x: u32, ) -> Weight {
-		(44_668_000 as Weight)
+		Weight::from_parts(44_668_000, 0)
 			// Standard Error: 12_000
-			.saturating_add((244_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(Weight::from_parts(244_000, 0).saturating_mul(r.into()))
 			// Standard Error: 1_000
-			.saturating_add((811_000 as Weight).saturating_mul(x as Weight))
-			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(Weight::from_parts(811_000, 0).saturating_mul(x.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
 	// Storage: Identity IdentityOf (r:1 w:0)
 	// Storage: Identity SubsOf (r:1 w:1)
 	// Storage: Identity SuperOf (r:1 w:1)
 	fn set_subs_new(s: u32, ) -> Weight {
-		(38_917_000 as Weight)
+		Weight::from_parts(38_917_000, 0)
 			// Standard Error: 3_000
-			.saturating_add((5_331_000 as Weight).saturating_mul(s as Weight))
-			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
-			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
-			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(Weight::from_parts(5_331_000, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(s.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(s.into())))
 	}
 	// Storage: Identity IdentityOf (r:1 w:0)
 	// Storage: Identity SubsOf (r:1 w:1)
 	// Storage: Identity SuperOf (r:0 w:1)
 	fn set_subs_old(p: u32, ) -> Weight {
-		(36_057_000 as Weight)
+		Weight::from_parts(36_057_000, 0)
 			// Standard Error: 1_000
-			.saturating_add((1_756_000 as Weight).saturating_mul(p as Weight))
-			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
-			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(Weight::from_parts(1_756_000, 0).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(p.into())))
 	}
 	// Storage: Identity SubsOf (r:1 w:1)
 	// Storage: Identity IdentityOf (r:1 w:1)
+	// Storage: Identity Registrars (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Tokens Accounts (r:20 w:20)
 	// Storage: Identity SuperOf (r:0 w:100)
+	// Storage: Identity PendingRequests (r:0 w:20)
+	// Storage: Identity JudgementExpiries (r:0 w:20)
+	// Estimated, not benchmarked yet.
 	fn clear_identity(r: u32, s: u32, x: u32, ) -> Weight {
-		(44_348_000 as Weight)
-			// Standard Error: 9_000
-			.saturating_add((183_000 as Weight).saturating_mul(r as Weight))
-			// Standard Error: 1_000
-			.saturating_add((1_724_000 as Weight).saturating_mul(s as Weight))
-			// Standard Error: 1_000
-			.saturating_add((439_000 as Weight).saturating_mul(x as Weight))
-			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
-			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(s as Weight)))
+		Weight::from_parts(44_348_000, 0)
+			.saturating_add(Weight::from_parts(183_000, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(1_724_000, 0).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(439_000, 0).saturating_mul(x.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(s.into())))
 	}
 	// Storage: Identity Registrars (r:1 w:0)
 	// Storage: Identity IdentityOf (r:1 w:1)
+	// Storage: Tokens Accounts (r:1 w:1)
+	// Storage: Identity PendingRequests (r:0 w:1)
+	// Estimated, not benchmarked yet.
 	fn request_judgement(r: u32, x: u32, ) -> Weight {
-		(46_592_000 as Weight)
-			// Standard Error: 5_000
-			.saturating_add((321_000 as Weight).saturating_mul(r as Weight))
-			// Standard Error: 0
-			.saturating_add((858_000 as Weight).saturating_mul(x as Weight))
-			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+		Weight::from_parts(46_592_000, 0)
+			.saturating_add(Weight::from_parts(321_000, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(858_000, 0).saturating_mul(x.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
 	}
 	// Storage: Identity IdentityOf (r:1 w:1)
+	// Storage: Identity Registrars (r:1 w:0)
+	// Storage: Tokens Accounts (r:1 w:1)
+	// Storage: Identity PendingRequests (r:0 w:1)
+	// Estimated, not benchmarked yet.
 	fn cancel_request(r: u32, x: u32, ) -> Weight {
-		(43_556_000 as Weight)
-			// Standard Error: 6_000
-			.saturating_add((174_000 as Weight).saturating_mul(r as Weight))
-			// Standard Error: 0
-			.saturating_add((850_000 as Weight).saturating_mul(x as Weight))
-			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+		Weight::from_parts(43_556_000, 0)
+			.saturating_add(Weight::from_parts(174_000, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(850_000, 0).saturating_mul(x.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
 	}
 	// Storage: Identity Registrars (r:1 w:1)
 	fn set_fee(r: u32, ) -> Weight {
-		(7_971_000 as Weight)
+		Weight::from_parts(7_971_000, 0)
 			// Standard Error: 4_000
-			.saturating_add((283_000 as Weight).saturating_mul(r as Weight))
-			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(Weight::from_parts(283_000, 0).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
 	// Storage: Identity Registrars (r:1 w:1)
 	fn set_account_id(r: u32, ) -> Weight {
-		(8_234_000 as Weight)
+		Weight::from_parts(8_234_000, 0)
 			// Standard Error: 4_000
-			.saturating_add((280_000 as Weight).saturating_mul(r as Weight))
-			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(Weight::from_parts(280_000, 0).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
 	// Storage: Identity Registrars (r:1 w:1)
 	fn set_fields(r: u32, ) -> Weight {
-		(8_126_000 as Weight)
+		Weight::from_parts(8_126_000, 0)
 			// Standard Error: 4_000
-			.saturating_add((275_000 as Weight).saturating_mul(r as Weight))
-			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(Weight::from_parts(275_000, 0).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
 	// Storage: Identity Registrars (r:1 w:0)
 	// Storage: Identity IdentityOf (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: Identity PendingRequests (r:0 w:1)
+	// Storage: Identity JudgementNonces (r:1 w:1)
+	// Storage: Identity JudgementExpiries (r:0 w:1)
+	// Storage: Identity ExpiringJudgements (r:0 w:1)
+	// Estimated, not benchmarked yet.
 	fn provide_judgement(r: u32, x: u32, ) -> Weight {
-		(30_949_000 as Weight)
-			// Standard Error: 5_000
-			.saturating_add((286_000 as Weight).saturating_mul(r as Weight))
-			// Standard Error: 0
-			.saturating_add((856_000 as Weight).saturating_mul(x as Weight))
-			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+		Weight::from_parts(30_949_000, 0)
+			.saturating_add(Weight::from_parts(286_000, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(856_000, 0).saturating_mul(x.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
 	}
 	// Storage: Identity SubsOf (r:1 w:1)
 	// Storage: Identity IdentityOf (r:1 w:1)
-	// Storage: System Account (r:1 w:1)
+	// Storage: Identity Registrars (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Tokens Accounts (r:40 w:40)
 	// Storage: Identity SuperOf (r:0 w:100)
+	// Storage: Identity PendingRequests (r:0 w:20)
+	// Storage: Identity JudgementExpiries (r:0 w:20)
+	// Estimated, not benchmarked yet.
 	fn kill_identity(r: u32, s: u32, _x: u32, ) -> Weight {
-		(63_792_000 as Weight)
-			// Standard Error: 11_000
-			.saturating_add((242_000 as Weight).saturating_mul(r as Weight))
-			// Standard Error: 1_000
-			.saturating_add((1_738_000 as Weight).saturating_mul(s as Weight))
-			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
-			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(s as Weight)))
+		Weight::from_parts(63_792_000, 0)
+			.saturating_add(Weight::from_parts(242_000, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(1_738_000, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes((4_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(s.into())))
 	}
 	// Storage: Identity IdentityOf (r:1 w:0)
 	// Storage: Identity SuperOf (r:1 w:1)
 	// Storage: Identity SubsOf (r:1 w:1)
 	fn add_sub(s: u32, ) -> Weight {
-		(48_751_000 as Weight)
+		Weight::from_parts(48_751_000, 0)
 			// Standard Error: 1_000
-			.saturating_add((193_000 as Weight).saturating_mul(s as Weight))
-			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(Weight::from_parts(193_000, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
 	// Storage: Identity IdentityOf (r:1 w:0)
 	// Storage: Identity SuperOf (r:1 w:1)
 	fn rename_sub(s: u32, ) -> Weight {
-		(15_892_000 as Weight)
+		Weight::from_parts(15_892_000, 0)
 			// Standard Error: 0
-			.saturating_add((49_000 as Weight).saturating_mul(s as Weight))
-			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(Weight::from_parts(49_000, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
 	// Storage: Identity IdentityOf (r:1 w:0)
 	// Storage: Identity SuperOf (r:1 w:1)
 	// Storage: Identity SubsOf (r:1 w:1)
 	fn remove_sub(s: u32, ) -> Weight {
-		(49_746_000 as Weight)
+		Weight::from_parts(49_746_000, 0)
 			// Standard Error: 1_000
-			.saturating_add((181_000 as Weight).saturating_mul(s as Weight))
-			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(Weight::from_parts(181_000, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
 	// Storage: Identity SuperOf (r:1 w:1)
 	// Storage: Identity SubsOf (r:1 w:1)
 	fn quit_sub(s: u32, ) -> Weight {
-		(32_286_000 as Weight)
+		Weight::from_parts(32_286_000, 0)
 			// Standard Error: 1_000
-			.saturating_add((166_000 as Weight).saturating_mul(s as Weight))
-			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(Weight::from_parts(166_000, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	// Storage: Identity UsernameAuthorities (r:0 w:1)
+	// Estimated, not benchmarked yet.
+	fn add_username_authority() -> Weight {
+		Weight::from_parts(13_240_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	// Storage: Identity UsernameAuthorities (r:1 w:1)
+	// Estimated, not benchmarked yet.
+	fn remove_username_authority() -> Weight {
+		Weight::from_parts(15_518_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	// Storage: Identity AccountOfUsername (r:2 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Identity PrimaryUsernameOf (r:1 w:1)
+	// Estimated, not benchmarked yet.
+	fn set_username() -> Weight {
+		Weight::from_parts(42_107_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	// Storage: Identity UsernameAuthorities (r:1 w:1)
+	// Storage: Identity AccountOfUsername (r:2 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Identity PrimaryUsernameOf (r:1 w:1)
+	// Estimated, not benchmarked yet.
+	fn grant_username() -> Weight {
+		Weight::from_parts(47_832_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	// Storage: Identity AccountOfUsername (r:1 w:1)
+	// Estimated, not benchmarked yet.
+	fn renew_username() -> Weight {
+		Weight::from_parts(18_905_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	// Storage: Identity AccountOfUsername (r:1 w:0)
+	// Storage: Identity PrimaryUsernameOf (r:0 w:1)
+	// Estimated, not benchmarked yet.
+	fn set_primary_username() -> Weight {
+		Weight::from_parts(17_361_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	// Storage: Identity AccountOfUsername (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Identity PrimaryUsernameOf (r:1 w:1)
+	// Estimated, not benchmarked yet.
+	fn release_username() -> Weight {
+		Weight::from_parts(35_264_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	// Storage: Identity Registrars (r:1 w:1)
+	// Estimated, not benchmarked yet.
+	fn set_judgement_period(r: u32, ) -> Weight {
+		Weight::from_parts(8_102_000, 0)
+			.saturating_add(Weight::from_parts(279_000, 0).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	// Storage: Identity ExpiringJudgements (r:1 w:1)
+	// Storage: Identity JudgementExpiries (r:1 w:1)
+	// Storage: Identity IdentityOf (r:1 w:1)
+	// Estimated, not benchmarked yet.
+	fn expire_judgement(r: u32, x: u32, ) -> Weight {
+		Weight::from_parts(27_416_000, 0)
+			.saturating_add(Weight::from_parts(262_000, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(851_000, 0).saturating_mul(x.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	// Storage: Identity IdentityOf (r:1 w:1)
+	// Storage: Identity SubsOf (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Estimated, not benchmarked yet.
+	fn poke_deposit(r: u32, s: u32, x: u32, ) -> Weight {
+		Weight::from_parts(38_204_000, 0)
+			.saturating_add(Weight::from_parts(231_000, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(94_000, 0).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(853_000, 0).saturating_mul(x.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	// Storage: Identity Registrars (r:1 w:1)
+	// Storage: Identity PendingRequests (r:1 w:0)
+	// Estimated, not benchmarked yet.
+	fn set_fee_currency(r: u32, ) -> Weight {
+		Weight::from_parts(9_873_000, 0)
+			.saturating_add(Weight::from_parts(285_000, 0).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	// Storage: Identity DepositCurrencies (r:0 w:1)
+	// Estimated, not benchmarked yet.
+	fn approve_deposit_currency() -> Weight {
+		Weight::from_parts(6_412_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	// Storage: Identity DepositCurrencies (r:1 w:1)
+	// Estimated, not benchmarked yet.
+	fn revoke_deposit_currency() -> Weight {
+		Weight::from_parts(9_107_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	// Storage: Identity DepositCurrencies (r:1 w:0)
+	// Storage: Identity IdentityOf (r:1 w:1)
+	// Storage: Identity SubsOf (r:1 w:1)
+	// Storage: Identity DepositCurrencyOf (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Estimated, not benchmarked yet.
+	fn set_deposit_currency(r: u32, s: u32, x: u32, ) -> Weight {
+		Weight::from_parts(45_618_000, 0)
+			.saturating_add(Weight::from_parts(236_000, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(97_000, 0).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(857_000, 0).saturating_mul(x.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	// Storage: Identity Registrars (r:1 w:0)
+	// Storage: Identity IdentityOf (r:1 w:1)
+	// Storage: Identity JudgementNonces (r:1 w:1)
+	// Storage: System Account (r:0 w:2)
+	// Estimated, not benchmarked yet.
+	fn provide_judgement_signed(r: u32, x: u32, ) -> Weight {
+		Weight::from_parts(80_949_000, 0)
+			.saturating_add(Weight::from_parts(286_000, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(856_000, 0).saturating_mul(x.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
 	}
 }
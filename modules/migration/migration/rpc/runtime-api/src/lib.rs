@@ -0,0 +1,13 @@
+//! Runtime API definition for the migration module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait MigrationApi {
+		/// Storage prefixes queued for cleanup with the number of keys left under each, counting
+		/// at most `limit` keys per prefix.
+		fn cleanup_remaining(limit: u32) -> Vec<(Vec<u8>, u32)>;
+	}
+}
@@ -1,29 +1,101 @@
 //! # Migration pallet
+//!
+//! Imports state exported from another chain. Balances are imported in chunks: root starts the
+//! import with `begin_balances_migration` and the expected number of rows, submits the rows with
+//! `migrate_balances` starting at the stored cursor, and completes it with
+//! `finalize_balances_migration` once every row has been processed.
+//!
+//! Each row is credited as free balance and then split into named reserves (mapped through
+//! `ReserveIdOf`), anonymous reserves and locks (re-created through `Locks`). A row which fails
+//! half way is rolled back and skipped. Finalizing emits `BalancesReconciled` with the imported
+//! totals and the issuance before and after the import.
+//!
+//! Rows are SCALE encoded [`BalanceRecord`]s and [`IdentityRecord`]s, bounded by `MaxChunkSize`
+//! per call. The `migration-converter` tool in `bin/utils` turns JSON exports into call data.
+//!
+//! Identities are imported with their judgements, mapped to local registrars through
+//! `RegistrarIndexOf`, and their sub accounts, reserving the recorded deposits. Every identity
+//! which differs from its source is listed in `IdentityMismatches` with an [`IdentityReport`].
+//!
+//! Instead of pushing every account, root can start an airdrop with `start_airdrop`, committing to
+//! the Merkle root over all [`ClaimLeaf`]s. Every account then claims its leaf with a free
+//! unsigned `claim` and a proof built by `migration-converter claims`, which vests the claimed
+//! amount with the schedule of the leaf through `Locks`. Once the deadline is reached, the
+//! unclaimed rest is credited to the treasury.
+//!
+//! Storage of removed pallets is not deleted in one go. `on_runtime_upgrade` queues their prefixes,
+//! and `CleanupOrigin` can queue further ones with `register_cleanup`. The queued prefixes are
+//! cleaned up in `on_idle`, at most `CleanupBatchSize` keys per block within the remaining weight,
+//! continuing after the last removed key. `MigrationApi::cleanup_remaining` reports the keys left.
+//!
+//! With the `try-runtime` feature, `post_upgrade` checks the imported state: the issuance grew by
+//! exactly the imported balances while a balances import is ongoing and equals the sum of all
+//! balances, the storage of the removed pallets is cleaned up, and every identity has its deposits
+//! reserved. Run it with
+//! `try-runtime on-runtime-upgrade live --uri <node>` on a node built with `--features try-runtime`.
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::{Decode, Encode, MaxEncodedLen};
-use frame_support::{traits::{Currency, StoredMap}};
-use sp_runtime::AccountId32;
-use sp_runtime::{traits::SaturatedConversion, RuntimeDebug};
-use sp_std::{num::ParseIntError};
-use serde_json::{Value};
-use serde::{Deserialize};
-use scale_info::prelude::string::String;
-use sp_std::convert::TryInto;
-use sp_std::vec::Vec;
+use frame_support::{
+	dispatch::DispatchResult,
+	ensure,
+	storage::with_transaction,
+	traits::{
+		ConstU32, Currency, Get, LockIdentifier, LockableCurrency, NamedReservableCurrency,
+		ReservableCurrency, StoredMap, VestingSchedule, WithdrawReasons,
+	},
+	weights::Weight,
+	BoundedVec,
+};
+use sp_core::H256;
+use sp_io::hashing::blake2_256;
+use sp_runtime::{
+	traits::{CheckedAdd, CheckedSub, Convert, Saturating, Zero},
+	RuntimeDebug, TransactionOutcome,
+};
+use sp_std::{boxed::Box, convert::TryInto, vec::Vec};
 
 pub use pallet::*;
 use scale_info::TypeInfo;
-use pallet_identity::{Registration, Data, IdentityInfo};
-use pallet_balances::AccountData;
+use pallet_identity::{IdentityInfo, Judgement, RegistrarIndex, Registration};
+use pallet_balances::{AccountData, Reasons};
+use orml_traits::MultiReservableCurrency;
 
 pub mod migration;
 pub use migration::migrate;
 
+pub mod merkle;
+#[cfg(test)]
+mod tests;
+mod types;
+pub use types::*;
 
-type BalanceOf<T> = <<T as pallet_identity::Config>::Currency as Currency<
+
+type BalanceOf<T> = pallet_identity::BalanceOf<T>;
+
+pub type BalanceRecordOf<T> = BalanceRecord<
+	<T as frame_system::Config>::AccountId,
+	<T as pallet_balances::Config>::Balance,
+	<T as pallet_balances::Config>::MaxReserves,
+	<T as pallet_balances::Config>::MaxLocks,
+>;
+
+pub type ClaimLeafOf<T> =
+	ClaimLeaf<<T as frame_system::Config>::AccountId, <T as pallet_balances::Config>::Balance>;
+
+pub type AirdropInfoOf<T> =
+	AirdropInfo<<T as pallet_balances::Config>::Balance, <T as frame_system::Config>::BlockNumber>;
+
+pub type IdentityRecordOf<T> = IdentityRecord<
 	<T as frame_system::Config>::AccountId,
->>::Balance;
+	BalanceOf<T>,
+	<T as pallet_identity::Config>::MaxAdditionalFields,
+	<T as pallet_identity::Config>::MaxRegistrars,
+	<T as pallet_identity::Config>::MaxSubAccounts,
+>;
+
+/// A storage prefix queued for cleanup, at least a pallet prefix of 16 bytes.
+pub type CleanupPrefix = BoundedVec<u8, ConstU32<128>>;
 
 #[derive(Encode, Decode, PartialEq, Clone, TypeInfo)]
 pub enum MigrationStatus {
@@ -32,6 +104,161 @@ pub enum MigrationStatus {
 	Complete,
 }
 
+/// Progress of a chunked import, kept on chain between chunks.
+#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, TypeInfo, MaxEncodedLen)]
+pub struct ImportProgress {
+	/// Number of rows the whole import consists of.
+	pub expected: u32,
+	/// Number of rows processed so far, the cursor the next chunk has to start at.
+	pub processed: u32,
+	/// Number of rows written to storage.
+	pub imported: u32,
+	/// Number of rows skipped because they could not be imported.
+	pub skipped: u32,
+}
+
+/// Why a row of an import was skipped.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, TypeInfo)]
+pub enum SkipReason {
+	/// The account cannot be written to the account store.
+	InvalidAccount,
+	/// The account already holds a balance on this chain.
+	AccountExists,
+	/// Importing the row would overflow the total issuance.
+	Overflow,
+	/// The named reserves exceed the reserved balance or cannot be created.
+	InvalidReserve,
+	/// The judgements exceed `MaxRegistrars` once mapped to the local registrars.
+	TooManyJudgements,
+	/// The sub accounts exceed `MaxSubAccounts`.
+	TooManySubAccounts,
+	/// The deposits cannot be reserved from the free balance of the account.
+	InsufficientBalance,
+}
+
+/// Balances written by an import, to reconcile the total issuance once it is finalized.
+#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, TypeInfo, MaxEncodedLen)]
+pub struct ImportedBalances<Balance> {
+	/// Sum of the imported free balances.
+	pub free: Balance,
+	/// Sum of the imported reserved balances, named or not.
+	pub reserved: Balance,
+	/// Total issuance when the import was started.
+	pub issuance_before: Balance,
+}
+
+/// Re-creates locks and vesting schedules of the source chain in the target runtime.
+///
+/// The funds are imported as free balance in any case; a lock or vesting schedule which is not
+/// re-created just leaves them unlocked.
+pub trait ImportLocks<AccountId, Balance> {
+	/// Lock `amount` of `who` for `reasons`, `id` being the lock identifier on the source chain.
+	/// Returns `false` if the lock is not supported.
+	fn import_lock(
+		who: &AccountId,
+		id: LockIdentifier,
+		amount: Balance,
+		reasons: WithdrawReasons,
+	) -> bool;
+
+	/// Vest `locked` of `who`, unlocking `per_block` from `starting_block` on. Returns `false` if
+	/// vesting is not supported.
+	fn import_vesting(
+		who: &AccountId,
+		locked: Balance,
+		per_block: Balance,
+		starting_block: u32,
+	) -> bool;
+
+	/// Whether [`Self::import_vesting`] would succeed with the same arguments, without changing
+	/// anything.
+	fn can_import_vesting(
+		who: &AccountId,
+		locked: Balance,
+		per_block: Balance,
+		starting_block: u32,
+	) -> bool;
+}
+
+/// Drops every lock and vesting schedule.
+impl<AccountId, Balance> ImportLocks<AccountId, Balance> for () {
+	fn import_lock(_: &AccountId, _: LockIdentifier, _: Balance, _: WithdrawReasons) -> bool {
+		false
+	}
+
+	fn import_vesting(_: &AccountId, _: Balance, _: Balance, _: u32) -> bool {
+		false
+	}
+
+	fn can_import_vesting(_: &AccountId, _: Balance, _: Balance, _: u32) -> bool {
+		false
+	}
+}
+
+/// Re-creates locks with their source chain identifier in `pallet_balances`, drops vesting.
+pub struct BalancesLocks<T>(sp_std::marker::PhantomData<T>);
+impl<T: pallet_balances::Config> ImportLocks<T::AccountId, T::Balance> for BalancesLocks<T> {
+	fn import_lock(
+		who: &T::AccountId,
+		id: LockIdentifier,
+		amount: T::Balance,
+		reasons: WithdrawReasons,
+	) -> bool {
+		<pallet_balances::Pallet<T> as LockableCurrency<T::AccountId>>::set_lock(
+			id, who, amount, reasons,
+		);
+		true
+	}
+
+	fn import_vesting(_: &T::AccountId, _: T::Balance, _: T::Balance, _: u32) -> bool {
+		false
+	}
+
+	fn can_import_vesting(_: &T::AccountId, _: T::Balance, _: T::Balance, _: u32) -> bool {
+		false
+	}
+}
+
+/// Re-creates locks like [`BalancesLocks`] and vesting schedules through `Vesting`, usually
+/// `pallet_vesting`.
+pub struct BalancesVesting<T, Vesting>(sp_std::marker::PhantomData<(T, Vesting)>);
+impl<T, Vesting> ImportLocks<T::AccountId, T::Balance> for BalancesVesting<T, Vesting>
+where
+	T: pallet_balances::Config,
+	Vesting: VestingSchedule<
+		T::AccountId,
+		Moment = T::BlockNumber,
+		Currency = pallet_balances::Pallet<T>,
+	>,
+{
+	fn import_lock(
+		who: &T::AccountId,
+		id: LockIdentifier,
+		amount: T::Balance,
+		reasons: WithdrawReasons,
+	) -> bool {
+		BalancesLocks::<T>::import_lock(who, id, amount, reasons)
+	}
+
+	fn import_vesting(
+		who: &T::AccountId,
+		locked: T::Balance,
+		per_block: T::Balance,
+		starting_block: u32,
+	) -> bool {
+		Vesting::add_vesting_schedule(who, locked, per_block, starting_block.into()).is_ok()
+	}
+
+	fn can_import_vesting(
+		who: &T::AccountId,
+		locked: T::Balance,
+		per_block: T::Balance,
+		starting_block: u32,
+	) -> bool {
+		Vesting::can_add_vesting_schedule(who, locked, per_block, starting_block.into()).is_ok()
+	}
+}
+
 #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, TypeInfo, MaxEncodedLen)]
 pub enum StorageVersion {
 	V1Initial,
@@ -41,31 +268,16 @@ impl Default for StorageVersion {
 	fn default() -> Self {Self::V1Initial}
 }
 
-// Balances structs
-#[derive(Deserialize)]
-struct AccountItem {
-	pub account_id: String,
-	pub free_balance: String,
-	pub reserved_balance: String,
-}
-
-// Identity structs
-struct IdentityItem<T: Config> {
-	account_id: T::AccountId,
-	registration: Registration<BalanceOf<T>, T::MaxRegistrars, T::MaxAdditionalFields>
-}
-
 #[frame_support::pallet]
 pub mod pallet {
 	use frame_support::pallet_prelude::*;
-	use frame_support::transactional;
 	use frame_system::pallet_prelude::*;
-	use sp_std::vec::Vec;
 
 	use super::*;
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub (super) trait Store)]
+	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
@@ -74,14 +286,77 @@ pub mod pallet {
 		+ pallet_identity::Config
 		+ pallet_balances::Config
 	{
-		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Maximum number of rows accepted in one chunk of an import.
+		#[pallet::constant]
+		type MaxChunkSize: Get<u32>;
+
+		/// Maps named reserve identifiers of the source chain to the `ReserveIdentifier` of this
+		/// runtime. Named reserves which are not mapped are imported as anonymous reserves.
+		type ReserveIdOf: Convert<
+			[u8; 8],
+			Option<<Self as pallet_balances::Config>::ReserveIdentifier>,
+		>;
+
+		/// Re-creates locks and vesting schedules of the source chain.
+		type Locks: ImportLocks<Self::AccountId, <Self as pallet_balances::Config>::Balance>;
+
+		/// Maps registrar indices of the source chain to the local ones. Judgements of registrars
+		/// which are not mapped are dropped.
+		type RegistrarIndexOf: Convert<RegistrarIndex, Option<RegistrarIndex>>;
+
+		/// Receives the unclaimed rest of an airdrop after its deadline.
+		type TreasuryAccount: Get<Self::AccountId>;
+
+		/// Maximum number of hashes in an airdrop proof.
+		#[pallet::constant]
+		type MaxProofLength: Get<u32>;
+
+		/// Origin allowed to queue storage prefixes for cleanup.
+		type CleanupOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Maximum number of prefixes queued for cleanup.
+		#[pallet::constant]
+		type MaxCleanups: Get<u32>;
+
+		/// Maximum number of keys removed per block.
+		#[pallet::constant]
+		type CleanupBatchSize: Get<u32>;
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-		fn on_runtime_upgrade() -> frame_support::weights::Weight {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			match <Airdrop<T>>::get() {
+				Some(airdrop) if now >= airdrop.deadline => {
+					Self::expire_airdrop(airdrop);
+					T::DbWeight::get().reads_writes(3, 3)
+				},
+				_ => T::DbWeight::get().reads(1),
+			}
+		}
+
+		fn on_idle(_now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			let (base, per_key) = migration::cleanup_weights::<T>();
+			if base.saturating_add(per_key).any_gt(remaining_weight) {
+				return Weight::zero()
+			}
+			let spare = remaining_weight.saturating_sub(base).ref_time();
+			let limit =
+				(spare / per_key.ref_time().max(1)).min(T::CleanupBatchSize::get() as u64) as u32;
+			let removed = migration::cleanup::<T>(limit);
+			base.saturating_add(per_key.saturating_mul(removed as u64))
+		}
+
+		fn on_runtime_upgrade() -> Weight {
 			migrate::<T>()
 		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+			migration::post_upgrade::<T>()
+		}
 	}
 
 	#[pallet::storage]
@@ -93,59 +368,242 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(super) type MigrationVersion<T: Config> = StorageValue<_, StorageVersion, ValueQuery, GetDefault>;
 
+	/// Progress of the balances import, `None` unless one is ongoing.
+	#[pallet::storage]
+	pub(super) type BalancesProgress<T: Config> = StorageValue<_, ImportProgress, OptionQuery>;
+
+	/// Balances written by the ongoing balances import.
+	#[pallet::storage]
+	pub(super) type BalancesImported<T: Config> =
+		StorageValue<_, ImportedBalances<<T as pallet_balances::Config>::Balance>, ValueQuery>;
+
+	/// Identities which differ from their source, as found by the identities import.
+	#[pallet::storage]
+	pub(super) type IdentityMismatches<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, IdentityReport<BalanceOf<T>>, OptionQuery>;
+
+	/// Number of identities imported and of those which differ from their source.
+	#[pallet::storage]
+	pub(super) type IdentitiesImported<T: Config> = StorageValue<_, (u32, u32), ValueQuery>;
+
+	/// The ongoing airdrop, if any.
+	#[pallet::storage]
+	pub(super) type Airdrop<T: Config> = StorageValue<_, AirdropInfoOf<T>, OptionQuery>;
+
+	/// Accounts which claimed their leaf, by airdrop root.
+	#[pallet::storage]
+	pub(super) type Claimed<T: Config> =
+		StorageDoubleMap<_, Identity, H256, Blake2_128Concat, T::AccountId, bool, ValueQuery>;
+
+	/// Storage prefixes to clean up, in order.
+	#[pallet::storage]
+	pub(super) type CleanupQueue<T: Config> =
+		StorageValue<_, BoundedVec<CleanupPrefix, T::MaxCleanups>, ValueQuery>;
+
+	/// Last key removed under the first prefix of `CleanupQueue`, the cleanup continues after it.
+	#[pallet::storage]
+	pub(super) type CleanupCursor<T: Config> = StorageValue<_, Vec<u8>, OptionQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
+		/// A chunk of balances was imported. \[imported\]
 		MigratedBalances(u32),
+		/// A chunk of identities was imported. \[imported\]
 		MigratedIdentities(u32),
+		/// A balances import of `expected` rows was started. \[expected\]
+		BalancesMigrationStarted(u32),
+		/// A row of the balances import was skipped. \[index, reason\]
+		BalanceSkipped(u32, SkipReason),
+		/// The balances import was completed. \[imported, skipped\]
+		BalancesMigrationFinalized(u32, u32),
+		/// Issuance after the balances import. The issuance before plus the imported free and
+		/// reserved balances equals the issuance after, unless it was changed by other means
+		/// meanwhile. \[free, reserved, issuance_before, issuance_after\]
+		BalancesReconciled(
+			<T as pallet_balances::Config>::Balance,
+			<T as pallet_balances::Config>::Balance,
+			<T as pallet_balances::Config>::Balance,
+			<T as pallet_balances::Config>::Balance,
+		),
+		/// A lock could not be re-created and was dropped. \[who, id\]
+		LockDropped(T::AccountId, LockIdentifier),
+		/// A vesting schedule could not be re-created and was dropped. \[who\]
+		VestingDropped(T::AccountId),
+		/// An identity was not imported. \[who, reason\]
+		IdentitySkipped(T::AccountId, SkipReason),
+		/// An identity was imported with differences to its source. \[who, report\]
+		IdentityMismatch(T::AccountId, IdentityReport<BalanceOf<T>>),
+		/// The identities import was completed. \[imported, mismatched\]
+		IdentitiesMigrationFinalized(u32, u32),
+		/// An airdrop was started. \[root, total, deadline\]
+		AirdropStarted(H256, <T as pallet_balances::Config>::Balance, T::BlockNumber),
+		/// A leaf of the airdrop was claimed. \[who, amount\]
+		AirdropClaimed(T::AccountId, <T as pallet_balances::Config>::Balance),
+		/// The airdrop reached its deadline, the unclaimed rest went to the treasury.
+		/// \[unclaimed\]
+		AirdropExpired(<T as pallet_balances::Config>::Balance),
+		/// A storage prefix was queued for cleanup. \[prefix\]
+		CleanupRegistered(Vec<u8>),
+		/// Keys under a queued prefix were removed. \[prefix, removed\]
+		CleanupProgress(Vec<u8>, u32),
+		/// No key is left under a queued prefix, it was dequeued. \[prefix\]
+		CleanupCompleted(Vec<u8>),
 	}
 
 	#[pallet::error]
 	pub enum Error<T> {
 		GuruMeditation,
+		/// The import has already been completed.
+		AlreadyMigrated,
+		/// An import is already ongoing.
+		MigrationOngoing,
+		/// No import has been started.
+		MigrationNotStarted,
+		/// The chunk does not start where the previous one ended.
+		InvalidCursor,
+		/// The chunk exceeds the expected total.
+		TooManyItems,
+		/// Not all expected rows have been processed yet.
+		MigrationIncomplete,
+		/// No airdrop is ongoing.
+		NoAirdrop,
+		/// An airdrop is already ongoing.
+		AirdropOngoing,
+		/// The deadline of the airdrop is not in the future or has passed.
+		InvalidDeadline,
+		/// The leaf has already been claimed.
+		AlreadyClaimed,
+		/// The proof does not lead to the airdrop root.
+		InvalidProof,
+		/// The identity does not match the identity hash of the leaf.
+		IdentityMismatch,
+		/// The claim exceeds the total of the airdrop.
+		ClaimExceedsTotal,
+		/// The vesting schedule of the leaf locks more than its amount, unlocks nothing per block
+		/// or cannot be created.
+		InvalidVesting,
+		/// The account already has an identity, which has to be cleared before claiming another
+		/// one.
+		IdentityExists,
+		/// The prefix is shorter than a pallet prefix.
+		PrefixTooShort,
+		/// The prefix overlaps a prefix already queued for cleanup.
+		CleanupAlreadyRegistered,
+		/// The cleanup queue is full.
+		TooManyCleanups,
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 
-		/// Migrates to `Balances` storage from another chain.
-		/// Storage: `TotalIssuance`, AccountStore
-		#[pallet::weight(5_000_000)]
-		#[transactional]
+		/// Starts an import of `expected` rows into `Balances` storage.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 1))]
+		pub fn begin_balances_migration(
+			origin: OriginFor<T>,
+			expected: u32,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(<BalancesVersion<T>>::get() != StorageVersion::V2Imported, Error::<T>::AlreadyMigrated);
+			ensure!(!<BalancesProgress<T>>::exists(), Error::<T>::MigrationOngoing);
+
+			<BalancesProgress<T>>::put(ImportProgress { expected, ..Default::default() });
+			<BalancesImported<T>>::put(ImportedBalances {
+				issuance_before: pallet_balances::TotalIssuance::<T>::get(),
+				..Default::default()
+			});
+			Self::deposit_event(Event::<T>::BalancesMigrationStarted(expected));
+			Ok(())
+		}
+
+		/// Migrates a chunk of rows to `Balances` storage from another chain.
+		///
+		/// `cursor` is the index of the first row of the chunk and has to match the number of
+		/// rows processed so far, so a failed chunk can simply be resubmitted. Rows which cannot
+		/// be imported are skipped with a `BalanceSkipped` event.
+		///
+		/// Storage: `TotalIssuance`, AccountStore, `Reserves`, `Locks`
+		#[pallet::call_index(1)]
+		#[pallet::weight(balances_chunk_weight::<T>(records.len() as u32))]
 		pub fn migrate_balances(
 			origin: OriginFor<T>,
-			data: Vec<u8>,
+			cursor: u32,
+			records: BoundedVec<BalanceRecordOf<T>, T::MaxChunkSize>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
-			
-			if <BalancesVersion<T>>::get() == StorageVersion::V2Imported {
-				return Ok(())
-			}
 
-			let data_str = sp_std::str::from_utf8(&data).unwrap();
-			let parsed: Vec<AccountItem> = serde_json::from_str(data_str).unwrap();
+			let items = records.len() as u32;
+			let mut progress =
+				<BalancesProgress<T>>::get().ok_or(Error::<T>::MigrationNotStarted)?;
+			ensure!(cursor == progress.processed, Error::<T>::InvalidCursor);
+			ensure!(
+				progress.processed.saturating_add(items) <= progress.expected,
+				Error::<T>::TooManyItems
+			);
 
-			for acc in &parsed {
-				let account_id = Self::get_account(acc);
-				let balance = Self::get_balance(&acc);
-				<T as pallet_balances::Config>::AccountStore::insert(&account_id, AccountData { free: balance, ..Default::default() }).unwrap();
-				pallet_balances::TotalIssuance::<T>::mutate(|t| *t += balance);
+			let mut imported = 0u32;
+			for (index, acc) in (cursor..).zip(records.iter()) {
+				// a skipped row must not leave anything behind
+				let result = with_transaction(|| match Self::import_balance(acc) {
+					Ok(()) => TransactionOutcome::Commit(Ok(Ok(()))),
+					Err(reason) => TransactionOutcome::Rollback(Ok(Err(reason))),
+				})?;
+				match result {
+					Ok(()) => imported += 1,
+					Err(reason) => {
+						progress.skipped += 1;
+						Self::deposit_event(Event::<T>::BalanceSkipped(index, reason));
+					},
+				}
 			}
-			<BalancesVersion<T>>::set(StorageVersion::V2Imported);
+			progress.processed += items;
+			progress.imported += imported;
+			<BalancesProgress<T>>::put(progress);
 
-			Self::deposit_event(Event::<T>::MigratedBalances(parsed.len() as u32));
+			Self::deposit_event(Event::<T>::MigratedBalances(imported));
 
 			Ok(())
 		}
 
-		/// Migrates to `Identity` storage from another chain.
-		/// Storage: `IdentityOf`
-		/// `IdentityOf` before using, privacy level should be patched: pub(super) -> pub
-		#[pallet::weight(5_000_000)]
-		#[transactional]
+		/// Completes the balances import once every expected row has been processed.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(3, 3))]
+		pub fn finalize_balances_migration(origin: OriginFor<T>) -> DispatchResult {
+			ensure_root(origin)?;
+
+			let progress = <BalancesProgress<T>>::get().ok_or(Error::<T>::MigrationNotStarted)?;
+			ensure!(progress.processed == progress.expected, Error::<T>::MigrationIncomplete);
+			let imported = <BalancesImported<T>>::take();
+
+			<BalancesProgress<T>>::kill();
+			<BalancesVersion<T>>::set(StorageVersion::V2Imported);
+			Self::deposit_event(Event::<T>::BalancesMigrationFinalized(
+				progress.imported,
+				progress.skipped,
+			));
+			Self::deposit_event(Event::<T>::BalancesReconciled(
+				imported.free,
+				imported.reserved,
+				imported.issuance_before,
+				pallet_balances::TotalIssuance::<T>::get(),
+			));
+			Ok(())
+		}
+
+		/// Migrates a chunk of rows to `Identity` storage from another chain, reserving their
+		/// deposits. Identities which differ from their source after the import are reported
+		/// with an `IdentityMismatch` event and in `IdentityMismatches`. Rows whose deposits
+		/// cannot be reserved are skipped with an `IdentitySkipped` event.
+		///
+		/// Storage: `IdentityOf`, `SuperOf`, `SubsOf`
+		/// `IdentityOf`, `SuperOf` and `SubsOf` before using, privacy level should be patched:
+		/// pub(super) -> pub
+		#[pallet::call_index(3)]
+		#[pallet::weight(identities_chunk_weight::<T>(records.len() as u32))]
 		pub fn migrate_identities(
 			origin: OriginFor<T>,
-			data: Vec<u8>,
+			records: BoundedVec<IdentityRecordOf<T>, T::MaxChunkSize>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
@@ -153,93 +611,451 @@ pub mod pallet {
 				return Ok(())
 			}
 
-			let data_str = sp_std::str::from_utf8(&data).unwrap();
-			let parsed: Vec<Value> = serde_json::from_str(data_str).unwrap();
-
-			for i in &parsed {
-				let r = &i["identity"];
-				let f = &r["info"];
-				let acc_id = i["account_id"].as_str().unwrap();
-				let item: IdentityItem<T> = IdentityItem {
-					account_id: Self::get_account_id(acc_id),
-					registration: Registration {
-						judgements: BoundedVec::default(),
-						deposit: r["deposit"].as_u64().ok_or(0).unwrap().saturated_into::<BalanceOf<T>>(),
-						info: IdentityInfo {
-							additional: BoundedVec::default(),
-							display: Self::parse_identity_field(&f["display"]),
-							pgp_fingerprint: Option::None,
-							legal: Self::parse_identity_field(&f["legal"]),
-							web: Self::parse_identity_field(&f["web"]),
-							riot: Self::parse_identity_field(&f["riot"]),
-							email: Self::parse_identity_field(&f["email"]),
-							image: Self::parse_identity_field(&f["image"]),
-							twitter: Self::parse_identity_field(&f["twitter"]),
+			let (mut imported, mut mismatched) = (0u32, 0u32);
+			for record in records.into_inner() {
+				let who = record.who.clone();
+				// a skipped row must not leave anything behind
+				let result = with_transaction(|| match Self::import_identity(record) {
+					Ok(report) => TransactionOutcome::Commit(Ok(Ok(report))),
+					Err(reason) => TransactionOutcome::Rollback(Ok(Err(reason))),
+				})?;
+				match result {
+					Ok(report) => {
+						imported += 1;
+						if let Some(report) = report {
+							mismatched += 1;
+							<IdentityMismatches<T>>::insert(&who, &report);
+							Self::deposit_event(Event::<T>::IdentityMismatch(who, report));
 						}
-					}
-				};
-				pallet_identity::IdentityOf::<T>::insert(&item.account_id, item.registration);
+					},
+					Err(reason) => Self::deposit_event(Event::<T>::IdentitySkipped(who, reason)),
+				}
 			}
+			<IdentitiesImported<T>>::mutate(|(total, mismatches)| {
+				*total = total.saturating_add(imported);
+				*mismatches = mismatches.saturating_add(mismatched);
+			});
+
+			Self::deposit_event(Event::<T>::MigratedIdentities(imported));
 
+			Ok(())
+		}
+
+		/// Completes the identities import, further chunks are ignored.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn finalize_identities_migration(origin: OriginFor<T>) -> DispatchResult {
+			ensure_root(origin)?;
+
+			let (imported, mismatched) = <IdentitiesImported<T>>::get();
 			<IdentityVersion<T>>::set(StorageVersion::V2Imported);
-			
-			Self::deposit_event(Event::<T>::MigratedIdentities(parsed.len() as u32));
+			Self::deposit_event(Event::<T>::IdentitiesMigrationFinalized(imported, mismatched));
+			Ok(())
+		}
+
+		/// Starts an airdrop of `total` over the leaves committed to by `root`, claimable until
+		/// `deadline`.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn start_airdrop(
+			origin: OriginFor<T>,
+			root: H256,
+			total: <T as pallet_balances::Config>::Balance,
+			deadline: T::BlockNumber,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(!<Airdrop<T>>::exists(), Error::<T>::AirdropOngoing);
+			ensure!(
+				deadline > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::InvalidDeadline
+			);
+
+			<Airdrop<T>>::put(AirdropInfo { root, total, claimed: Zero::zero(), deadline });
+			Self::deposit_event(Event::<T>::AirdropStarted(root, total, deadline));
+			Ok(())
+		}
+
+		/// Claims `leaf` of the ongoing airdrop with the Merkle `proof`, crediting its amount to
+		/// `leaf.who`, vesting it with the schedule of the leaf and setting `identity` if the leaf
+		/// commits to one.
+		///
+		/// Fails if the vesting schedule cannot be created through `Locks` or if `leaf.who`
+		/// already has an identity.
+		///
+		/// Unsigned and free, anyone can submit the claim of any leaf.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(4, 5).saturating_add(Weight::from_parts(50_000_000, 0)))]
+		pub fn claim(
+			origin: OriginFor<T>,
+			leaf: ClaimLeafOf<T>,
+			identity: Option<Box<IdentityInfo<T::MaxAdditionalFields>>>,
+			proof: BoundedVec<H256, T::MaxProofLength>,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+			let mut airdrop = Self::check_claim(&leaf, &identity, &proof)?;
+
+			airdrop.claimed = airdrop.claimed.saturating_add(leaf.amount);
+			<Airdrop<T>>::put(&airdrop);
+			<Claimed<T>>::insert(airdrop.root, &leaf.who, true);
 
+			let _ = <pallet_balances::Pallet<T> as Currency<T::AccountId>>::deposit_creating(
+				&leaf.who,
+				leaf.amount,
+			);
+			if let Some(vesting) = &leaf.vesting {
+				ensure!(
+					T::Locks::import_vesting(
+						&leaf.who,
+						vesting.locked,
+						vesting.per_block,
+						vesting.starting_block,
+					),
+					Error::<T>::InvalidVesting
+				);
+			}
+			if let Some(info) = identity {
+				let registration = Registration {
+					judgements: BoundedVec::default(),
+					deposit: Zero::zero(),
+					info: *info,
+				};
+				pallet_identity::IdentityOf::<T>::insert(&leaf.who, registration);
+			}
+
+			Self::deposit_event(Event::<T>::AirdropClaimed(leaf.who, leaf.amount));
 			Ok(())
+		}
 
+		/// Queues all keys under `prefix` for removal, which happens over the following blocks.
+		///
+		/// Nothing prevents queueing the storage of a pallet still in use, the prefix has to be
+		/// checked before submitting this.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn register_cleanup(origin: OriginFor<T>, prefix: CleanupPrefix) -> DispatchResult {
+			T::CleanupOrigin::ensure_origin(origin)?;
+			Self::queue_cleanup(prefix)
 		}
 	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			if let Call::claim { leaf, identity, proof } = call {
+				Self::check_claim(leaf, identity, proof).map_err(|e| match e {
+					Error::<T>::InvalidProof | Error::<T>::IdentityMismatch =>
+						InvalidTransaction::BadProof,
+					Error::<T>::InvalidVesting => InvalidTransaction::Call,
+					_ => InvalidTransaction::Stale,
+				})?;
+
+				ValidTransaction::with_tag_prefix("MigrationClaim")
+					.and_provides(&leaf.who)
+					.longevity(64)
+					.propagate(true)
+					.build()
+			} else {
+				InvalidTransaction::Call.into()
+			}
+		}
+	}
+}
+
+/// Weight of a balances chunk of `items` rows: each row reads and writes its account, the total
+/// issuance, the imported totals and its named reserves and locks, plus the progress read and
+/// written once.
+fn balances_chunk_weight<T: Config>(items: u32) -> Weight {
+	let items = items as u64;
+	T::DbWeight::get()
+		.reads_writes(1 + 5 * items, 1 + 5 * items)
+		.saturating_add(Weight::from_parts(100_000_000, 0))
+		.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(items))
+}
+
+/// Weight of an identities chunk of `items` rows: the version and counts read once, and per row
+/// the identity, the account, the report, the maximum number of sub accounts and a pending request
+/// per registrar.
+fn identities_chunk_weight<T: Config>(items: u32) -> Weight {
+	let items = items as u64;
+	let subs = <T as pallet_identity::Config>::MaxSubAccounts::get() as u64;
+	let requests = <T as pallet_identity::Config>::MaxRegistrars::get() as u64;
+	T::DbWeight::get()
+		.reads_writes(2 + (2 + subs) * items, 1 + (4 + subs + requests) * items)
+		.saturating_add(Weight::from_parts(10_000_000, 0))
+		.saturating_add(Weight::from_parts(20_000_000, 0).saturating_mul(items))
 }
 
 impl<T: Config> Pallet<T> {
-	// Balances helpers
-	fn get_balance(item: &AccountItem) -> T::Balance {
-		let free: u128 = item.free_balance.parse().unwrap();
-		let reserved: u128 = item.reserved_balance.parse().unwrap();
-		(free + reserved).saturated_into()
-	}
-	
-	fn get_account(item: &AccountItem) -> T::AccountId {
-		let mut array = [0; 32];
-		let mut decoded = bs58::decode(&item.account_id).into_vec().unwrap();
-		let cut_address_vec:Vec<u8> = decoded.drain(1..33).collect();
-		let bytes = &cut_address_vec[..array.len()];
-		array.copy_from_slice(bytes);
-		let account32: AccountId32 = array.into();
-		let mut to32 = AccountId32::as_ref(&account32);
-		let to_address: T::AccountId = T::AccountId::decode(&mut to32).unwrap_or_default();
-		to_address
+	/// Number of keys left under every queued prefix, counting at most `limit` per prefix.
+	pub fn cleanup_remaining(limit: u32) -> Vec<(Vec<u8>, u32)> {
+		migration::remaining_keys::<T>(limit)
+	}
+
+	// Cleanup helpers
+	pub(crate) fn queue_cleanup(prefix: CleanupPrefix) -> DispatchResult {
+		ensure!(prefix.len() >= 16, Error::<T>::PrefixTooShort);
+		<CleanupQueue<T>>::try_mutate(|queue| -> Result<(), Error<T>> {
+			let overlaps =
+				|queued: &CleanupPrefix| queued.starts_with(&prefix) || prefix.starts_with(queued);
+			ensure!(!queue.iter().any(overlaps), Error::<T>::CleanupAlreadyRegistered);
+			queue.try_push(prefix.clone()).map_err(|_| Error::<T>::TooManyCleanups)
+		})?;
+		Self::deposit_event(Event::<T>::CleanupRegistered(prefix.into_inner()));
+		Ok(())
+	}
+
+	// Airdrop helpers
+	fn check_claim(
+		leaf: &ClaimLeafOf<T>,
+		identity: &Option<Box<IdentityInfo<T::MaxAdditionalFields>>>,
+		proof: &[H256],
+	) -> Result<AirdropInfoOf<T>, Error<T>> {
+		let airdrop = <Airdrop<T>>::get().ok_or(Error::<T>::NoAirdrop)?;
+		ensure!(
+			frame_system::Pallet::<T>::block_number() < airdrop.deadline,
+			Error::<T>::InvalidDeadline
+		);
+		ensure!(!<Claimed<T>>::get(airdrop.root, &leaf.who), Error::<T>::AlreadyClaimed);
+		ensure!(
+			merkle::verify(&airdrop.root, merkle::leaf_hash(leaf), proof),
+			Error::<T>::InvalidProof
+		);
+		let identity_hash = identity
+			.as_ref()
+			.map_or_else(H256::zero, |info| info.using_encoded(blake2_256).into());
+		ensure!(identity_hash == leaf.identity_hash, Error::<T>::IdentityMismatch);
+		if identity.is_some() {
+			ensure!(
+				!pallet_identity::IdentityOf::<T>::contains_key(&leaf.who),
+				Error::<T>::IdentityExists
+			);
+		}
+		if let Some(vesting) = &leaf.vesting {
+			ensure!(
+				vesting.locked <= leaf.amount &&
+					!vesting.per_block.is_zero() &&
+					T::Locks::can_import_vesting(
+						&leaf.who,
+						vesting.locked,
+						vesting.per_block,
+						vesting.starting_block,
+					),
+				Error::<T>::InvalidVesting
+			);
+		}
+		ensure!(
+			airdrop.claimed.saturating_add(leaf.amount) <= airdrop.total,
+			Error::<T>::ClaimExceedsTotal
+		);
+		Ok(airdrop)
+	}
+
+	fn expire_airdrop(airdrop: AirdropInfoOf<T>) {
+		let unclaimed = airdrop.total.saturating_sub(airdrop.claimed);
+		let _ = <pallet_balances::Pallet<T> as Currency<T::AccountId>>::deposit_creating(
+			&T::TreasuryAccount::get(),
+			unclaimed,
+		);
+		<Airdrop<T>>::kill();
+		Self::deposit_event(Event::<T>::AirdropExpired(unclaimed));
 	}
 
 	// Identity helpers
-	pub fn decode_hex(s: &str) -> Result<Vec<u8>, ParseIntError> {
-		(0..s.len())
-			.step_by(2)
-			.map(|i| u8::from_str_radix(&s[i..i + 2], 16))
-			.collect()
-	}
-	
-	pub fn get_account_id(s: &str) -> T::AccountId {
-		let mut array = [0; 32];
-		let mut decoded = bs58::decode(s).into_vec().unwrap();
-		let cut_address_vec:Vec<u8> = decoded.drain(1..33).collect();
-		let bytes = &cut_address_vec[..array.len()];
-		array.copy_from_slice(bytes);
-		let account32: AccountId32 = array.into();
-		let mut to32 = AccountId32::as_ref(&account32);
-		let to_address: T::AccountId = T::AccountId::decode(&mut to32).unwrap_or_default();
-		to_address
-	}
-	
-	pub fn parse_identity_field(f: &Value) -> Data {
-		match &f["raw"] {
-			Value::String(s) => {
-				Data::Raw(Self::decode_hex(&s[2..]).unwrap().try_into().unwrap())
-			},
-			_ => {
-				Data::None
+	/// Imports `record`, `Some` report if the imported identity differs from the source.
+	fn import_identity(
+		record: IdentityRecordOf<T>,
+	) -> Result<Option<IdentityReport<BalanceOf<T>>>, SkipReason> {
+		let IdentityRecord { who, deposit, info, judgements, subs, subs_deposit } = record;
+		if pallet_identity::IdentityOf::<T>::contains_key(&who) {
+			return Err(SkipReason::AccountExists)
+		}
+
+		let fee = |judgement: &Judgement<BalanceOf<T>>| match judgement {
+			Judgement::FeePaid(fee) => *fee,
+			_ => Zero::zero(),
+		};
+		let mut report = IdentityReport {
+			expected_deposit: judgements
+				.iter()
+				.fold(deposit.saturating_add(subs_deposit), |sum, (_, j)| {
+					sum.saturating_add(fee(j))
+				}),
+			..Default::default()
+		};
+
+		// judgements have to be ordered by the local registrar index, one per registrar
+		let mut mapped: Vec<(RegistrarIndex, Judgement<BalanceOf<T>>)> = Vec::new();
+		for (index, judgement) in judgements {
+			match T::RegistrarIndexOf::convert(index) {
+				Some(local) => match mapped.binary_search_by_key(&local, |(i, _)| *i) {
+					Ok(_) => report.dropped_judgements += 1,
+					Err(position) => mapped.insert(position, (local, judgement)),
+				},
+				None => report.dropped_judgements += 1,
+			}
+		}
+
+		// everything is reserved in the native currency, so pending requests to registrars taking
+		// their fee in another currency are dropped
+		let native = <T as pallet_identity::Config>::NativeCurrencyId::get();
+		let registrars = pallet_identity::Pallet::<T>::registrars();
+		let before = mapped.len();
+		mapped.retain(|(index, j)| {
+			!matches!(j, Judgement::FeePaid(_)) ||
+				registrars
+					.get(*index as usize)
+					.and_then(Option::as_ref)
+					.map_or(true, |r| r.fee_currency == native)
+		});
+		report.dropped_judgements += (before - mapped.len()) as u32;
+
+		let total_subs = subs.len() as u32;
+		let mut kept_subs: Vec<(T::AccountId, pallet_identity::Data)> = Vec::new();
+		for (sub, name) in subs {
+			let taken = sub == who ||
+				pallet_identity::SuperOf::<T>::contains_key(&sub) ||
+				kept_subs.iter().any(|(kept, _)| *kept == sub);
+			if !taken {
+				kept_subs.push((sub, name));
+			}
+		}
+		report.dropped_subs = total_subs - kept_subs.len() as u32;
+
+		let subs_deposit = if report.dropped_subs == 0 {
+			subs_deposit
+		} else {
+			// the share of the sub accounts which are kept
+			subs_deposit / BalanceOf::<T>::from(total_subs) *
+				BalanceOf::<T>::from(kept_subs.len() as u32)
+		};
+
+		// everything is checked before the first write, a skipped row leaves nothing behind
+		let to_reserve = mapped
+			.iter()
+			.fold(deposit.saturating_add(subs_deposit), |sum, (_, j)| sum.saturating_add(fee(j)));
+		let judgements: BoundedVec<_, _> =
+			mapped.try_into().map_err(|_| SkipReason::TooManyJudgements)?;
+		let accounts: BoundedVec<_, _> = kept_subs
+			.iter()
+			.map(|(sub, _)| sub.clone())
+			.collect::<Vec<_>>()
+			.try_into()
+			.map_err(|_| SkipReason::TooManySubAccounts)?;
+		if !<T as pallet_identity::Config>::Currency::can_reserve(native, &who, to_reserve) {
+			return Err(SkipReason::InsufficientBalance)
+		}
+
+		<T as pallet_identity::Config>::Currency::reserve(native, &who, to_reserve)
+			.map_err(|_| SkipReason::InsufficientBalance)?;
+		report.reserved_deposit = to_reserve;
+		pallet_identity::DepositCurrencyOf::<T>::remove(&who);
+		for (index, judgement) in judgements.iter() {
+			if let Judgement::FeePaid(_) = judgement {
+				pallet_identity::PendingRequests::<T>::insert(index, &who, ());
+			}
+		}
+		pallet_identity::IdentityOf::<T>::insert(&who, Registration { judgements, deposit, info });
+		if !accounts.is_empty() {
+			for (sub, name) in kept_subs {
+				pallet_identity::SuperOf::<T>::insert(&sub, (who.clone(), name));
 			}
+			pallet_identity::SubsOf::<T>::insert(&who, (subs_deposit, accounts));
 		}
+
+		let differs = report.expected_deposit != report.reserved_deposit ||
+			report.dropped_judgements > 0 ||
+			report.dropped_subs > 0;
+		Ok(if differs { Some(report) } else { None })
+	}
+
+	// Balances helpers
+	fn import_balance(record: &BalanceRecordOf<T>) -> Result<(), SkipReason> {
+		let BalanceRecord { who, free, reserved, .. } = record;
+		let total = free.checked_add(reserved).ok_or(SkipReason::Overflow)?;
+
+		let existing = <T as pallet_balances::Config>::AccountStore::get(who);
+		if existing != Default::default() {
+			return Err(SkipReason::AccountExists)
+		}
+		let issuance = pallet_balances::TotalIssuance::<T>::get()
+			.checked_add(&total)
+			.ok_or(SkipReason::Overflow)?;
+		let mut imported = <BalancesImported<T>>::get();
+		imported.free = imported.free.checked_add(free).ok_or(SkipReason::Overflow)?;
+		imported.reserved = imported.reserved.checked_add(reserved).ok_or(SkipReason::Overflow)?;
+
+		// everything is credited as free first, then moved to the reserves
+		<T as pallet_balances::Config>::AccountStore::insert(
+			who,
+			AccountData { free: total, ..Default::default() },
+		)
+		.map_err(|_| SkipReason::InvalidAccount)?;
+		pallet_balances::TotalIssuance::<T>::put(issuance);
+		<BalancesImported<T>>::put(imported);
+
+		Self::import_reserves(who, *reserved, &record.named_reserves)?;
+		Self::import_locks(who, &record.locks, &record.vesting);
+		Ok(())
 	}
 
+	fn import_reserves(
+		who: &T::AccountId,
+		reserved: T::Balance,
+		named_reserves: &[NamedReserveRecord<T::Balance>],
+	) -> Result<(), SkipReason> {
+		let mut anonymous = reserved;
+		for named in named_reserves {
+			anonymous = anonymous.checked_sub(&named.amount).ok_or(SkipReason::InvalidReserve)?;
+			match T::ReserveIdOf::convert(named.id) {
+				Some(id) =>
+					<pallet_balances::Pallet<T> as NamedReservableCurrency<T::AccountId>>::reserve_named(
+						&id,
+						who,
+						named.amount,
+					)
+					.map_err(|_| SkipReason::InvalidReserve)?,
+				None => <pallet_balances::Pallet<T> as ReservableCurrency<T::AccountId>>::reserve(
+					who,
+					named.amount,
+				)
+				.map_err(|_| SkipReason::InvalidReserve)?,
+			}
+		}
+		if !anonymous.is_zero() {
+			<pallet_balances::Pallet<T> as ReservableCurrency<T::AccountId>>::reserve(
+				who, anonymous,
+			)
+			.map_err(|_| SkipReason::InvalidReserve)?;
+		}
+		Ok(())
+	}
+
+	fn import_locks(
+		who: &T::AccountId,
+		locks: &[LockRecord<T::Balance>],
+		vesting: &Option<VestingRecord<T::Balance>>,
+	) {
+		for lock in locks {
+			let reasons = match lock.reasons {
+				Reasons::All => WithdrawReasons::all(),
+				Reasons::Fee => WithdrawReasons::TRANSACTION_PAYMENT,
+				Reasons::Misc => WithdrawReasons::except(WithdrawReasons::TRANSACTION_PAYMENT),
+			};
+			if !T::Locks::import_lock(who, lock.id, lock.amount, reasons) {
+				Self::deposit_event(Event::<T>::LockDropped(who.clone(), lock.id));
+			}
+		}
+		if let Some(vesting) = vesting {
+			if !T::Locks::import_vesting(
+				who,
+				vesting.locked,
+				vesting.per_block,
+				vesting.starting_block,
+			) {
+				Self::deposit_event(Event::<T>::VestingDropped(who.clone()));
+			}
+		}
+	}
 }
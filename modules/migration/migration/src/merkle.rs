@@ -0,0 +1,84 @@
+//! Binary Merkle tree over blake2-256 hashes, as used by the airdrop claims.
+//!
+//! Pairs are hashed in sorted order, so a proof is just the list of siblings from the leaf up to
+//! the root. The last node of a level with an odd number of nodes is moved up unchanged and has no
+//! sibling in the proof.
+//!
+//! Leaves and inner nodes are hashed with different prefixes, so an inner node can never be
+//! passed off as a leaf.
+
+use codec::Encode;
+use sp_core::H256;
+use sp_io::hashing::blake2_256;
+#[cfg(feature = "std")]
+use sp_std::vec::Vec;
+
+/// Prefix of the hashed leaves.
+const LEAF_PREFIX: u8 = 0;
+/// Prefix of the hashed pairs of nodes.
+const NODE_PREFIX: u8 = 1;
+
+/// Hash of a leaf, the blake2-256 hash of its SCALE encoding prefixed with `LEAF_PREFIX`.
+pub fn leaf_hash<Leaf: Encode>(leaf: &Leaf) -> H256 {
+	(LEAF_PREFIX, leaf).using_encoded(blake2_256).into()
+}
+
+/// Hash of two nodes, the blake2-256 hash of both in sorted order prefixed with `NODE_PREFIX`.
+pub(crate) fn pair_hash(a: &H256, b: &H256) -> H256 {
+	let (first, second) = if a <= b { (a, b) } else { (b, a) };
+	(NODE_PREFIX, first, second).using_encoded(blake2_256).into()
+}
+
+/// Whether `proof` leads from `leaf` to `root`.
+pub fn verify(root: &H256, leaf: H256, proof: &[H256]) -> bool {
+	proof.iter().fold(leaf, |node, sibling| pair_hash(&node, sibling)) == *root
+}
+
+/// All levels of a tree, from the leaves up to the root.
+#[cfg(feature = "std")]
+pub struct MerkleTree {
+	levels: Vec<Vec<H256>>,
+}
+
+#[cfg(feature = "std")]
+impl MerkleTree {
+	/// Builds the tree over `leaves`, `None` if there are none.
+	pub fn new(leaves: Vec<H256>) -> Option<Self> {
+		if leaves.is_empty() {
+			return None
+		}
+		let mut levels = vec![leaves];
+		while let Some(level) = levels.last().filter(|level| level.len() > 1) {
+			let next = level
+				.chunks(2)
+				.map(|pair| match pair {
+					[a, b] => pair_hash(a, b),
+					[last] => *last,
+					_ => unreachable!("chunks of two are never empty"),
+				})
+				.collect();
+			levels.push(next);
+		}
+		Some(Self { levels })
+	}
+
+	pub fn root(&self) -> H256 {
+		self.levels[self.levels.len() - 1][0]
+	}
+
+	/// Siblings of the leaf at `index` from the bottom up, `None` if there is no such leaf.
+	pub fn proof(&self, index: usize) -> Option<Vec<H256>> {
+		if index >= self.levels[0].len() {
+			return None
+		}
+		let mut index = index;
+		let mut proof = Vec::new();
+		for level in &self.levels[..self.levels.len() - 1] {
+			if let Some(sibling) = level.get(index ^ 1) {
+				proof.push(*sibling);
+			}
+			index /= 2;
+		}
+		Some(proof)
+	}
+}
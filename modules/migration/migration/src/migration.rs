@@ -1,36 +1,210 @@
-use frame_support::{
-	weights::Weight,
-	storage::unhashed::kill_prefix,
-	traits::Get
+use super::{
+	BalancesImported, BalancesProgress, CleanupCursor, CleanupPrefix, CleanupQueue, Config, Event,
+	MigrationVersion, Pallet, StorageVersion,
 };
-use sp_io::{
-	KillStorageResult,
-	hashing::twox_128
-};
-use super::{Config, MigrationVersion, StorageVersion};
+use frame_support::{storage::unhashed, traits::Get, weights::Weight};
+use sp_io::hashing::twox_128;
+use sp_std::{convert::TryInto, vec::Vec};
+
+/// Pallets removed from the runtime, their storage is cleaned up after the upgrade.
+const REMOVED_PALLETS: [&str; 10] = [
+	"ZeroSense",
+	"Sense48",
+	"GameDaoControl",
+	"Control50",
+	"GameDaoGovernance",
+	"Signal50",
+	"GameDaoCrowdfunding",
+	"Flow50",
+	"GameDaoTangram",
+	"Tangram50",
+];
 
+/// Queues the storage of the removed pallets for cleanup, which happens over the following
+/// blocks in `on_idle`.
 pub fn migrate<T: Config>() -> Weight {
 	if <MigrationVersion<T>>::get() == StorageVersion::V2Imported {
-		return 0 as Weight;
-	}
-	let names = [
-		"ZeroSense", "Sense48",
-		"GameDaoControl", "Control50",
-		"GameDaoGovernance", "Signal50",
-		"GameDaoCrowdfunding", "Flow50",
-		"GameDaoTangram", "Tangram50"
-	];
-	let mut total_removed: u32 = 0;
-	for name in names {
-		match kill_prefix(&twox_128(&name.as_bytes()), None) {
-			KillStorageResult::AllRemoved(n) => {
-				total_removed += n;
-			},
-			KillStorageResult::SomeRemaining(n) => {
-				total_removed += n;
-			}
+		return T::DbWeight::get().reads(1)
+	}
+	for name in REMOVED_PALLETS {
+		let prefix: CleanupPrefix = match twox_128(name.as_bytes()).to_vec().try_into() {
+			Ok(prefix) => prefix,
+			Err(_) => continue,
+		};
+		if let Err(e) = Pallet::<T>::queue_cleanup(prefix) {
+			log::warn!(target: "runtime::migration", "cannot clean up {}: {:?}", name, e);
 		}
 	}
 	<MigrationVersion<T>>::set(StorageVersion::V2Imported);
-	T::DbWeight::get().reads_writes(total_removed as Weight, total_removed as Weight)
+	T::DbWeight::get().reads_writes(2, 2)
+}
+
+/// Weight of a cleanup without any removed key, and of every removed key.
+pub fn cleanup_weights<T: Config>() -> (Weight, Weight) {
+	(T::DbWeight::get().reads_writes(2, 2), T::DbWeight::get().reads_writes(1, 1))
+}
+
+/// Removes up to `limit` keys under the queued prefixes, in queue order, and returns the number of
+/// removed keys.
+///
+/// Continues after the last removed key stored in `CleanupCursor`, prefixes are dequeued once no
+/// key is left under them.
+pub fn cleanup<T: Config>(limit: u32) -> u32 {
+	let mut queue = <CleanupQueue<T>>::get();
+	if queue.is_empty() {
+		return 0
+	}
+	let mut cursor = <CleanupCursor<T>>::take();
+	let mut removed = 0u32;
+
+	while let Some(prefix) = queue.first().cloned() {
+		let mut key = cursor.take().unwrap_or_else(|| prefix.to_vec());
+		let mut removed_of_prefix = 0u32;
+		let done = loop {
+			match sp_io::storage::next_key(&key).filter(|next| next.starts_with(&prefix)) {
+				None => break true,
+				Some(_) if removed == limit => break false,
+				Some(next) => {
+					unhashed::kill(&next);
+					removed += 1;
+					removed_of_prefix += 1;
+					key = next;
+				},
+			}
+		};
+
+		if removed_of_prefix > 0 {
+			Pallet::<T>::deposit_event(Event::<T>::CleanupProgress(
+				prefix.to_vec(),
+				removed_of_prefix,
+			));
+		}
+		if !done {
+			cursor = Some(key);
+			break
+		}
+		queue.remove(0);
+		Pallet::<T>::deposit_event(Event::<T>::CleanupCompleted(prefix.to_vec()));
+	}
+
+	if queue.is_empty() {
+		<CleanupQueue<T>>::kill();
+	} else {
+		<CleanupQueue<T>>::put(queue);
+	}
+	if let Some(cursor) = cursor {
+		<CleanupCursor<T>>::put(cursor);
+	}
+	removed
+}
+
+/// Number of keys left under every queued prefix, counting at most `limit` per prefix.
+pub fn remaining_keys<T: Config>(limit: u32) -> Vec<(Vec<u8>, u32)> {
+	<CleanupQueue<T>>::get()
+		.into_iter()
+		.map(|prefix| {
+			let mut key = prefix.to_vec();
+			let mut count = 0u32;
+			while count < limit {
+				match sp_io::storage::next_key(&key).filter(|next| next.starts_with(&prefix)) {
+					Some(next) => {
+						count += 1;
+						key = next;
+					},
+					None => break,
+				}
+			}
+			(prefix.into_inner(), count)
+		})
+		.collect()
+}
+
+/// Checks the invariants of the imported state:
+/// - while a balances import is ongoing, the issuance is the issuance from before the import plus
+///   the imported balances, which assumes nothing else mints or burns during the import,
+/// - the issuance equals the sum of all account balances,
+/// - the storage of every removed pallet is either empty or queued for cleanup, and is empty once
+///   the queue has been worked off,
+/// - every identity has its deposit and the deposit of its sub accounts reserved in its deposit
+///   currency, and its pending judgement fees in the fee currencies of their registrars.
+#[cfg(feature = "try-runtime")]
+pub fn post_upgrade<T: Config>() -> Result<(), &'static str> {
+	use frame_support::{storage::with_transaction, traits::Currency};
+	use orml_traits::MultiReservableCurrency;
+	use pallet_identity::Judgement;
+	use sp_runtime::{
+		traits::{Saturating, Zero},
+		TransactionOutcome,
+	};
+
+	type Balances<T> = pallet_balances::Pallet<T>;
+
+	let issuance = <Balances<T> as Currency<T::AccountId>>::total_issuance();
+	if <BalancesProgress<T>>::exists() {
+		let imported = <BalancesImported<T>>::get();
+		let expected = imported
+			.issuance_before
+			.saturating_add(imported.free)
+			.saturating_add(imported.reserved);
+		frame_support::ensure!(
+			issuance == expected,
+			"the issuance differs from the issuance before the import plus the imported balances"
+		);
+	}
+	let zero: <T as pallet_balances::Config>::Balance = Zero::zero();
+	let total = frame_system::Account::<T>::iter_keys().fold(zero, |sum, who| {
+		sum.saturating_add(<Balances<T> as Currency<T::AccountId>>::total_balance(&who))
+	});
+	frame_support::ensure!(total == issuance, "the issuance differs from the sum of all balances");
+
+	let queue = <CleanupQueue<T>>::get();
+	let is_empty = |prefix: &[u8]| {
+		sp_io::storage::next_key(prefix).filter(|n| n.starts_with(prefix)).is_none()
+	};
+	for name in REMOVED_PALLETS {
+		let prefix = twox_128(name.as_bytes());
+		frame_support::ensure!(
+			is_empty(&prefix) || queue.iter().any(|queued| prefix.starts_with(queued)),
+			"storage of a removed pallet is neither empty nor queued for cleanup"
+		);
+	}
+	let cleaned_up = with_transaction(|| {
+		cleanup::<T>(u32::MAX);
+		let cleaned_up = <CleanupQueue<T>>::get().is_empty() &&
+			REMOVED_PALLETS.iter().all(|name| is_empty(&twox_128(name.as_bytes())));
+		TransactionOutcome::Rollback(Ok::<_, sp_runtime::DispatchError>(cleaned_up))
+	})
+	.map_err(|_| "the cleanup exceeds the transactional layers")?;
+	frame_support::ensure!(cleaned_up, "the cleanup leaves keys behind");
+
+	let native = <T as pallet_identity::Config>::NativeCurrencyId::get();
+	let registrars = pallet_identity::Pallet::<T>::registrars();
+	for (who, registration) in pallet_identity::IdentityOf::<T>::iter() {
+		let (subs_deposit, _) = pallet_identity::SubsOf::<T>::get(&who);
+		let deposit_currency = pallet_identity::Pallet::<T>::deposit_currency(&who);
+		// Expected reserved amount per currency, fees of removed registrars are in the native one.
+		let mut expected =
+			sp_std::vec![(deposit_currency, registration.deposit.saturating_add(subs_deposit))];
+		for (index, judgement) in registration.judgements.iter() {
+			if let Judgement::FeePaid(fee) = judgement {
+				let currency = registrars
+					.get(*index as usize)
+					.and_then(Option::as_ref)
+					.map_or(native, |registrar| registrar.fee_currency);
+				match expected.iter_mut().find(|(c, _)| *c == currency) {
+					Some((_, sum)) => *sum = sum.saturating_add(*fee),
+					None => expected.push((currency, *fee)),
+				}
+			}
+		}
+		for (currency, amount) in expected {
+			let reserved =
+				<T as pallet_identity::Config>::Currency::reserved_balance(currency, &who);
+			frame_support::ensure!(
+				reserved >= amount,
+				"an identity has less reserved than its deposits in one of its currencies"
+			);
+		}
+	}
+	Ok(())
 }
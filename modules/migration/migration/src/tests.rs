@@ -0,0 +1,746 @@
+// Tests for the migration pallet
+
+use super::*;
+use crate as module_migration;
+
+use frame_support::{
+	assert_noop, assert_ok, parameter_types,
+	storage::unhashed,
+	traits::{ConstU32, ConstU64, Everything, Hooks},
+	unsigned::ValidateUnsigned,
+	weights::Weight,
+};
+use frame_system::EnsureRoot;
+use merkle::MerkleTree;
+use orml_traits::parameter_type_with_key;
+use pallet_identity::{Data, Judgement};
+use sp_runtime::{
+	testing::{Header, TestSignature, UintAuthorityId},
+	traits::{BadOrigin, BlakeTwo256, ConvertInto, IdentityLookup},
+	transaction_validity::{InvalidTransaction, TransactionSource, ValidTransaction},
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Tokens: orml_tokens::{Pallet, Storage, Config<T>, Event<T>},
+		Currencies: orml_currencies::{Pallet, Call},
+		Vesting: pallet_vesting::{Pallet, Call, Storage, Event<T>},
+		Identity: pallet_identity::{Pallet, Call, Storage, Event<T>},
+		Migration: module_migration::{Pallet, Call, Storage, Event<T>, ValidateUnsigned},
+	}
+);
+
+pub const NATIVE: u32 = 0;
+pub const TREASURY: u64 = 99;
+/// Named reserve of the source chain which has a counterpart in the mock.
+pub const MAPPED_RESERVE: [u8; 8] = *b"mapped  ";
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type RuntimeCall = RuntimeCall;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type Balance = u64;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type MaxLocks = ConstU32<2>;
+	type MaxReserves = ConstU32<2>;
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub UnvestedFundsAllowedWithdrawReasons: WithdrawReasons =
+		WithdrawReasons::except(WithdrawReasons::TRANSFER | WithdrawReasons::RESERVE);
+}
+impl pallet_vesting::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type BlockNumberToBalance = ConvertInto;
+	type MinVestedTransfer = ConstU64<1>;
+	type WeightInfo = ();
+	type UnvestedFundsAllowedWithdrawReasons = UnvestedFundsAllowedWithdrawReasons;
+	const MAX_VESTING_SCHEDULES: u32 = 3;
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: u32| -> u64 {
+		1
+	};
+}
+impl orml_tokens::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = u64;
+	type Amount = i64;
+	type CurrencyId = u32;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type CurrencyHooks = ();
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type DustRemovalWhitelist = Everything;
+}
+
+parameter_types! {
+	pub const GetNativeCurrencyId: u32 = NATIVE;
+	pub const TreasuryAccount: u64 = TREASURY;
+}
+impl orml_currencies::Config for Test {
+	type MultiCurrency = Tokens;
+	type NativeCurrency = orml_currencies::BasicCurrencyAdapter<Test, Balances, i64, u64>;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type WeightInfo = ();
+}
+
+impl pallet_identity::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Currencies;
+	type NativeCurrencyId = GetNativeCurrencyId;
+	type DepositRate = ();
+	type DepositCurrencyOrigin = EnsureRoot<u64>;
+	type Slashed = TreasuryAccount;
+	type BasicDeposit = ConstU64<10>;
+	type FieldDeposit = ConstU64<10>;
+	type SubAccountDeposit = ConstU64<10>;
+	type MaxSubAccounts = ConstU32<2>;
+	type MaxAdditionalFields = ConstU32<2>;
+	type MaxRegistrars = ConstU32<20>;
+	type RegistrarOrigin = EnsureRoot<u64>;
+	type ForceOrigin = EnsureRoot<u64>;
+	type OffchainSignature = TestSignature;
+	type SigningPublicKey = UintAuthorityId;
+	type UsernameAuthorityOrigin = EnsureRoot<u64>;
+	type UsernameDeposit = ConstU64<5>;
+	type UsernameDuration = ConstU64<100>;
+	type MaxUsernameLength = ConstU32<16>;
+	type MaxSuffixLength = ConstU32<8>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = MockSigner;
+	type WeightInfo = ();
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct MockSigner;
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_identity::BenchmarkHelper<UintAuthorityId, u64, TestSignature> for MockSigner {
+	fn signer() -> (UintAuthorityId, u64) {
+		(UintAuthorityId(1_000), 1_000)
+	}
+
+	fn sign(signer: &UintAuthorityId, message: &[u8]) -> TestSignature {
+		TestSignature(signer.0, message.to_vec())
+	}
+}
+
+/// Maps `MAPPED_RESERVE` to itself, every other named reserve is imported as anonymous reserve.
+pub struct MockReserveIds;
+impl Convert<[u8; 8], Option<[u8; 8]>> for MockReserveIds {
+	fn convert(id: [u8; 8]) -> Option<[u8; 8]> {
+		(id == MAPPED_RESERVE).then(|| id)
+	}
+}
+
+impl module_migration::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type MaxChunkSize = ConstU32<4>;
+	type ReserveIdOf = MockReserveIds;
+	type Locks = BalancesVesting<Test, Vesting>;
+	type RegistrarIndexOf = ConvertInto;
+	type TreasuryAccount = TreasuryAccount;
+	type MaxProofLength = ConstU32<8>;
+	type CleanupOrigin = EnsureRoot<u64>;
+	type MaxCleanups = ConstU32<16>;
+	type CleanupBatchSize = ConstU32<3>;
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(1, 10), (2, 10)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// A row of `who` without named reserves, locks or vesting.
+fn record(who: u64, free: u64, reserved: u64) -> BalanceRecordOf<Test> {
+	BalanceRecord {
+		who,
+		free,
+		reserved,
+		named_reserves: Default::default(),
+		locks: Default::default(),
+		vesting: None,
+	}
+}
+
+fn chunk(records: Vec<BalanceRecordOf<Test>>) -> BoundedVec<BalanceRecordOf<Test>, ConstU32<4>> {
+	records.try_into().unwrap()
+}
+
+fn progress() -> ImportProgress {
+	<BalancesProgress<Test>>::get().unwrap()
+}
+
+#[test]
+fn balances_should_be_imported_in_chunks_across_blocks() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Migration::migrate_balances(RuntimeOrigin::root(), 0, chunk(vec![record(10, 100, 0)])),
+			Error::<Test>::MigrationNotStarted
+		);
+		assert_noop!(Migration::begin_balances_migration(RuntimeOrigin::signed(1), 3), BadOrigin);
+		assert_ok!(Migration::begin_balances_migration(RuntimeOrigin::root(), 3));
+		System::assert_last_event(Event::BalancesMigrationStarted(3).into());
+		assert_noop!(
+			Migration::begin_balances_migration(RuntimeOrigin::root(), 3),
+			Error::<Test>::MigrationOngoing
+		);
+
+		assert_ok!(Migration::migrate_balances(
+			RuntimeOrigin::root(),
+			0,
+			chunk(vec![record(10, 100, 0), record(11, 50, 0)])
+		));
+		System::assert_last_event(Event::MigratedBalances(2).into());
+		assert_eq!(
+			progress(),
+			ImportProgress { expected: 3, processed: 2, imported: 2, skipped: 0 }
+		);
+
+		// the next chunk is submitted in a later block, the progress is kept in storage
+		System::set_block_number(2);
+		assert_noop!(
+			Migration::migrate_balances(RuntimeOrigin::root(), 0, chunk(vec![record(10, 100, 0)])),
+			Error::<Test>::InvalidCursor
+		);
+		assert_noop!(
+			Migration::migrate_balances(
+				RuntimeOrigin::root(),
+				2,
+				chunk(vec![record(12, 5, 0), record(13, 5, 0)])
+			),
+			Error::<Test>::TooManyItems
+		);
+		assert_noop!(
+			Migration::finalize_balances_migration(RuntimeOrigin::root()),
+			Error::<Test>::MigrationIncomplete
+		);
+
+		assert_ok!(Migration::migrate_balances(
+			RuntimeOrigin::root(),
+			2,
+			chunk(vec![record(12, 5, 0)])
+		));
+		assert_eq!(
+			progress(),
+			ImportProgress { expected: 3, processed: 3, imported: 3, skipped: 0 }
+		);
+
+		System::set_block_number(3);
+		assert_ok!(Migration::finalize_balances_migration(RuntimeOrigin::root()));
+		System::assert_has_event(Event::BalancesMigrationFinalized(3, 0).into());
+		System::assert_last_event(Event::BalancesReconciled(155, 0, 20, 175).into());
+		assert_eq!(<BalancesProgress<Test>>::get(), None);
+		assert_eq!(Balances::free_balance(10), 100);
+		assert_eq!(Balances::free_balance(11), 50);
+		assert_eq!(Balances::free_balance(12), 5);
+		assert_eq!(Balances::total_issuance(), 175);
+
+		assert_noop!(
+			Migration::begin_balances_migration(RuntimeOrigin::root(), 1),
+			Error::<Test>::AlreadyMigrated
+		);
+		assert_noop!(
+			Migration::migrate_balances(RuntimeOrigin::root(), 3, chunk(vec![record(14, 5, 0)])),
+			Error::<Test>::MigrationNotStarted
+		);
+	});
+}
+
+#[test]
+fn malformed_rows_should_be_skipped_without_side_effects() {
+	new_test_ext().execute_with(|| {
+		let mut exceeding = record(12, 10, 5);
+		exceeding.named_reserves =
+			vec![NamedReserveRecord { id: MAPPED_RESERVE, amount: 8 }].try_into().unwrap();
+
+		assert_ok!(Migration::begin_balances_migration(RuntimeOrigin::root(), 4));
+		assert_ok!(Migration::migrate_balances(
+			RuntimeOrigin::root(),
+			0,
+			chunk(vec![record(1, 100, 0), record(11, u64::MAX, 1), exceeding, record(13, 7, 3)])
+		));
+		System::assert_has_event(Event::BalanceSkipped(0, SkipReason::AccountExists).into());
+		System::assert_has_event(Event::BalanceSkipped(1, SkipReason::Overflow).into());
+		System::assert_has_event(Event::BalanceSkipped(2, SkipReason::InvalidReserve).into());
+		System::assert_last_event(Event::MigratedBalances(1).into());
+		assert_eq!(
+			progress(),
+			ImportProgress { expected: 4, processed: 4, imported: 1, skipped: 3 }
+		);
+
+		// skipped rows leave neither accounts nor issuance behind
+		assert_eq!(Balances::free_balance(1), 10);
+		assert!(!System::account_exists(&11));
+		assert!(!System::account_exists(&12));
+		assert_eq!(Balances::reserved_balance(13), 3);
+		assert_eq!(Balances::total_issuance(), 30);
+
+		assert_ok!(Migration::finalize_balances_migration(RuntimeOrigin::root()));
+		System::assert_has_event(Event::BalancesMigrationFinalized(1, 3).into());
+		System::assert_last_event(Event::BalancesReconciled(7, 3, 20, 30).into());
+	});
+}
+
+#[test]
+fn reserves_and_locks_should_be_preserved() {
+	new_test_ext().execute_with(|| {
+		let mut row = record(20, 100, 30);
+		row.named_reserves = vec![
+			NamedReserveRecord { id: MAPPED_RESERVE, amount: 10 },
+			NamedReserveRecord { id: *b"unmapped", amount: 5 },
+		]
+		.try_into()
+		.unwrap();
+		row.locks = vec![
+			LockRecord { id: *b"staking ", amount: 50, reasons: Reasons::All },
+			LockRecord { id: *b"democrac", amount: 20, reasons: Reasons::Misc },
+		]
+		.try_into()
+		.unwrap();
+		row.vesting = Some(VestingRecord { locked: 40, per_block: 1, starting_block: 10 });
+
+		assert_ok!(Migration::begin_balances_migration(RuntimeOrigin::root(), 1));
+		assert_ok!(Migration::migrate_balances(RuntimeOrigin::root(), 0, chunk(vec![row])));
+
+		assert_eq!(Balances::free_balance(20), 100);
+		assert_eq!(Balances::reserved_balance(20), 30);
+		assert_eq!(
+			<Balances as NamedReservableCurrency<u64>>::reserved_balance_named(
+				&MAPPED_RESERVE,
+				&20
+			),
+			10
+		);
+		// the unmapped named reserve is part of the anonymous reserve
+		assert_eq!(
+			<Balances as NamedReservableCurrency<u64>>::reserved_balance_named(b"unmapped", &20),
+			0
+		);
+		assert_eq!(
+			pallet_balances::Locks::<Test>::get(20).into_inner(),
+			vec![
+				pallet_balances::BalanceLock {
+					id: *b"staking ",
+					amount: 50,
+					reasons: Reasons::All
+				},
+				pallet_balances::BalanceLock {
+					id: *b"democrac",
+					amount: 20,
+					reasons: Reasons::Misc
+				},
+				pallet_balances::BalanceLock {
+					id: *b"vesting ",
+					amount: 40,
+					reasons: Reasons::Misc
+				},
+			]
+		);
+		let account = System::account(20).data;
+		assert_eq!((account.misc_frozen, account.fee_frozen), (50, 50));
+		assert_eq!(
+			pallet_vesting::Vesting::<Test>::get(20).unwrap().into_inner(),
+			vec![pallet_vesting::VestingInfo::new(40, 1, 10)]
+		);
+		System::assert_last_event(Event::MigratedBalances(1).into());
+	});
+}
+
+fn identity(
+	who: u64,
+	deposit: u64,
+	judgements: Vec<(RegistrarIndex, Judgement<u64>)>,
+	subs: Vec<u64>,
+	subs_deposit: u64,
+) -> IdentityRecordOf<Test> {
+	IdentityRecord {
+		who,
+		deposit,
+		info: info(b"imported"),
+		judgements: judgements.try_into().unwrap(),
+		subs: subs
+			.into_iter()
+			.map(|sub| (sub, Data::None))
+			.collect::<Vec<_>>()
+			.try_into()
+			.unwrap(),
+		subs_deposit,
+	}
+}
+
+#[test]
+fn identities_should_be_imported_with_their_deposits() {
+	new_test_ext().execute_with(|| {
+		for who in [40, 50, 60] {
+			Balances::make_free_balance_be(&who, if who == 50 { 10 } else { 100 });
+		}
+		let pending = vec![(1, Judgement::Reasonable), (0, Judgement::FeePaid(5))];
+		let rows = vec![
+			identity(40, 20, pending.clone(), vec![41, 42], 20),
+			identity(50, 20, pending, vec![51], 10),
+			identity(60, 10, vec![], vec![41], 10),
+			identity(40, 20, vec![], vec![], 0),
+		];
+		assert_ok!(Migration::migrate_identities(RuntimeOrigin::root(), rows.try_into().unwrap()));
+
+		let registration = pallet_identity::IdentityOf::<Test>::get(40).unwrap();
+		assert_eq!(
+			registration.judgements.into_inner(),
+			vec![(0, Judgement::FeePaid(5)), (1, Judgement::Reasonable)]
+		);
+		assert_eq!(registration.deposit, 20);
+		assert_eq!(Balances::reserved_balance(40), 45);
+		assert!(pallet_identity::PendingRequests::<Test>::contains_key(0, 40));
+		assert_eq!(pallet_identity::SuperOf::<Test>::get(41), Some((40, Data::None)));
+		let (subs_deposit, subs) = pallet_identity::SubsOf::<Test>::get(40);
+		assert_eq!((subs_deposit, subs.into_inner()), (20, vec![41, 42]));
+
+		// nothing of a row whose deposits cannot be reserved is written
+		System::assert_has_event(
+			Event::IdentitySkipped(50, SkipReason::InsufficientBalance).into(),
+		);
+		assert_eq!(pallet_identity::IdentityOf::<Test>::get(50), None);
+		assert!(!pallet_identity::PendingRequests::<Test>::contains_key(0, 50));
+		assert_eq!(pallet_identity::SuperOf::<Test>::get(51), None);
+		assert_eq!(Balances::reserved_balance(50), 0);
+
+		// a sub account of another identity is dropped along with its share of the deposit
+		let report = IdentityReport {
+			expected_deposit: 20,
+			reserved_deposit: 10,
+			dropped_judgements: 0,
+			dropped_subs: 1,
+		};
+		System::assert_has_event(Event::IdentityMismatch(60, report.clone()).into());
+		assert_eq!(<IdentityMismatches<Test>>::get(60), Some(report));
+		assert_eq!(pallet_identity::SubsOf::<Test>::get(60).1.len(), 0);
+		assert_eq!(Balances::reserved_balance(60), 10);
+
+		System::assert_has_event(Event::IdentitySkipped(40, SkipReason::AccountExists).into());
+		System::assert_last_event(Event::MigratedIdentities(2).into());
+		assert_eq!(<IdentitiesImported<Test>>::get(), (2, 1));
+	});
+}
+
+#[test]
+fn undecodable_chunks_should_be_rejected() {
+	// `migrate_balances` is call 1, the cursor and the rows follow
+	let call = |records: Vec<BalanceRecordOf<Test>>| (1u8, 0u32, records).encode();
+	let decode = |encoded: &[u8]| Call::<Test>::decode(&mut &encoded[..]);
+
+	let valid = call(vec![record(10, 100, 0)]);
+	assert!(decode(&valid).is_ok());
+	assert!(decode(&valid[..valid.len() - 1]).is_err());
+
+	// more rows than `MaxChunkSize`
+	assert!(decode(&call((10..15).map(|who| record(who, 1, 0)).collect())).is_err());
+
+	// more named reserves than `MaxReserves`
+	let reserves = vec![NamedReserveRecord { id: MAPPED_RESERVE, amount: 1u64 }; 3];
+	let row =
+		(10u64, 3u64, 3u64, reserves, Vec::<LockRecord<u64>>::new(), None::<VestingRecord<u64>>);
+	assert!(decode(&(1u8, 0u32, vec![row]).encode()).is_err());
+}
+
+const ALICE: u64 = 30;
+const BOB: u64 = 31;
+const CAROL: u64 = 32;
+const DAVE: u64 = 33;
+
+type Info = IdentityInfo<ConstU32<2>>;
+
+fn info(display: &[u8]) -> Info {
+	IdentityInfo {
+		additional: Default::default(),
+		display: Data::Raw(display.to_vec().try_into().unwrap()),
+		legal: Data::None,
+		web: Data::None,
+		riot: Data::None,
+		email: Data::None,
+		pgp_fingerprint: None,
+		image: Data::None,
+		twitter: Data::None,
+		discord: Data::None,
+		steam: Data::None,
+		epic: Data::None,
+		twitch: Data::None,
+	}
+}
+
+fn leaf(
+	who: u64,
+	amount: u64,
+	vesting: Option<VestingRecord<u64>>,
+	identity: Option<&Info>,
+) -> ClaimLeafOf<Test> {
+	let identity_hash =
+		identity.map_or_else(H256::zero, |info| info.using_encoded(blake2_256).into());
+	ClaimLeaf { who, amount, vesting, identity_hash }
+}
+
+/// The leaves of the test airdrop: a vested one, one with an identity, a plain one and one whose
+/// vesting schedule locks more than its amount.
+fn leaves() -> Vec<ClaimLeafOf<Test>> {
+	vec![
+		leaf(
+			ALICE,
+			100,
+			Some(VestingRecord { locked: 60, per_block: 10, starting_block: 2 }),
+			None,
+		),
+		leaf(BOB, 200, None, Some(&info(b"bob"))),
+		leaf(CAROL, 300, None, None),
+		leaf(DAVE, 50, Some(VestingRecord { locked: 500, per_block: 10, starting_block: 2 }), None),
+	]
+}
+
+/// Starts the airdrop over `leaves()` with a deadline at block 10.
+fn start_airdrop() -> MerkleTree {
+	let tree = MerkleTree::new(leaves().iter().map(merkle::leaf_hash).collect()).unwrap();
+	assert_ok!(Migration::start_airdrop(RuntimeOrigin::root(), tree.root(), 650, 10));
+	tree
+}
+
+fn proof(tree: &MerkleTree, index: usize) -> BoundedVec<H256, ConstU32<8>> {
+	tree.proof(index).unwrap().try_into().unwrap()
+}
+
+fn claim(tree: &MerkleTree, index: usize, identity: Option<Info>) -> DispatchResult {
+	Migration::claim(
+		RuntimeOrigin::none(),
+		leaves()[index].clone(),
+		identity.map(Box::new),
+		proof(tree, index),
+	)
+}
+
+#[test]
+fn merkle_proofs_should_lead_to_the_root() {
+	assert!(MerkleTree::new(Vec::new()).is_none());
+	for count in 1..=9u64 {
+		let leaves = (0..count).map(|i| merkle::leaf_hash(&i)).collect::<Vec<_>>();
+		let tree = MerkleTree::new(leaves.clone()).unwrap();
+		for (index, leaf) in leaves.iter().enumerate() {
+			let proof = tree.proof(index).unwrap();
+			assert!(merkle::verify(&tree.root(), *leaf, &proof));
+			if count > 1 {
+				let other = leaves[(index + 1) % leaves.len()];
+				assert!(!merkle::verify(&tree.root(), other, &proof));
+			}
+		}
+		assert_eq!(tree.proof(count as usize), None);
+	}
+}
+
+#[test]
+fn inner_nodes_should_not_pass_as_leaves() {
+	let leaves = (0..4u64).map(|i| merkle::leaf_hash(&i)).collect::<Vec<_>>();
+	let tree = MerkleTree::new(leaves.clone()).unwrap();
+	let inner = merkle::pair_hash(&leaves[0], &leaves[1]);
+	assert!(merkle::verify(&tree.root(), inner, &[merkle::pair_hash(&leaves[2], &leaves[3])]));
+
+	// a leaf encoding both children does not hash to their node
+	let children = (leaves[0].min(leaves[1]), leaves[0].max(leaves[1]));
+	assert_ne!(merkle::leaf_hash(&children), inner);
+	assert_ne!(merkle::leaf_hash(&0u64), H256::from(0u64.using_encoded(blake2_256)));
+}
+
+#[test]
+fn claims_should_credit_vest_and_set_identities() {
+	new_test_ext().execute_with(|| {
+		let tree = start_airdrop();
+		System::assert_last_event(Event::AirdropStarted(tree.root(), 650, 10).into());
+
+		assert_ok!(claim(&tree, 0, None));
+		System::assert_last_event(Event::AirdropClaimed(ALICE, 100).into());
+		assert_eq!(Balances::free_balance(ALICE), 100);
+		assert_eq!(
+			pallet_vesting::Vesting::<Test>::get(ALICE).unwrap().into_inner(),
+			vec![pallet_vesting::VestingInfo::new(60, 10, 2)]
+		);
+		assert_eq!(Balances::usable_balance(ALICE), 40);
+
+		assert_ok!(claim(&tree, 1, Some(info(b"bob"))));
+		let registration = pallet_identity::IdentityOf::<Test>::get(BOB).unwrap();
+		assert_eq!((registration.info, registration.deposit), (info(b"bob"), 0));
+		assert_eq!(Balances::free_balance(BOB), 200);
+
+		assert_ok!(claim(&tree, 2, None));
+		assert_eq!(pallet_identity::IdentityOf::<Test>::get(CAROL), None);
+		assert!(<Claimed<Test>>::get(tree.root(), CAROL));
+		assert_eq!(<Airdrop<Test>>::get().unwrap().claimed, 600);
+
+		// the rest of DAVE goes to the treasury once the deadline is reached
+		Migration::on_initialize(10);
+		System::assert_last_event(Event::AirdropExpired(50).into());
+		assert_eq!(Balances::free_balance(TREASURY), 50);
+		assert_eq!(<Airdrop<Test>>::get(), None);
+	});
+}
+
+#[test]
+fn invalid_claims_should_be_rejected() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			claim(&MerkleTree::new(vec![H256::zero()]).unwrap(), 0, None),
+			Error::<Test>::NoAirdrop
+		);
+		let tree = start_airdrop();
+		let alice = leaves()[0].clone();
+
+		assert_noop!(
+			Migration::claim(RuntimeOrigin::signed(ALICE), alice.clone(), None, proof(&tree, 0)),
+			BadOrigin
+		);
+		assert_noop!(
+			Migration::claim(RuntimeOrigin::none(), alice.clone(), None, proof(&tree, 1)),
+			Error::<Test>::InvalidProof
+		);
+		let inflated = ClaimLeaf { amount: 1_000, ..alice.clone() };
+		assert_noop!(
+			Migration::claim(RuntimeOrigin::none(), inflated, None, proof(&tree, 0)),
+			Error::<Test>::InvalidProof
+		);
+		assert_noop!(claim(&tree, 1, None), Error::<Test>::IdentityMismatch);
+		assert_noop!(claim(&tree, 1, Some(info(b"eve"))), Error::<Test>::IdentityMismatch);
+		assert_noop!(claim(&tree, 3, None), Error::<Test>::InvalidVesting);
+
+		// an existing identity is not overwritten
+		let existing =
+			Registration { judgements: Default::default(), deposit: 0, info: info(b"old") };
+		pallet_identity::IdentityOf::<Test>::insert(BOB, existing.clone());
+		assert_noop!(claim(&tree, 1, Some(info(b"bob"))), Error::<Test>::IdentityExists);
+		assert_eq!(pallet_identity::IdentityOf::<Test>::get(BOB), Some(existing));
+
+		// a vesting schedule which cannot be created fails the whole claim
+		let schedules = vec![pallet_vesting::VestingInfo::new(1, 1, 1); 3];
+		pallet_vesting::Vesting::<Test>::insert(ALICE, BoundedVec::try_from(schedules).unwrap());
+		assert_noop!(claim(&tree, 0, None), Error::<Test>::InvalidVesting);
+		pallet_vesting::Vesting::<Test>::remove(ALICE);
+
+		assert_ok!(claim(&tree, 2, None));
+		assert_noop!(claim(&tree, 2, None), Error::<Test>::AlreadyClaimed);
+
+		System::set_block_number(10);
+		assert_noop!(claim(&tree, 0, None), Error::<Test>::InvalidDeadline);
+	});
+}
+
+#[test]
+fn claims_should_be_validated_before_entering_the_pool() {
+	new_test_ext().execute_with(|| {
+		let tree = start_airdrop();
+		let validate = |index: usize, proof_index: usize| {
+			let call = Call::claim {
+				leaf: leaves()[index].clone(),
+				identity: None,
+				proof: proof(&tree, proof_index),
+			};
+			Migration::validate_unsigned(TransactionSource::External, &call)
+		};
+
+		assert_eq!(
+			validate(2, 2),
+			ValidTransaction::with_tag_prefix("MigrationClaim")
+				.and_provides(&CAROL)
+				.longevity(64)
+				.propagate(true)
+				.build()
+		);
+		assert_eq!(validate(2, 1), Err(InvalidTransaction::BadProof.into()));
+		assert_eq!(validate(3, 3), Err(InvalidTransaction::Call.into()));
+
+		// a vesting schedule which cannot be created keeps the claim out of the pool
+		let schedules = vec![pallet_vesting::VestingInfo::new(1, 1, 1); 3];
+		pallet_vesting::Vesting::<Test>::insert(ALICE, BoundedVec::try_from(schedules).unwrap());
+		assert_eq!(validate(0, 0), Err(InvalidTransaction::Call.into()));
+		pallet_vesting::Vesting::<Test>::remove(ALICE);
+		assert!(validate(0, 0).is_ok());
+
+		assert_ok!(claim(&tree, 2, None));
+		assert_eq!(validate(2, 2), Err(InvalidTransaction::Stale.into()));
+
+		let call = Call::register_cleanup { prefix: vec![0; 16].try_into().unwrap() };
+		assert_eq!(
+			Migration::validate_unsigned(TransactionSource::External, &call),
+			Err(InvalidTransaction::Call.into())
+		);
+	});
+}
+
+#[test]
+fn cleanup_should_remove_queued_prefixes_in_batches() {
+	new_test_ext().execute_with(|| {
+		let prefix = vec![7; 16];
+		for i in 0..5u8 {
+			unhashed::put_raw(&[&prefix[..], &[i]].concat(), &[i]);
+		}
+		assert_ok!(Migration::register_cleanup(
+			RuntimeOrigin::root(),
+			prefix.clone().try_into().unwrap()
+		));
+		assert_eq!(Migration::cleanup_remaining(10), vec![(prefix.clone(), 5)]);
+
+		Migration::on_idle(1, Weight::MAX);
+		System::assert_last_event(Event::CleanupProgress(prefix.clone(), 3).into());
+		assert_eq!(Migration::cleanup_remaining(10), vec![(prefix.clone(), 2)]);
+
+		Migration::on_idle(1, Weight::MAX);
+		System::assert_last_event(Event::CleanupCompleted(prefix.clone()).into());
+		assert_eq!(Migration::cleanup_remaining(10), vec![]);
+		assert!(!<CleanupQueue<Test>>::exists());
+		assert!(!<CleanupCursor<Test>>::exists());
+
+		// an empty queue is not written back
+		assert_eq!(migration::cleanup::<Test>(10), 0);
+		assert!(!<CleanupQueue<Test>>::exists());
+	});
+}
@@ -0,0 +1,118 @@
+//! Records submitted to the migration calls, SCALE encoded by the `migration-converter` tool.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	traits::{Get, LockIdentifier},
+	BoundedVec, CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+};
+use pallet_balances::Reasons;
+use pallet_identity::{Data, IdentityInfo, Judgement, RegistrarIndex};
+use scale_info::TypeInfo;
+use sp_core::H256;
+use sp_runtime::RuntimeDebug;
+use sp_std::fmt::Debug;
+
+/// A named reserve of an account on the source chain.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct NamedReserveRecord<Balance> {
+	/// Reserve identifier on the source chain, mapped through `Config::ReserveIdOf`.
+	pub id: [u8; 8],
+	pub amount: Balance,
+}
+
+/// A balance lock of an account on the source chain.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct LockRecord<Balance> {
+	pub id: LockIdentifier,
+	pub amount: Balance,
+	pub reasons: Reasons,
+}
+
+/// A vesting schedule of an account on the source chain.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct VestingRecord<Balance> {
+	pub locked: Balance,
+	pub per_block: Balance,
+	pub starting_block: u32,
+}
+
+/// An account of the source chain, one row of a balances import.
+#[derive(CloneNoBound, Encode, Decode, Eq, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo)]
+#[scale_info(skip_type_params(MaxReserves, MaxLocks))]
+pub struct BalanceRecord<
+	AccountId: Encode + Decode + Clone + Debug + Eq + PartialEq,
+	Balance: Encode + Decode + Copy + Clone + Debug + Eq + PartialEq,
+	MaxReserves: Get<u32>,
+	MaxLocks: Get<u32>,
+> {
+	pub who: AccountId,
+	pub free: Balance,
+	/// Total reserved balance, including `named_reserves`.
+	pub reserved: Balance,
+	pub named_reserves: BoundedVec<NamedReserveRecord<Balance>, MaxReserves>,
+	pub locks: BoundedVec<LockRecord<Balance>, MaxLocks>,
+	pub vesting: Option<VestingRecord<Balance>>,
+}
+
+/// An identity of the source chain with its judgements and sub accounts, one row of an
+/// identities import.
+#[derive(CloneNoBound, Encode, Decode, Eq, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo)]
+#[scale_info(skip_type_params(FieldLimit, MaxJudgements, MaxSubs))]
+pub struct IdentityRecord<
+	AccountId: Encode + Decode + Clone + Debug + Eq + PartialEq,
+	Balance: Encode + Decode + MaxEncodedLen + Copy + Clone + Debug + Eq + PartialEq,
+	FieldLimit: Get<u32>,
+	MaxJudgements: Get<u32>,
+	MaxSubs: Get<u32>,
+> {
+	pub who: AccountId,
+	/// Amount held on deposit for the identity.
+	pub deposit: Balance,
+	pub info: IdentityInfo<FieldLimit>,
+	/// Judgements by registrar index on the source chain, mapped through
+	/// `Config::RegistrarIndexOf`.
+	pub judgements: BoundedVec<(RegistrarIndex, Judgement<Balance>), MaxJudgements>,
+	/// Sub accounts with their names.
+	pub subs: BoundedVec<(AccountId, Data), MaxSubs>,
+	/// Amount held on deposit for all of `subs`.
+	pub subs_deposit: Balance,
+}
+
+/// Differences between an identity of the source chain and the one imported from it.
+#[derive(Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct IdentityReport<Balance> {
+	/// Deposit recorded on the source chain, for the identity, pending judgement fees and subs.
+	pub expected_deposit: Balance,
+	/// Deposit reserved by the import.
+	pub reserved_deposit: Balance,
+	/// Judgements of registrars without a local index, or pending judgements of registrars
+	/// taking their fee in another currency than the native one.
+	pub dropped_judgements: u32,
+	/// Sub accounts which already are a sub account of another identity.
+	pub dropped_subs: u32,
+}
+
+/// An account of the source chain committed to by the root of an airdrop, claimed with a proof.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ClaimLeaf<AccountId, Balance> {
+	pub who: AccountId,
+	/// Free balance credited by the claim.
+	pub amount: Balance,
+	/// Vesting schedule over the claimed amount, if any.
+	pub vesting: Option<VestingRecord<Balance>>,
+	/// blake2-256 hash of the SCALE encoded `IdentityInfo`, zero without an identity.
+	pub identity_hash: H256,
+}
+
+/// An ongoing airdrop.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct AirdropInfo<Balance, BlockNumber> {
+	/// Merkle root over the hashes of all `ClaimLeaf`s.
+	pub root: H256,
+	/// Sum of the amounts of all leaves.
+	pub total: Balance,
+	/// Sum of the amounts claimed so far.
+	pub claimed: Balance,
+	/// First block at which claims are rejected and the unclaimed rest goes to the treasury.
+	pub deadline: BlockNumber,
+}
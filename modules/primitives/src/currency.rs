@@ -5,7 +5,9 @@ use codec::{Decode, Encode, MaxEncodedLen};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use scale_info::TypeInfo;
 use sp_runtime::RuntimeDebug;
-use sp_std::{convert::TryFrom, prelude::*};
+use sp_std::{convert::TryFrom, ops::Range, prelude::*};
+
+use crate::EvmAddress;
 
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
@@ -84,6 +86,13 @@ macro_rules! create_currency_id {
 					$((stringify!($symbol), $deci),)*
 				]
 			}
+
+			/// Every token symbol, in declaration order.
+			pub fn all() -> Vec<TokenSymbol> {
+				vec![
+					$(TokenSymbol::$symbol,)*
+				]
+			}
 		}
 
     }
@@ -146,6 +155,61 @@ pub enum CurrencyIdType {
 	ForeignAsset,
 }
 
+/// Position of the `CurrencyIdType` byte in the H160 address of a `CurrencyId`.
+pub const H160_POSITION_CURRENCY_ID_TYPE: usize = 9;
+/// Position of the `TokenSymbol` byte in the H160 address of a `CurrencyId::Token`.
+pub const H160_POSITION_TOKEN: usize = 19;
+/// Position of the big endian `ForeignAssetId` in the H160 address of a
+/// `CurrencyId::ForeignAsset`.
+pub const H160_POSITION_FOREIGN_ASSET: Range<usize> = 16..20;
+
+/// Canonical address of a currency, e.g. for ERC20 adapters and precompiles.
+///
+/// All bytes but the `CurrencyIdType` and the id itself are zero, e.g. `DOT` is
+/// `0x000000000000000000010000000000000000000c` and `ForeignAsset(1)` is
+/// `0x0000000000000000000200000000000000000001`.
+impl From<CurrencyId> for EvmAddress {
+	fn from(currency_id: CurrencyId) -> Self {
+		let mut address = [0u8; 20];
+		match currency_id {
+			CurrencyId::Token(symbol) => {
+				address[H160_POSITION_CURRENCY_ID_TYPE] = CurrencyIdType::Token.into();
+				address[H160_POSITION_TOKEN] = symbol.into();
+			},
+			CurrencyId::ForeignAsset(id) => {
+				address[H160_POSITION_CURRENCY_ID_TYPE] = CurrencyIdType::ForeignAsset.into();
+				address[H160_POSITION_FOREIGN_ASSET].copy_from_slice(&id.to_be_bytes());
+			},
+		}
+		EvmAddress::from(address)
+	}
+}
+
+/// Inverse of the H160 encoding, fails for any address not produced by it.
+impl TryFrom<EvmAddress> for CurrencyId {
+	type Error = ();
+
+	fn try_from(address: EvmAddress) -> Result<Self, Self::Error> {
+		let bytes = address.as_bytes();
+		let currency_id = match CurrencyIdType::try_from(bytes[H160_POSITION_CURRENCY_ID_TYPE])
+			.map_err(|_| ())?
+		{
+			CurrencyIdType::Token =>
+				CurrencyId::Token(TokenSymbol::try_from(bytes[H160_POSITION_TOKEN])?),
+			CurrencyIdType::ForeignAsset => {
+				let mut id = [0u8; 4];
+				id.copy_from_slice(&bytes[H160_POSITION_FOREIGN_ASSET]);
+				CurrencyId::ForeignAsset(ForeignAssetId::from_be_bytes(id))
+			},
+		};
+		// reject addresses with anything but zeros around the id
+		if EvmAddress::from(currency_id) != address {
+			return Err(())
+		}
+		Ok(currency_id)
+	}
+}
+
 #[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo)]
 pub enum AssetIds {
 	ForeignAssetId(ForeignAssetId),
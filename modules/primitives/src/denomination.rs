@@ -0,0 +1,175 @@
+//! Decimals and human readable amounts for any `CurrencyId`.
+//!
+//! Tokens carry their decimals in [`TokenSymbol`](crate::TokenSymbol), foreign assets only know
+//! theirs through the asset registry. [`CurrencyDecimals`] abstracts over where the decimals come
+//! from, [`Denomination`] does the arithmetic without ever panicking.
+
+use crate::{
+	currency::{AssetIdMapping, AssetIds, AssetMetadata, ForeignAssetId},
+	Balance, CurrencyId, TokenInfo,
+};
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::{fmt, marker::PhantomData};
+
+/// Largest number of decimals for which one unit still fits into a `Balance`.
+pub const MAX_DECIMALS: u8 = 38;
+
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, TypeInfo)]
+pub enum DecimalsError {
+	/// The decimals of the currency are not known.
+	UnknownDecimals,
+	/// The decimals exceed [`MAX_DECIMALS`].
+	TooManyDecimals,
+	/// The amount does not fit into a `Balance`.
+	Overflow,
+	/// The amount is not a valid decimal number for this denomination.
+	InvalidAmount,
+}
+
+/// Source of the decimals of a `CurrencyId`.
+pub trait CurrencyDecimals {
+	fn decimals(currency_id: CurrencyId) -> Option<u8>;
+}
+
+/// Only knows the decimals of `CurrencyId::Token`.
+impl CurrencyDecimals for () {
+	fn decimals(currency_id: CurrencyId) -> Option<u8> {
+		currency_id.decimals()
+	}
+}
+
+/// Resolves the decimals of foreign assets through an [`AssetIdMapping`].
+pub struct MappedDecimals<Mapping, MultiLocation, AssetBalance, BoundedString>(
+	PhantomData<(Mapping, MultiLocation, AssetBalance, BoundedString)>,
+);
+impl<Mapping, MultiLocation, AssetBalance, BoundedString> CurrencyDecimals
+	for MappedDecimals<Mapping, MultiLocation, AssetBalance, BoundedString>
+where
+	Mapping:
+		AssetIdMapping<ForeignAssetId, MultiLocation, AssetMetadata<AssetBalance, BoundedString>>,
+{
+	fn decimals(currency_id: CurrencyId) -> Option<u8> {
+		match currency_id {
+			CurrencyId::Token(_) => currency_id.decimals(),
+			CurrencyId::ForeignAsset(id) =>
+				Mapping::get_asset_metadata(AssetIds::ForeignAssetId(id)).map(|m| m.decimals),
+		}
+	}
+}
+
+/// Amounts of a currency with a known number of decimals.
+#[derive(Eq, PartialEq, Copy, Clone, RuntimeDebug)]
+pub struct Denomination {
+	decimals: u8,
+}
+
+impl Denomination {
+	pub fn new(decimals: u8) -> Result<Self, DecimalsError> {
+		if decimals > MAX_DECIMALS {
+			return Err(DecimalsError::TooManyDecimals)
+		}
+		Ok(Self { decimals })
+	}
+
+	/// The denomination of `currency_id`, with decimals taken from `D`.
+	pub fn of<D: CurrencyDecimals>(currency_id: CurrencyId) -> Result<Self, DecimalsError> {
+		Self::new(D::decimals(currency_id).ok_or(DecimalsError::UnknownDecimals)?)
+	}
+
+	pub fn decimals(&self) -> u8 {
+		self.decimals
+	}
+
+	/// One whole unit, e.g. one DOT in plancks.
+	pub fn dollar(&self) -> Balance {
+		10u128.pow(self.decimals.into())
+	}
+
+	pub fn cent(&self) -> Balance {
+		self.dollar() / 100
+	}
+
+	pub fn millicent(&self) -> Balance {
+		self.cent() / 1000
+	}
+
+	pub fn microcent(&self) -> Balance {
+		self.millicent() / 1000
+	}
+
+	/// `units` whole units in the smallest denomination.
+	pub fn units(&self, units: Balance) -> Result<Balance, DecimalsError> {
+		units.checked_mul(self.dollar()).ok_or(DecimalsError::Overflow)
+	}
+
+	/// Parse a human readable amount like `1_000.25` into the smallest denomination.
+	///
+	/// Underscores are ignored, at most `decimals` fractional digits are accepted.
+	pub fn parse(&self, amount: &str) -> Result<Balance, DecimalsError> {
+		fn digits(s: &str) -> impl Iterator<Item = u8> + '_ {
+			s.bytes().filter(|b| *b != b'_')
+		}
+
+		let (whole, fraction) = match amount.split_once('.') {
+			Some((whole, fraction)) => (whole, fraction),
+			None => (amount, ""),
+		};
+		if digits(whole).next().is_none() && digits(fraction).next().is_none() {
+			return Err(DecimalsError::InvalidAmount)
+		}
+
+		let mut result: Balance = 0;
+		let mut fraction_digits = 0u8;
+		for (byte, is_fraction) in
+			digits(whole).map(|b| (b, false)).chain(digits(fraction).map(|b| (b, true)))
+		{
+			if !byte.is_ascii_digit() {
+				return Err(DecimalsError::InvalidAmount)
+			}
+			if is_fraction {
+				if fraction_digits == self.decimals {
+					return Err(DecimalsError::InvalidAmount)
+				}
+				fraction_digits += 1;
+			}
+			result = result
+				.checked_mul(10)
+				.and_then(|r| r.checked_add((byte - b'0').into()))
+				.ok_or(DecimalsError::Overflow)?;
+		}
+
+		result
+			.checked_mul(10u128.pow((self.decimals - fraction_digits).into()))
+			.ok_or(DecimalsError::Overflow)
+	}
+
+	/// Display `amount` in whole units, without trailing zeros in the fractional part.
+	pub fn display(&self, amount: Balance) -> DisplayAmount {
+		DisplayAmount { amount, denomination: *self }
+	}
+}
+
+/// A `Balance` displayed in whole units of a [`Denomination`].
+pub struct DisplayAmount {
+	amount: Balance,
+	denomination: Denomination,
+}
+
+impl fmt::Display for DisplayAmount {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let dollar = self.denomination.dollar();
+		let (whole, mut fraction) = (self.amount / dollar, self.amount % dollar);
+		write!(f, "{}", whole)?;
+		if fraction == 0 {
+			return Ok(())
+		}
+		let mut width = usize::from(self.denomination.decimals);
+		while fraction % 10 == 0 {
+			fraction /= 10;
+			width -= 1;
+		}
+		write!(f, ".{:0width$}", fraction, width = width)
+	}
+}
@@ -27,7 +27,11 @@ use sp_runtime::{
 use sp_std::{prelude::*};
 
 pub mod currency;
+pub mod denomination;
+#[cfg(test)]
+mod tests;
 pub use currency::{AssetIdMapping, CurrencyId, TokenSymbol, TokenInfo};
+pub use denomination::{CurrencyDecimals, DecimalsError, Denomination};
 
 /// An index to a block.
 pub type BlockNumber = u32;
@@ -71,6 +75,9 @@ pub type Index = u32;
 /// A hash of some data used by the chain.
 pub type Hash = sp_core::H256;
 
+/// An address in the Ethereum format, used to represent currencies to contracts.
+pub type EvmAddress = sp_core::H160;
+
 /// A timestamp: milliseconds since the unix epoch.
 /// `u64` is enough to represent a duration of half a billion years, when the
 /// time scale is milliseconds.
@@ -107,8 +114,14 @@ pub enum ReserveIdentifier {
 }
 
 
+/// One whole unit of a token.
+///
+/// # Panics
+///
+/// For `CurrencyId::ForeignAsset`, whose decimals are only known to the asset registry. Use
+/// [`Denomination::of`] with a registry-aware [`CurrencyDecimals`] for those.
 pub fn dollar(currency_id: CurrencyId) -> Balance {
-	10u128.saturating_pow(currency_id.decimals().expect("Not support Non-Token decimals").into())
+	Denomination::of::<()>(currency_id).expect("Not support Non-Token decimals").dollar()
 }
 
 pub fn cent(currency_id: CurrencyId) -> Balance {
@@ -0,0 +1,206 @@
+// Tests for the primitives
+
+use super::*;
+use crate::{
+	currency::{
+		AssetIds, AssetMetadata, CurrencyIdType, ForeignAssetId, H160_POSITION_CURRENCY_ID_TYPE,
+		H160_POSITION_FOREIGN_ASSET, H160_POSITION_TOKEN,
+	},
+	denomination::{MappedDecimals, MAX_DECIMALS},
+};
+use sp_std::convert::TryFrom;
+
+const DOT_ID: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
+
+fn all_token_symbols() -> Vec<TokenSymbol> {
+	(0..=u8::MAX).filter_map(|v| TokenSymbol::try_from(v).ok()).collect()
+}
+
+#[test]
+fn every_token_symbol_is_covered() {
+	assert_eq!(all_token_symbols().len(), TokenSymbol::get_info().len());
+}
+
+#[test]
+fn token_addresses_round_trip() {
+	for symbol in all_token_symbols() {
+		let currency_id = CurrencyId::Token(symbol);
+		let address = EvmAddress::from(currency_id);
+
+		let bytes = address.as_bytes();
+		assert_eq!(bytes[H160_POSITION_CURRENCY_ID_TYPE], u8::from(CurrencyIdType::Token));
+		assert_eq!(bytes[H160_POSITION_TOKEN], Into::<u8>::into(symbol));
+		assert_eq!(CurrencyId::try_from(address), Ok(currency_id));
+	}
+}
+
+#[test]
+fn foreign_asset_addresses_round_trip() {
+	for id in [0, 1, 2, 255, 256, 65_535, 65_536, 0x1234_5678, u32::MAX - 1, u32::MAX] {
+		let currency_id = CurrencyId::ForeignAsset(id);
+		let address = EvmAddress::from(currency_id);
+
+		let bytes = address.as_bytes();
+		assert_eq!(bytes[H160_POSITION_CURRENCY_ID_TYPE], u8::from(CurrencyIdType::ForeignAsset));
+		assert_eq!(bytes[H160_POSITION_FOREIGN_ASSET], id.to_be_bytes());
+		assert_eq!(CurrencyId::try_from(address), Ok(currency_id));
+	}
+}
+
+#[test]
+fn addresses_are_stable() {
+	let mut dot = [0u8; 20];
+	dot[9] = 1;
+	dot[19] = 12;
+	assert_eq!(EvmAddress::from(DOT_ID), EvmAddress::from(dot));
+
+	let mut foreign_asset = [0u8; 20];
+	foreign_asset[9] = 2;
+	foreign_asset[16..20].copy_from_slice(&[0x12, 0x34, 0x56, 0x78]);
+	assert_eq!(
+		EvmAddress::from(CurrencyId::ForeignAsset(0x1234_5678)),
+		EvmAddress::from(foreign_asset)
+	);
+}
+
+#[test]
+fn addresses_are_unique() {
+	let mut addresses: Vec<EvmAddress> = all_token_symbols()
+		.into_iter()
+		.map(CurrencyId::Token)
+		.chain([0, 1, 12, u32::MAX].into_iter().map(CurrencyId::ForeignAsset))
+		.map(EvmAddress::from)
+		.collect();
+	let count = addresses.len();
+	addresses.sort();
+	addresses.dedup();
+	assert_eq!(addresses.len(), count);
+}
+
+#[test]
+fn invalid_addresses_are_rejected() {
+	let decode = |address: [u8; 20]| CurrencyId::try_from(EvmAddress::from(address));
+
+	// the zero address and precompiles
+	assert_eq!(decode([0u8; 20]), Err(()));
+	let mut precompile = [0u8; 20];
+	precompile[19] = 1;
+	assert_eq!(decode(precompile), Err(()));
+
+	// unknown currency id type
+	let mut address = EvmAddress::from(DOT_ID).to_fixed_bytes();
+	address[H160_POSITION_CURRENCY_ID_TYPE] = 3;
+	assert_eq!(decode(address), Err(()));
+
+	// unknown token symbol
+	let mut address = EvmAddress::from(DOT_ID).to_fixed_bytes();
+	address[H160_POSITION_TOKEN] = 255;
+	assert_eq!(decode(address), Err(()));
+
+	// garbage around a valid id
+	for position in (0..20).filter(|p| {
+		*p != H160_POSITION_CURRENCY_ID_TYPE && !H160_POSITION_FOREIGN_ASSET.contains(p)
+	}) {
+		let mut address = EvmAddress::from(DOT_ID).to_fixed_bytes();
+		address[position] = 1;
+		assert_eq!(decode(address), Err(()));
+
+		let mut address = EvmAddress::from(CurrencyId::ForeignAsset(7)).to_fixed_bytes();
+		address[position] = 1;
+		assert_eq!(decode(address), Err(()));
+	}
+}
+
+/// Knows foreign asset 7 with 6 decimals and foreign asset 8 with too many decimals.
+struct MockRegistry;
+impl AssetIdMapping<ForeignAssetId, (), AssetMetadata<Balance, Vec<u8>>> for MockRegistry {
+	fn get_asset_metadata(asset_ids: AssetIds) -> Option<AssetMetadata<Balance, Vec<u8>>> {
+		let decimals = match asset_ids {
+			AssetIds::ForeignAssetId(7) => 6,
+			AssetIds::ForeignAssetId(8) => MAX_DECIMALS + 1,
+			_ => return None,
+		};
+		Some(AssetMetadata { name: vec![], symbol: vec![], decimals, minimal_balance: 1 })
+	}
+
+	fn get_multi_location(_: ForeignAssetId) -> Option<()> {
+		None
+	}
+
+	fn get_currency_id(_: ()) -> Option<CurrencyId> {
+		None
+	}
+}
+
+type RegistryDecimals = MappedDecimals<MockRegistry, (), Balance, Vec<u8>>;
+
+#[test]
+fn token_denominations_match_the_unit_helpers() {
+	for symbol in all_token_symbols() {
+		let currency_id = CurrencyId::Token(symbol);
+		let denomination = Denomination::of::<()>(currency_id).unwrap();
+		assert_eq!(Some(denomination.decimals()), currency_id.decimals());
+		assert_eq!(denomination.dollar(), dollar(currency_id));
+		assert_eq!(denomination.cent(), cent(currency_id));
+		assert_eq!(denomination.millicent(), millicent(currency_id));
+		assert_eq!(denomination.microcent(), microcent(currency_id));
+		assert_eq!(Denomination::of::<RegistryDecimals>(currency_id), Ok(denomination));
+	}
+}
+
+#[test]
+fn foreign_asset_decimals_come_from_the_registry() {
+	let asset = |id| CurrencyId::ForeignAsset(id);
+
+	assert_eq!(Denomination::of::<()>(asset(7)), Err(DecimalsError::UnknownDecimals));
+	assert_eq!(Denomination::of::<RegistryDecimals>(asset(7)), Denomination::new(6));
+	assert_eq!(Denomination::of::<RegistryDecimals>(asset(8)), Err(DecimalsError::TooManyDecimals));
+	assert_eq!(Denomination::of::<RegistryDecimals>(asset(9)), Err(DecimalsError::UnknownDecimals));
+}
+
+#[test]
+fn denominations_never_overflow() {
+	assert_eq!(Denomination::new(MAX_DECIMALS).unwrap().dollar(), 10u128.pow(38));
+	assert_eq!(Denomination::new(MAX_DECIMALS + 1), Err(DecimalsError::TooManyDecimals));
+
+	let micro = Denomination::new(6).unwrap();
+	assert_eq!(micro.units(3), Ok(3_000_000));
+	assert_eq!(micro.units(Balance::MAX), Err(DecimalsError::Overflow));
+	assert_eq!(Denomination::new(0).unwrap().units(Balance::MAX), Ok(Balance::MAX));
+}
+
+#[test]
+fn amounts_are_parsed() {
+	let micro = Denomination::new(6).unwrap();
+	assert_eq!(micro.parse("1_000.25"), Ok(1_000_250_000));
+	assert_eq!(micro.parse("0.000001"), Ok(1));
+	assert_eq!(micro.parse(".5"), Ok(500_000));
+	assert_eq!(micro.parse("5."), Ok(5_000_000));
+	assert_eq!(micro.parse("007"), Ok(7_000_000));
+
+	for invalid in ["", ".", "_", "1.0000001", "1a", "-1", "+1", "1.2.3", " 1"] {
+		assert_eq!(micro.parse(invalid), Err(DecimalsError::InvalidAmount), "{invalid:?}");
+	}
+
+	let max = Denomination::new(MAX_DECIMALS).unwrap();
+	assert_eq!(max.parse("3"), Ok(3 * 10u128.pow(38)));
+	assert_eq!(max.parse("4"), Err(DecimalsError::Overflow));
+	assert_eq!(
+		micro.parse("340282366920938463463374607431768211456"),
+		Err(DecimalsError::Overflow)
+	);
+}
+
+#[test]
+fn amounts_are_displayed_in_whole_units() {
+	let micro = Denomination::new(6).unwrap();
+	assert_eq!(micro.display(1_000_250_000).to_string(), "1000.25");
+	assert_eq!(micro.display(0).to_string(), "0");
+	assert_eq!(micro.display(1).to_string(), "0.000001");
+	assert_eq!(micro.display(5_000_000).to_string(), "5");
+	assert_eq!(Denomination::new(0).unwrap().display(42).to_string(), "42");
+
+	for amount in [0, 1, 10, 999_999, 1_000_000, 1_000_250_000, Balance::MAX] {
+		assert_eq!(micro.parse(&micro.display(amount).to_string()), Ok(amount));
+	}
+}
@@ -0,0 +1,83 @@
+//! Relay randomness module benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use crate::Pallet as RelayRandomness;
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::traits::Hooks;
+use frame_system::RawOrigin;
+use sp_runtime::traits::{BlakeTwo256, Bounded};
+use sp_trie::{MemoryDB, StorageProof, TrieDBMutBuilderV1, TrieMut};
+
+/// The storage root and proof of a relay state holding `randomness` as the VRF output of the relay
+/// parent, built without the `std` only sproof builder.
+pub(crate) fn relay_state_proof(randomness: [u8; 32]) -> (H256, StorageProof) {
+	let mut db = MemoryDB::<BlakeTwo256>::default();
+	let mut root = H256::default();
+	{
+		let mut trie = TrieDBMutBuilderV1::<BlakeTwo256>::new(&mut db, &mut root).build();
+		trie.insert(well_known_keys::CURRENT_BLOCK_RANDOMNESS, &Some(randomness).encode())
+			.expect("inserting into an in-memory trie cannot fail");
+	}
+	let nodes = db.drain().into_values().filter(|(_, rc)| *rc > 0).map(|(node, _)| node);
+	(root, StorageProof::new(nodes))
+}
+
+fn funded_caller<T: Config>() -> T::AccountId {
+	let caller: T::AccountId = whitelisted_caller();
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+	caller
+}
+
+/// Requests randomness as `who` and finalizes the requested block, so the request can be revealed.
+fn revealable_request<T: Config>(who: T::AccountId) -> Result<RequestId, DispatchError> {
+	let id = RelayRandomness::<T>::request(who, H256::zero())?;
+	let block = Requests::<T>::get(id).ok_or("request not stored")?.block;
+	frame_system::Pallet::<T>::set_block_number(block);
+	T::RelayState::set_relay_randomness([7; 32]);
+	RelayRandomness::<T>::on_finalize(block);
+	frame_system::Pallet::<T>::set_block_number(block + One::one());
+	Ok(id)
+}
+
+benchmarks! {
+	// A block with relay randomness and a pending request for it, so its randomness is kept.
+	on_finalize {
+		let now = frame_system::Pallet::<T>::block_number();
+		PendingRequests::<T>::insert(now, 1);
+		T::RelayState::set_relay_randomness([7; 32]);
+	}: {
+		RelayRandomness::<T>::on_finalize(now);
+	}
+	verify {
+		assert_eq!(BlockRandomness::<T>::get(now), Some(H256::repeat_byte(7)));
+	}
+
+	request_randomness {
+		let caller = funded_caller::<T>();
+	}: _(RawOrigin::Signed(caller), H256::zero())
+	verify {
+		assert!(Requests::<T>::contains_key(0));
+	}
+
+	// The last pending request of its block, which also removes the randomness of the block.
+	reveal_randomness {
+		let caller = funded_caller::<T>();
+		let id = revealable_request::<T>(caller.clone())?;
+	}: _(RawOrigin::Signed(caller), id)
+	verify {
+		assert!(!Requests::<T>::contains_key(id));
+	}
+
+	cancel_request {
+		let caller = funded_caller::<T>();
+		let id = RelayRandomness::<T>::request(caller.clone(), H256::zero())?;
+	}: _(RawOrigin::Signed(caller), id)
+	verify {
+		assert!(!Requests::<T>::contains_key(id));
+	}
+
+	impl_benchmark_test_suite!(RelayRandomness, crate::tests::new_test_ext(), crate::tests::Test);
+}
@@ -0,0 +1,336 @@
+//! # Relay chain randomness
+//!
+//! Randomness taken from the BABE VRF output of the relay chain. Every block reads the VRF output
+//! of its relay parent, `CURRENT_BLOCK_RANDOMNESS`, from the relay state proof of
+//! `cumulus_pallet_parachain_system`. The proof is checked against the relay parent storage root,
+//! so collators cannot choose the randomness, they can only see it before they build a block.
+//!
+//! The pallet implements [`Randomness`] with the randomness of the last block, like
+//! `pallet_insecure_randomness_collective_flip` did. That randomness is known to the collator of
+//! the current block. Whatever is worth manipulating, loot drops or matchmaking, commits first and
+//! reveals later:
+//!
+//! 1. [`Pallet::request_randomness`] commits to the randomness of the block
+//!    [`Config::RequestDelay`] blocks ahead, which nobody knows yet.
+//! 2. Once that block is built, [`Pallet::reveal_randomness`] returns the randomness of the
+//!    request, derived from the randomness of the block, the request id and the salt of the
+//!    request.
+//!
+//! Every request reserves [`Config::RequestDeposit`] from its owner until it is revealed or
+//! cancelled, so pending requests do not fill the storage for free.
+//!
+//! The randomness of a block is only kept while requests for it are pending. If a block has no
+//! relay randomness, which only happens if its relay state proof lacks the entry, its requests
+//! cannot be revealed and are cancelled with [`Pallet::cancel_request`].
+//!
+//! Pallets use [`Pallet::request`], [`Pallet::reveal`] and [`Pallet::cancel`] directly.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use cumulus_pallet_parachain_system::RelayChainStateProof;
+use cumulus_primitives_core::relay_chain::well_known_keys;
+use frame_support::{
+	ensure,
+	traits::{Currency, Get, Randomness, ReservableCurrency},
+};
+use scale_info::TypeInfo;
+use sp_core::H256;
+use sp_runtime::{
+	traits::{Hash, One, Saturating, Zero},
+	DispatchError, DispatchResult, RuntimeDebug,
+};
+#[cfg(feature = "runtime-benchmarks")]
+use sp_std::vec::Vec;
+
+mod benchmarking;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+/// Identifier of a randomness request.
+pub type RequestId = u64;
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+type RequestOf<T> = RandomnessRequest<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+>;
+
+/// A commitment to the randomness of a future block.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct RandomnessRequest<AccountId, Balance, BlockNumber> {
+	/// Account which may reveal or cancel the request.
+	pub owner: AccountId,
+	/// Amount reserved from `owner` until the request is removed.
+	pub deposit: Balance,
+	/// Block whose randomness is used.
+	pub block: BlockNumber,
+	/// Mixed into the randomness, so requests for the same block get different randomness.
+	pub salt: H256,
+}
+
+/// Provides the relay state proof of the current block.
+pub trait RelayStateProvider {
+	/// The relay state proof of the current block, `None` before the validation data is set or if
+	/// the proof does not match the relay parent storage root.
+	fn relay_chain_state() -> Option<RelayChainStateProof>;
+
+	/// Makes `relay_chain_state` return a proof holding `randomness` as the VRF output of the
+	/// relay parent.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_relay_randomness(randomness: [u8; 32]);
+}
+
+impl<T: cumulus_pallet_parachain_system::Config> RelayStateProvider
+	for cumulus_pallet_parachain_system::Pallet<T>
+{
+	fn relay_chain_state() -> Option<RelayChainStateProof> {
+		let root = Self::validation_data()?.relay_parent_storage_root;
+		let proof = Self::relay_state_proof()?;
+		RelayChainStateProof::new(T::SelfParaId::get(), root, proof).ok()
+	}
+
+	// Writes the validation data and relay state proof `set_validation_data` would set.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_relay_randomness(randomness: [u8; 32]) {
+		use cumulus_primitives_core::PersistedValidationData;
+		use frame_support::{storage::migration::put_storage_value, traits::PalletInfoAccess};
+
+		let (root, proof) = benchmarking::relay_state_proof(randomness);
+		let data = PersistedValidationData {
+			parent_head: Vec::new().into(),
+			relay_parent_number: Zero::zero(),
+			relay_parent_storage_root: root,
+			max_pov_size: Zero::zero(),
+		};
+		let pallet = <Self as PalletInfoAccess>::name().as_bytes();
+		put_storage_value(pallet, b"ValidationData", &[], data);
+		put_storage_value(pallet, b"RelayStateProof", &[], proof);
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Source of the relay state proof, `cumulus_pallet_parachain_system` in the runtime.
+		type RelayState: RelayStateProvider;
+
+		/// Currency of the request deposits.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// Deposit reserved from the owner of a request until it is revealed or cancelled.
+		#[pallet::constant]
+		type RequestDeposit: Get<BalanceOf<Self>>;
+
+		/// Number of blocks between a request and the block whose randomness it uses, at least
+		/// one.
+		#[pallet::constant]
+		type RequestDelay: Get<Self::BlockNumber>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The relay randomness of the last block which had one, and that block.
+	#[pallet::storage]
+	pub(super) type LatestRandomness<T: Config> =
+		StorageValue<_, (T::BlockNumber, H256), OptionQuery>;
+
+	/// The relay randomness of blocks with pending requests.
+	#[pallet::storage]
+	pub(super) type BlockRandomness<T: Config> =
+		StorageMap<_, Twox64Concat, T::BlockNumber, H256, OptionQuery>;
+
+	/// Number of pending requests for the randomness of a block.
+	#[pallet::storage]
+	pub(super) type PendingRequests<T: Config> =
+		StorageMap<_, Twox64Concat, T::BlockNumber, u32, ValueQuery>;
+
+	/// Pending requests.
+	#[pallet::storage]
+	pub(super) type Requests<T: Config> =
+		StorageMap<_, Twox64Concat, RequestId, RequestOf<T>, OptionQuery>;
+
+	/// Id of the next request.
+	#[pallet::storage]
+	pub(super) type NextRequestId<T: Config> = StorageValue<_, RequestId, ValueQuery>;
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The request does not exist.
+		UnknownRequest,
+		/// The request belongs to another account.
+		NotOwner,
+		/// The block of the request has not been built yet.
+		TooEarly,
+		/// The block of the request had no relay randomness.
+		RandomnessUnavailable,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// `who` requested the randomness of `block`.
+		RandomnessRequested { id: RequestId, who: T::AccountId, block: T::BlockNumber },
+		/// The randomness of a request was revealed, the request is removed.
+		RandomnessRevealed { id: RequestId, randomness: T::Hash },
+		/// A request was cancelled.
+		RequestCancelled { id: RequestId },
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_: T::BlockNumber) -> Weight {
+			T::WeightInfo::on_finalize()
+		}
+
+		// The relay state proof is set by the validation data inherent, after `on_initialize`.
+		fn on_finalize(now: T::BlockNumber) {
+			if let Some(randomness) = Self::relay_randomness() {
+				LatestRandomness::<T>::put((now, randomness));
+				if PendingRequests::<T>::get(now) > 0 {
+					BlockRandomness::<T>::insert(now, randomness);
+				}
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Request the randomness of the block `T::RequestDelay` blocks ahead, mixed with `salt`.
+		/// Reserves `T::RequestDeposit` until the request is revealed or cancelled.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// Emits `RandomnessRequested` if successful.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::request_randomness())]
+		pub fn request_randomness(origin: OriginFor<T>, salt: H256) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::request(who, salt).map(|_| ())
+		}
+
+		/// Reveal the randomness of request `id` and remove the request.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the owner of the request.
+		///
+		/// Emits `RandomnessRevealed` if successful.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::reveal_randomness())]
+		pub fn reveal_randomness(origin: OriginFor<T>, id: RequestId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::reveal(&who, id).map(|_| ())
+		}
+
+		/// Remove request `id` without revealing it.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the owner of the request.
+		///
+		/// Emits `RequestCancelled` if successful.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::cancel_request())]
+		pub fn cancel_request(origin: OriginFor<T>, id: RequestId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::cancel(&who, id)
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The relay randomness of the current block, `None` before the validation data is set.
+	pub fn relay_randomness() -> Option<H256> {
+		let proof = T::RelayState::relay_chain_state()?;
+		proof
+			.read_optional_entry::<Option<[u8; 32]>>(well_known_keys::CURRENT_BLOCK_RANDOMNESS)
+			.ok()
+			.flatten()
+			.flatten()
+			.map(H256::from)
+	}
+
+	/// Commit `who` to the randomness of the block `T::RequestDelay` blocks ahead, reserving
+	/// `T::RequestDeposit` from `who`.
+	pub fn request(who: T::AccountId, salt: H256) -> Result<RequestId, DispatchError> {
+		let deposit = T::RequestDeposit::get();
+		T::Currency::reserve(&who, deposit)?;
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let block = now.saturating_add(T::RequestDelay::get().max(One::one()));
+		let id = NextRequestId::<T>::get();
+		NextRequestId::<T>::put(id.wrapping_add(1));
+
+		PendingRequests::<T>::mutate(block, |pending| *pending = pending.saturating_add(1));
+		Requests::<T>::insert(id, RandomnessRequest { owner: who.clone(), deposit, block, salt });
+		Self::deposit_event(Event::RandomnessRequested { id, who, block });
+		Ok(id)
+	}
+
+	/// Reveal the randomness of request `id` owned by `who` and remove the request.
+	pub fn reveal(who: &T::AccountId, id: RequestId) -> Result<T::Hash, DispatchError> {
+		let request = Self::owned_request(who, id)?;
+		// The randomness of a block is recorded in its `on_finalize`.
+		ensure!(request.block < frame_system::Pallet::<T>::block_number(), Error::<T>::TooEarly);
+		let block_randomness =
+			BlockRandomness::<T>::get(request.block).ok_or(Error::<T>::RandomnessUnavailable)?;
+		let randomness = T::Hashing::hash_of(&(block_randomness, id, request.salt));
+
+		Self::remove_request(id, &request);
+		Self::deposit_event(Event::RandomnessRevealed { id, randomness });
+		Ok(randomness)
+	}
+
+	/// Remove request `id` owned by `who` without revealing it.
+	pub fn cancel(who: &T::AccountId, id: RequestId) -> DispatchResult {
+		let request = Self::owned_request(who, id)?;
+		Self::remove_request(id, &request);
+		Self::deposit_event(Event::RequestCancelled { id });
+		Ok(())
+	}
+
+	fn owned_request(who: &T::AccountId, id: RequestId) -> Result<RequestOf<T>, DispatchError> {
+		let request = Requests::<T>::get(id).ok_or(Error::<T>::UnknownRequest)?;
+		ensure!(request.owner == *who, Error::<T>::NotOwner);
+		Ok(request)
+	}
+
+	// The randomness of a block is dropped with its last pending request.
+	fn remove_request(id: RequestId, request: &RequestOf<T>) {
+		Requests::<T>::remove(id);
+		T::Currency::unreserve(&request.owner, request.deposit);
+		let pending = PendingRequests::<T>::get(request.block).saturating_sub(1);
+		if pending == 0 {
+			PendingRequests::<T>::remove(request.block);
+			BlockRandomness::<T>::remove(request.block);
+		} else {
+			PendingRequests::<T>::insert(request.block, pending);
+		}
+	}
+}
+
+impl<T: Config> Randomness<T::Hash, T::BlockNumber> for Pallet<T> {
+	/// The randomness of the last block with relay randomness mixed with `subject`, and that
+	/// block. The collator of the current block knows it, use requests for anything worth
+	/// manipulating.
+	fn random(subject: &[u8]) -> (T::Hash, T::BlockNumber) {
+		let (block, randomness) =
+			LatestRandomness::<T>::get().unwrap_or((Zero::zero(), H256::zero()));
+		(T::Hashing::hash_of(&(randomness, subject)), block)
+	}
+}
@@ -0,0 +1,282 @@
+// Tests for the relay randomness module, with relay state proofs built by the sproof builder
+
+use super::*;
+use crate as module_relay_randomness;
+
+use cumulus_test_relay_sproof_builder::RelayStateSproofBuilder;
+use frame_support::{
+	assert_noop, assert_ok, parameter_types,
+	traits::{ConstU32, ConstU64, Everything, Hooks},
+};
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+use sp_trie::StorageProof;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		RelayRandomness: module_relay_randomness::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+const ALICE: u64 = 1;
+const BOB: u64 = 2;
+const CHARLIE: u64 = 3;
+const PARA_ID: u32 = 2000;
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type RuntimeCall = RuntimeCall;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type Balance = u64;
+	type DustRemoval = ();
+	type RuntimeEvent = RuntimeEvent;
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+}
+
+parameter_types! {
+	/// Relay parent storage root and relay state proof of the current block.
+	pub static RelayState: Option<(H256, StorageProof)> = None;
+}
+pub struct MockRelayState;
+impl RelayStateProvider for MockRelayState {
+	fn relay_chain_state() -> Option<RelayChainStateProof> {
+		let (root, proof) = RelayState::get()?;
+		RelayChainStateProof::new(PARA_ID.into(), root, proof).ok()
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_relay_randomness(randomness: [u8; 32]) {
+		RelayState::set(Some(relay_state(Some(randomness))));
+	}
+}
+
+impl module_relay_randomness::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type RelayState = MockRelayState;
+	type Currency = Balances;
+	type RequestDeposit = ConstU64<10>;
+	type RequestDelay = ConstU64<2>;
+	type WeightInfo = ();
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(ALICE, 100), (BOB, 100)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// A relay state proof holding `randomness` as the VRF output of the relay parent.
+fn relay_state(randomness: Option<[u8; 32]>) -> (H256, StorageProof) {
+	let mut builder = RelayStateSproofBuilder { para_id: PARA_ID.into(), ..Default::default() };
+	if let Some(randomness) = randomness {
+		builder
+			.additional_key_values
+			.push((well_known_keys::CURRENT_BLOCK_RANDOMNESS.to_vec(), Some(randomness).encode()));
+	}
+	builder.into_state_root_and_proof()
+}
+
+/// Finalizes the current block with `randomness` in its relay state proof and starts the next.
+fn build_block(randomness: Option<[u8; 32]>) {
+	RelayState::set(Some(relay_state(randomness)));
+	RelayRandomness::on_finalize(System::block_number());
+	RelayState::set(None);
+	System::set_block_number(System::block_number() + 1);
+}
+
+fn mixed(randomness: [u8; 32], subject: impl Encode) -> H256 {
+	BlakeTwo256::hash_of(&(H256::from(randomness), subject))
+}
+
+#[test]
+fn relay_randomness_should_be_read_from_the_proof() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(RelayRandomness::relay_randomness(), None);
+
+		RelayState::set(Some(relay_state(Some([7; 32]))));
+		assert_eq!(RelayRandomness::relay_randomness(), Some(H256::from([7; 32])));
+
+		RelayState::set(Some(relay_state(None)));
+		assert_eq!(RelayRandomness::relay_randomness(), None);
+	});
+}
+
+#[test]
+fn proofs_not_matching_the_storage_root_should_be_ignored() {
+	new_test_ext().execute_with(|| {
+		let (_, proof) = relay_state(Some([7; 32]));
+		let (root, _) = relay_state(Some([8; 32]));
+		RelayState::set(Some((root, proof)));
+		assert_eq!(RelayRandomness::relay_randomness(), None);
+	});
+}
+
+#[test]
+fn random_should_mix_the_latest_relay_randomness_with_the_subject() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(RelayRandomness::random(b"loot"), (mixed([0; 32], &b"loot"[..]), 0));
+
+		build_block(Some([7; 32]));
+		assert_eq!(RelayRandomness::random(b"loot"), (mixed([7; 32], &b"loot"[..]), 1));
+		assert_ne!(RelayRandomness::random(b"loot").0, RelayRandomness::random(b"match").0);
+
+		// A block without relay randomness keeps the randomness of the last block which had one.
+		build_block(None);
+		assert_eq!(RelayRandomness::random(b"loot"), (mixed([7; 32], &b"loot"[..]), 1));
+
+		build_block(Some([9; 32]));
+		assert_eq!(RelayRandomness::random(b"loot"), (mixed([9; 32], &b"loot"[..]), 3));
+	});
+}
+
+#[test]
+fn requests_should_reveal_the_randomness_of_a_future_block() {
+	new_test_ext().execute_with(|| {
+		let salt = H256::repeat_byte(1);
+		assert_ok!(RelayRandomness::request_randomness(RuntimeOrigin::signed(ALICE), salt));
+		System::assert_last_event(
+			Event::RandomnessRequested { id: 0, who: ALICE, block: 3 }.into(),
+		);
+		assert_noop!(
+			RelayRandomness::reveal_randomness(RuntimeOrigin::signed(ALICE), 0),
+			Error::<Test>::TooEarly
+		);
+
+		build_block(Some([1; 32]));
+		build_block(Some([2; 32]));
+		assert_noop!(
+			RelayRandomness::reveal_randomness(RuntimeOrigin::signed(ALICE), 0),
+			Error::<Test>::TooEarly
+		);
+		assert_eq!(BlockRandomness::<Test>::get(1), None);
+
+		build_block(Some([3; 32]));
+		assert_eq!(BlockRandomness::<Test>::get(3), Some(H256::from([3; 32])));
+		assert_noop!(
+			RelayRandomness::reveal_randomness(RuntimeOrigin::signed(BOB), 0),
+			Error::<Test>::NotOwner
+		);
+		assert_ok!(RelayRandomness::reveal_randomness(RuntimeOrigin::signed(ALICE), 0));
+		System::assert_last_event(
+			Event::RandomnessRevealed { id: 0, randomness: mixed([3; 32], (0u64, salt)) }.into(),
+		);
+
+		assert_noop!(
+			RelayRandomness::reveal_randomness(RuntimeOrigin::signed(ALICE), 0),
+			Error::<Test>::UnknownRequest
+		);
+		assert_eq!(BlockRandomness::<Test>::get(3), None);
+		assert_eq!(PendingRequests::<Test>::get(3), 0);
+	});
+}
+
+#[test]
+fn requests_for_the_same_block_should_get_different_randomness() {
+	new_test_ext().execute_with(|| {
+		let salt = H256::repeat_byte(1);
+		assert_eq!(RelayRandomness::request(ALICE, salt), Ok(0));
+		assert_eq!(RelayRandomness::request(BOB, salt), Ok(1));
+		assert_eq!(PendingRequests::<Test>::get(3), 2);
+
+		build_block(Some([1; 32]));
+		build_block(Some([2; 32]));
+		build_block(Some([3; 32]));
+
+		let alice = RelayRandomness::reveal(&ALICE, 0).unwrap();
+		assert_eq!(alice, mixed([3; 32], (0u64, salt)));
+		// The randomness of the block is kept for the pending request of BOB.
+		assert_eq!(BlockRandomness::<Test>::get(3), Some(H256::from([3; 32])));
+
+		let bob = RelayRandomness::reveal(&BOB, 1).unwrap();
+		assert_eq!(bob, mixed([3; 32], (1u64, salt)));
+		assert_ne!(alice, bob);
+		assert_eq!(BlockRandomness::<Test>::get(3), None);
+	});
+}
+
+#[test]
+fn requests_for_blocks_without_relay_randomness_should_be_cancelled() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(RelayRandomness::request_randomness(RuntimeOrigin::signed(ALICE), H256::zero()));
+		build_block(Some([1; 32]));
+		build_block(Some([2; 32]));
+		build_block(None);
+
+		assert_noop!(RelayRandomness::reveal(&ALICE, 0), Error::<Test>::RandomnessUnavailable);
+		assert_noop!(
+			RelayRandomness::cancel_request(RuntimeOrigin::signed(BOB), 0),
+			Error::<Test>::NotOwner
+		);
+		assert_ok!(RelayRandomness::cancel_request(RuntimeOrigin::signed(ALICE), 0));
+		System::assert_last_event(Event::RequestCancelled { id: 0 }.into());
+		assert_eq!(Requests::<Test>::get(0), None);
+		assert_eq!(PendingRequests::<Test>::get(3), 0);
+	});
+}
+
+#[test]
+fn requests_should_reserve_a_deposit_until_removed() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(RelayRandomness::request(ALICE, H256::zero()), Ok(0));
+		assert_eq!(RelayRandomness::request(ALICE, H256::zero()), Ok(1));
+		assert_eq!(Balances::reserved_balance(ALICE), 20);
+		assert_noop!(
+			RelayRandomness::request_randomness(RuntimeOrigin::signed(CHARLIE), H256::zero()),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+
+		assert_ok!(RelayRandomness::cancel(&ALICE, 0));
+		assert_eq!(Balances::reserved_balance(ALICE), 10);
+
+		build_block(Some([1; 32]));
+		build_block(Some([2; 32]));
+		build_block(Some([3; 32]));
+		assert_ok!(RelayRandomness::reveal(&ALICE, 1));
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::free_balance(ALICE), 100);
+	});
+}
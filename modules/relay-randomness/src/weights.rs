@@ -0,0 +1,91 @@
+//! Weights for module_relay_randomness
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for module_relay_randomness.
+pub trait WeightInfo {
+	fn on_finalize() -> Weight;
+	fn request_randomness() -> Weight;
+	fn reveal_randomness() -> Weight;
+	fn cancel_request() -> Weight;
+}
+
+/// Weights for module_relay_randomness using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: ParachainSystem ValidationData (r:1 w:0)
+	// Storage: ParachainSystem RelayStateProof (r:1 w:0)
+	// Storage: ParachainInfo ParachainId (r:1 w:0)
+	// Storage: RelayRandomness PendingRequests (r:1 w:0)
+	// Storage: RelayRandomness LatestRandomness (r:0 w:1)
+	// Storage: RelayRandomness BlockRandomness (r:0 w:1)
+	// Estimated, not benchmarked yet.
+	fn on_finalize() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	// Storage: System Account (r:1 w:1)
+	// Storage: RelayRandomness NextRequestId (r:1 w:1)
+	// Storage: RelayRandomness PendingRequests (r:1 w:1)
+	// Storage: RelayRandomness Requests (r:0 w:1)
+	// Estimated, not benchmarked yet.
+	fn request_randomness() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	// Storage: RelayRandomness Requests (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: RelayRandomness BlockRandomness (r:1 w:1)
+	// Storage: RelayRandomness PendingRequests (r:1 w:1)
+	// Estimated, not benchmarked yet.
+	fn reveal_randomness() -> Weight {
+		Weight::from_parts(18_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	// Storage: RelayRandomness Requests (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: RelayRandomness PendingRequests (r:1 w:1)
+	// Storage: RelayRandomness BlockRandomness (r:0 w:1)
+	// Estimated, not benchmarked yet.
+	fn cancel_request() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Estimated, not benchmarked yet.
+	fn on_finalize() -> Weight {
+		Weight::from_parts(25_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	// Estimated, not benchmarked yet.
+	fn request_randomness() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().writes(4))
+	}
+	// Estimated, not benchmarked yet.
+	fn reveal_randomness() -> Weight {
+		Weight::from_parts(18_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4))
+			.saturating_add(RocksDbWeight::get().writes(4))
+	}
+	// Estimated, not benchmarked yet.
+	fn cancel_request() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().writes(4))
+	}
+}
@@ -0,0 +1,23 @@
+//! Runtime API definition for the currency transaction payment module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_runtime::traits::MaybeDisplay;
+
+sp_api::decl_runtime_apis! {
+	pub trait TxPaymentApi<Balance, CurrencyId> where
+		Balance: Codec + MaybeDisplay,
+		CurrencyId: Codec,
+	{
+		/// Quote the partial fee of `uxt` in `currency_id`, `None` if fees cannot be paid in it.
+		fn query_fee_in_currency(
+			uxt: Block::Extrinsic,
+			len: u32,
+			currency_id: CurrencyId,
+		) -> Option<Balance>;
+
+		/// Convert a fee given in the native currency to `currency_id`.
+		fn quote_fee(native_fee: Balance, currency_id: CurrencyId) -> Option<Balance>;
+	}
+}
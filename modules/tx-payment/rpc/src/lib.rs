@@ -0,0 +1,157 @@
+//! RPC interface for the currency transaction payment module.
+
+use std::{convert::TryInto, sync::Arc};
+
+use codec::{Codec, Decode};
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorCode, ErrorObject},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::{Block as BlockT, MaybeDisplay};
+
+pub use module_tx_payment_rpc_runtime_api::TxPaymentApi as TxPaymentRuntimeApi;
+
+#[rpc(client, server)]
+pub trait TxPaymentApi<BlockHash, CurrencyId> {
+	/// Quote the partial fee of an encoded extrinsic in `currency_id`.
+	#[method(name = "txPayment_queryFeeInCurrency")]
+	fn query_fee_in_currency(
+		&self,
+		encoded_xt: Bytes,
+		currency_id: CurrencyId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<NumberOrHex>>;
+
+	/// Convert a fee given in the native currency to `currency_id`.
+	#[method(name = "txPayment_quoteFee")]
+	fn quote_fee(
+		&self,
+		native_fee: NumberOrHex,
+		currency_id: CurrencyId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<NumberOrHex>>;
+}
+
+/// Provides RPC methods to quote transaction fees in any currency.
+///
+/// `Balance` is the balance type of the runtime API, which the RPC methods do not expose.
+pub struct TxPayment<C, P, Balance> {
+	/// Shared reference to the client.
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<(P, Balance)>,
+}
+
+impl<C, P, Balance> TxPayment<C, P, Balance> {
+	/// Creates a new instance of the TxPayment Rpc helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The transaction was not decodable.
+	DecodeError,
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::DecodeError => 2,
+		}
+	}
+}
+
+fn runtime_error(e: impl std::fmt::Display) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(
+		Error::RuntimeError.into(),
+		"Unable to quote fee.",
+		Some(e.to_string()),
+	))
+	.into()
+}
+
+fn into_number_or_hex<Balance: TryInto<NumberOrHex> + Copy + MaybeDisplay>(
+	value: Balance,
+) -> RpcResult<NumberOrHex> {
+	value.try_into().map_err(|_| {
+		CallError::Custom(ErrorObject::owned(
+			ErrorCode::InvalidParams.code(),
+			format!("{} doesn't fit in NumberOrHex representation", value),
+			None::<()>,
+		))
+		.into()
+	})
+}
+
+#[async_trait]
+impl<C, Block, Balance, CurrencyId> TxPaymentApiServer<<Block as BlockT>::Hash, CurrencyId>
+	for TxPayment<C, Block, Balance>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: TxPaymentRuntimeApi<Block, Balance, CurrencyId>,
+	Balance:
+		Codec + MaybeDisplay + Copy + TryInto<NumberOrHex> + TryFrom<u128> + Send + Sync + 'static,
+	CurrencyId: Codec + Send + Sync + 'static + serde::de::DeserializeOwned,
+{
+	fn query_fee_in_currency(
+		&self,
+		encoded_xt: Bytes,
+		currency_id: CurrencyId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<NumberOrHex>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let encoded_len = encoded_xt.len() as u32;
+		let uxt: Block::Extrinsic = Decode::decode(&mut &*encoded_xt).map_err(|e| {
+			CallError::Custom(ErrorObject::owned(
+				Error::DecodeError.into(),
+				"Unable to quote fee.",
+				Some(format!("{:?}", e)),
+			))
+		})?;
+
+		api.query_fee_in_currency(at_hash, uxt, encoded_len, currency_id)
+			.map_err(runtime_error)?
+			.map(into_number_or_hex)
+			.transpose()
+	}
+
+	fn quote_fee(
+		&self,
+		native_fee: NumberOrHex,
+		currency_id: CurrencyId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<NumberOrHex>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let native_fee: Balance = native_fee
+			.into_u256()
+			.try_into()
+			.ok()
+			.and_then(|fee: u128| Balance::try_from(fee).ok())
+			.ok_or_else(|| {
+				CallError::Custom(ErrorObject::owned(
+					ErrorCode::InvalidParams.code(),
+					"native_fee doesn't fit in the runtime balance type",
+					None::<()>,
+				))
+			})?;
+
+		api.quote_fee(at_hash, native_fee, currency_id)
+			.map_err(runtime_error)?
+			.map(into_number_or_hex)
+			.transpose()
+	}
+}
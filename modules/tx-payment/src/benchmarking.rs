@@ -0,0 +1,23 @@
+//! Transaction payment module benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use crate::Pallet as TxPayment;
+use frame_benchmarking::benchmarks;
+
+benchmarks! {
+	set_fee_split {
+		let origin =
+			T::UpdateOrigin::try_successful_origin().map_err(|_| "UpdateOrigin has no successful origin")?;
+		let split = FeeSplit { treasury: Percent::from_percent(60), author: Percent::from_percent(40) };
+	}: _<T::RuntimeOrigin>(origin, FeeSource::TransactionFee, split)
+	verify {
+		frame_system::Pallet::<T>::assert_last_event(
+			<T as Config>::RuntimeEvent::from(Event::<T>::FeeSplitSet { source: FeeSource::TransactionFee, split }).into(),
+		);
+	}
+
+	impl_benchmark_test_suite!(TxPayment, crate::tests::new_test_ext(), crate::tests::Test);
+}
@@ -0,0 +1,553 @@
+//! # Transaction payment in any currency
+//!
+//! Extends `pallet_transaction_payment` so that signers may pick the currency their transaction
+//! fee is paid in. The fee is always computed in the native currency first and then converted
+//! through [`Config::FeeRate`], so weight-to-fee, length-to-fee and the fee multiplier behave
+//! exactly the same for every currency.
+//!
+//! The pallet does not replace `pallet_transaction_payment`, it only provides the
+//! [`ChargeCurrencyTxPayment`] signed extension which takes the place of
+//! `pallet_transaction_payment::ChargeTransactionPayment` in the runtime's `SignedExtra`. Fees
+//! paid in the native currency still go through the configured `OnChargeTransaction`.
+//!
+//! Collected fees, tips and XCM revenue are split between the treasury, the block author and
+//! burning. The split is kept in storage per [`FeeSource`] and can be changed by
+//! [`Config::UpdateOrigin`]; [`DealWithFees`] applies it to native imbalances and
+//! [`Pallet::distribute`] to amounts in any other currency.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	dispatch::{DispatchInfo, GetDispatchInfo, PostDispatchInfo},
+	traits::{Currency, Get, Imbalance, OnUnbalanced},
+};
+use orml_traits::{GetByKey, MultiCurrency};
+use pallet_transaction_payment::OnChargeTransaction;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{
+		CheckedSub, DispatchInfoOf, Dispatchable, Extrinsic as ExtrinsicT, PostDispatchInfoOf,
+		Saturating, SignedExtension, Zero,
+	},
+	transaction_validity::{
+		InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+	},
+	FixedPointOperand, PerThing, Percent, RuntimeDebug,
+};
+use sp_std::marker::PhantomData;
+
+mod benchmarking;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+/// Balance type of the transaction payment pallet, shared by every currency.
+pub type BalanceOf<T> = <<T as pallet_transaction_payment::Config>::OnChargeTransaction as OnChargeTransaction<T>>::Balance;
+type LiquidityInfoOf<T> = <<T as pallet_transaction_payment::Config>::OnChargeTransaction as OnChargeTransaction<T>>::LiquidityInfo;
+type NegativeImbalanceOf<T> = <<T as Config>::NativeCurrency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::NegativeImbalance;
+
+/// Where collected funds come from.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum FeeSource {
+	/// The inclusion fee of a transaction, without the tip.
+	TransactionFee,
+	/// The tip of a transaction.
+	Tip,
+	/// Fees paid for XCM execution.
+	XcmRevenue,
+}
+
+/// How collected funds are shared. Whatever is not given to the treasury or the block author is
+/// burned.
+#[derive(
+	Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub struct FeeSplit {
+	/// Share deposited into the treasury.
+	pub treasury: Percent,
+	/// Share deposited to the block author. Goes to the treasury if there is no author.
+	pub author: Percent,
+}
+
+impl FeeSplit {
+	/// The share that is burned.
+	pub fn burn(&self) -> Percent {
+		Percent::one().saturating_sub(self.treasury).saturating_sub(self.author)
+	}
+
+	/// Whether the treasury and author shares do not exceed the whole.
+	pub fn is_valid(&self) -> bool {
+		self.treasury.deconstruct().saturating_add(self.author.deconstruct()) <= 100
+	}
+
+	/// Split `amount` into the treasury and author shares; the rest is to be burned.
+	pub fn shares<Balance>(&self, amount: Balance) -> (Balance, Balance)
+	where
+		Balance: sp_runtime::traits::AtLeast32BitUnsigned + Copy,
+	{
+		let to_treasury = self.treasury.mul_floor(amount);
+		let to_author = self.author.mul_floor(amount).min(amount.saturating_sub(to_treasury));
+		(to_treasury, to_author)
+	}
+}
+
+/// Converts a fee quoted in the native currency into the amount due in another currency.
+pub trait FeeRate<CurrencyId, Balance> {
+	/// Returns `None` if fees cannot be paid in `currency_id`.
+	fn convert(currency_id: CurrencyId, native_fee: Balance) -> Option<Balance>;
+}
+
+/// What has been withdrawn from the signer in `pre_dispatch`.
+pub enum InitialPayment<T: Config> {
+	/// No initial fee was paid.
+	Nothing,
+	/// The initial fee was paid in the native currency.
+	Native(LiquidityInfoOf<T>),
+	/// The initial fee was paid in another currency.
+	Currency(T::CurrencyId, BalanceOf<T>),
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config:
+		frame_system::Config + pallet_transaction_payment::Config + pallet_authorship::Config
+	{
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Identifier of a currency fees may be paid in.
+		type CurrencyId: Parameter + Member + Copy + MaybeSerializeDeserialize + Ord + MaxEncodedLen;
+
+		/// The currencies fees are withdrawn from.
+		type MultiCurrency: MultiCurrency<
+			Self::AccountId,
+			CurrencyId = Self::CurrencyId,
+			Balance = BalanceOf<Self>,
+		>;
+
+		/// The native currency, paid through `pallet_transaction_payment::OnChargeTransaction`.
+		#[pallet::constant]
+		type NativeCurrencyId: Get<Self::CurrencyId>;
+
+		/// Conversion of native fees into other currencies.
+		type FeeRate: FeeRate<Self::CurrencyId, BalanceOf<Self>>;
+
+		/// The native currency, used to resolve the imbalances handed to [`DealWithFees`].
+		type NativeCurrency: Currency<Self::AccountId, Balance = BalanceOf<Self>>;
+
+		/// Account receiving the treasury share of collected funds.
+		#[pallet::constant]
+		type TreasuryAccount: Get<Self::AccountId>;
+
+		/// The split used for a source as long as governance has not set one.
+		type DefaultFeeSplit: GetByKey<FeeSource, FeeSplit>;
+
+		/// The origin which may change the fee split.
+		type UpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The split set by governance for each source of funds.
+	#[pallet::storage]
+	pub(super) type FeeSplits<T: Config> =
+		StorageMap<_, Twox64Concat, FeeSource, FeeSplit, OptionQuery>;
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Treasury and author shares add up to more than 100%.
+		InvalidFeeSplit,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A transaction fee `actual_fee`, of which `tip` was added to the minimum inclusion fee,
+		/// has been paid by `who` in `currency_id`.
+		CurrencyTxFeePaid {
+			who: T::AccountId,
+			currency_id: T::CurrencyId,
+			actual_fee: BalanceOf<T>,
+			tip: BalanceOf<T>,
+		},
+		/// The split of a source of funds was changed.
+		FeeSplitSet { source: FeeSource, split: FeeSplit },
+		/// Collected funds were distributed.
+		FeeDistributed {
+			source: FeeSource,
+			currency_id: T::CurrencyId,
+			to_treasury: BalanceOf<T>,
+			to_author: BalanceOf<T>,
+			burned: BalanceOf<T>,
+		},
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set how funds collected from `source` are split.
+		///
+		/// The dispatch origin for this call must be `T::UpdateOrigin`.
+		///
+		/// Emits `FeeSplitSet` if successful.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::set_fee_split())]
+		pub fn set_fee_split(
+			origin: OriginFor<T>,
+			source: FeeSource,
+			split: FeeSplit,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(split.is_valid(), Error::<T>::InvalidFeeSplit);
+
+			FeeSplits::<T>::insert(source, split);
+			Self::deposit_event(Event::FeeSplitSet { source, split });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The split currently applied to `source`.
+	pub fn fee_split(source: FeeSource) -> FeeSplit {
+		FeeSplits::<T>::get(source).unwrap_or_else(|| T::DefaultFeeSplit::get(&source))
+	}
+
+	/// Distribute `amount` of `currency_id`, which must already have been taken out of
+	/// circulation, between the treasury and the block author. The burned share is simply not
+	/// deposited anywhere.
+	///
+	/// A share which cannot be deposited, e.g. because it is below the existential deposit of a
+	/// new account, goes to the treasury if it is the author's and is burned otherwise.
+	pub fn distribute(source: FeeSource, currency_id: T::CurrencyId, amount: BalanceOf<T>) {
+		if amount.is_zero() {
+			return
+		}
+		let (mut to_treasury, mut to_author) = Self::fee_split(source).shares(amount);
+		let mut burned = amount.saturating_sub(to_treasury).saturating_sub(to_author);
+
+		let author_paid = match pallet_authorship::Pallet::<T>::author() {
+			Some(author) =>
+				to_author.is_zero() ||
+					T::MultiCurrency::deposit(currency_id, &author, to_author).is_ok(),
+			None => false,
+		};
+		if !author_paid {
+			to_treasury = to_treasury.saturating_add(to_author);
+			to_author = Zero::zero();
+		}
+		if !to_treasury.is_zero() &&
+			T::MultiCurrency::deposit(currency_id, &T::TreasuryAccount::get(), to_treasury)
+				.is_err()
+		{
+			burned = burned.saturating_add(to_treasury);
+			to_treasury = Zero::zero();
+		}
+
+		Self::deposit_event(Event::FeeDistributed {
+			source,
+			currency_id,
+			to_treasury,
+			to_author,
+			burned,
+		});
+	}
+
+	/// Same as [`Self::distribute`] for an imbalance of the native currency. Dropping the burned
+	/// share reduces the total issuance.
+	fn distribute_imbalance(source: FeeSource, imbalance: NegativeImbalanceOf<T>) {
+		let amount = imbalance.peek();
+		if amount.is_zero() {
+			return
+		}
+		let (to_treasury, to_author) = Self::fee_split(source).shares(amount);
+		let (mut treasury_imbalance, rest) = imbalance.split(to_treasury);
+		let (author_imbalance, burned) = rest.split(to_author);
+
+		let to_author = match pallet_authorship::Pallet::<T>::author() {
+			Some(author) => {
+				T::NativeCurrency::resolve_creating(&author, author_imbalance);
+				to_author
+			},
+			None => {
+				treasury_imbalance.subsume(author_imbalance);
+				Zero::zero()
+			},
+		};
+		let to_treasury = treasury_imbalance.peek();
+		T::NativeCurrency::resolve_creating(&T::TreasuryAccount::get(), treasury_imbalance);
+
+		Self::deposit_event(Event::FeeDistributed {
+			source,
+			currency_id: T::NativeCurrencyId::get(),
+			to_treasury,
+			to_author,
+			burned: burned.peek(),
+		});
+	}
+}
+
+/// Splits native transaction fees and tips according to [`Pallet::fee_split`]. Meant to be used
+/// as the `OnUnbalanced` handler of `pallet_transaction_payment::CurrencyAdapter`.
+pub struct DealWithFees<T>(PhantomData<T>);
+impl<T: Config> OnUnbalanced<NegativeImbalanceOf<T>> for DealWithFees<T> {
+	fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalanceOf<T>>) {
+		if let Some(fees) = fees_then_tips.next() {
+			Pallet::<T>::distribute_imbalance(FeeSource::TransactionFee, fees);
+			if let Some(tips) = fees_then_tips.next() {
+				Pallet::<T>::distribute_imbalance(FeeSource::Tip, tips);
+			}
+		}
+	}
+}
+
+impl<T: Config> Pallet<T>
+where
+	BalanceOf<T>: FixedPointOperand,
+{
+	/// Quote a fee given in the native currency in `currency_id`.
+	///
+	/// Returns `None` if fees cannot be paid in `currency_id`.
+	pub fn convert_fee(
+		currency_id: T::CurrencyId,
+		native_fee: BalanceOf<T>,
+	) -> Option<BalanceOf<T>> {
+		if currency_id == T::NativeCurrencyId::get() {
+			Some(native_fee)
+		} else {
+			T::FeeRate::convert(currency_id, native_fee)
+		}
+	}
+
+	/// Query the partial fee of `uxt` in `currency_id`.
+	///
+	/// The tip is not included, same as in `pallet_transaction_payment::Pallet::query_info`.
+	pub fn query_fee_in_currency<Extrinsic: ExtrinsicT + GetDispatchInfo>(
+		uxt: Extrinsic,
+		len: u32,
+		currency_id: T::CurrencyId,
+	) -> Option<BalanceOf<T>>
+	where
+		T::RuntimeCall: Dispatchable<Info = DispatchInfo>,
+	{
+		let native_fee = pallet_transaction_payment::Pallet::<T>::query_info(uxt, len).partial_fee;
+		Self::convert_fee(currency_id, native_fee)
+	}
+
+	/// Withdraw `fee`, converted to `currency_id`, from `who`.
+	///
+	/// The account is kept alive, same as `pallet_transaction_payment::CurrencyAdapter` does.
+	fn withdraw_currency_fee(
+		who: &T::AccountId,
+		currency_id: T::CurrencyId,
+		fee: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, TransactionValidityError> {
+		let converted = Self::convert_fee(currency_id, fee).ok_or(InvalidTransaction::Payment)?;
+		let remaining = T::MultiCurrency::free_balance(currency_id, who)
+			.checked_sub(&converted)
+			.ok_or(InvalidTransaction::Payment)?;
+		if remaining < T::MultiCurrency::minimum_balance(currency_id) {
+			return Err(InvalidTransaction::Payment.into())
+		}
+		T::MultiCurrency::withdraw(currency_id, who, converted)
+			.map_err(|_| InvalidTransaction::Payment)?;
+		Ok(converted)
+	}
+
+	/// Refund the part of `already_withdrawn` exceeding `corrected_fee` to `who`.
+	///
+	/// Returns the converted fee and tip that were finally paid.
+	fn correct_currency_fee(
+		who: &T::AccountId,
+		currency_id: T::CurrencyId,
+		corrected_fee: BalanceOf<T>,
+		tip: BalanceOf<T>,
+		already_withdrawn: BalanceOf<T>,
+	) -> Result<(BalanceOf<T>, BalanceOf<T>), TransactionValidityError> {
+		let converted_fee = Self::convert_fee(currency_id, corrected_fee)
+			.ok_or(InvalidTransaction::Payment)?
+			.min(already_withdrawn);
+		let refund = already_withdrawn.saturating_sub(converted_fee);
+		if !refund.is_zero() {
+			T::MultiCurrency::deposit(currency_id, who, refund)
+				.map_err(|_| InvalidTransaction::Payment)?;
+		}
+		let converted_tip =
+			Self::convert_fee(currency_id, tip).unwrap_or_else(Zero::zero).min(converted_fee);
+		Ok((converted_fee, converted_tip))
+	}
+}
+
+/// Require the transactor pay for themselves and maybe include a tip to gain additional priority
+/// in the queue. Allows paying in any currency accepted by [`Config::FeeRate`]; `None` pays in the
+/// native currency.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct ChargeCurrencyTxPayment<T: Config> {
+	#[codec(compact)]
+	tip: BalanceOf<T>,
+	currency_id: Option<T::CurrencyId>,
+}
+
+impl<T: Config> ChargeCurrencyTxPayment<T>
+where
+	T::RuntimeCall: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+	BalanceOf<T>: Send + Sync + FixedPointOperand,
+{
+	/// Utility constructor. Used only in client/factory code.
+	pub fn from(tip: BalanceOf<T>, currency_id: Option<T::CurrencyId>) -> Self {
+		Self { tip, currency_id }
+	}
+
+	/// Fee withdrawal logic that dispatches to either `OnChargeTransaction` or the pallet's own
+	/// currency handling.
+	fn withdraw_fee(
+		&self,
+		who: &T::AccountId,
+		call: &T::RuntimeCall,
+		info: &DispatchInfoOf<T::RuntimeCall>,
+		len: usize,
+	) -> Result<(BalanceOf<T>, InitialPayment<T>), TransactionValidityError> {
+		let fee = pallet_transaction_payment::Pallet::<T>::compute_fee(len as u32, info, self.tip);
+		debug_assert!(self.tip <= fee, "tip should be included in the computed fee");
+		if fee.is_zero() {
+			return Ok((fee, InitialPayment::Nothing))
+		}
+
+		match self.currency_id {
+			Some(currency_id) if currency_id != T::NativeCurrencyId::get() =>
+				Pallet::<T>::withdraw_currency_fee(who, currency_id, fee)
+					.map(|paid| (fee, InitialPayment::Currency(currency_id, paid))),
+			_ => <T::OnChargeTransaction as OnChargeTransaction<T>>::withdraw_fee(
+				who, call, info, fee, self.tip,
+			)
+			.map(|i| (fee, InitialPayment::Native(i))),
+		}
+	}
+}
+
+impl<T: Config> sp_std::fmt::Debug for ChargeCurrencyTxPayment<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "ChargeCurrencyTxPayment<{:?}, {:?}>", self.tip, self.currency_id.encode())
+	}
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Config> SignedExtension for ChargeCurrencyTxPayment<T>
+where
+	T::RuntimeCall: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+	BalanceOf<T>: Send + Sync + From<u64> + FixedPointOperand,
+	T::CurrencyId: Send + Sync,
+{
+	const IDENTIFIER: &'static str = "ChargeCurrencyTxPayment";
+	type AccountId = T::AccountId;
+	type Call = T::RuntimeCall;
+	type AdditionalSigned = ();
+	type Pre = (
+		// tip
+		BalanceOf<T>,
+		// who paid the fee
+		Self::AccountId,
+		// what has been withdrawn to pay the fee
+		InitialPayment<T>,
+	);
+
+	fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> TransactionValidity {
+		use pallet_transaction_payment::ChargeTransactionPayment;
+		let (fee, _) = self.withdraw_fee(who, call, info, len)?;
+		let priority = ChargeTransactionPayment::<T>::get_priority(info, len, self.tip, fee);
+		Ok(ValidTransaction { priority, ..Default::default() })
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		let (_fee, initial_payment) = self.withdraw_fee(who, call, info, len)?;
+		Ok((self.tip, who.clone(), initial_payment))
+	}
+
+	fn post_dispatch(
+		pre: Option<Self::Pre>,
+		info: &DispatchInfoOf<Self::Call>,
+		post_info: &PostDispatchInfoOf<Self::Call>,
+		len: usize,
+		result: &sp_runtime::DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		if let Some((tip, who, initial_payment)) = pre {
+			match initial_payment {
+				InitialPayment::Native(already_withdrawn) => {
+					pallet_transaction_payment::ChargeTransactionPayment::<T>::post_dispatch(
+						Some((tip, who, already_withdrawn)),
+						info,
+						post_info,
+						len,
+						result,
+					)?;
+				},
+				InitialPayment::Currency(currency_id, already_withdrawn) => {
+					let actual_fee = pallet_transaction_payment::Pallet::<T>::compute_actual_fee(
+						len as u32, info, post_info, tip,
+					);
+					let (actual_fee, tip) = Pallet::<T>::correct_currency_fee(
+						&who,
+						currency_id,
+						actual_fee,
+						tip,
+						already_withdrawn,
+					)?;
+					Pallet::<T>::distribute(
+						FeeSource::TransactionFee,
+						currency_id,
+						actual_fee.saturating_sub(tip),
+					);
+					Pallet::<T>::distribute(FeeSource::Tip, currency_id, tip);
+					Pallet::<T>::deposit_event(Event::<T>::CurrencyTxFeePaid {
+						who,
+						currency_id,
+						actual_fee,
+						tip,
+					});
+				},
+				InitialPayment::Nothing => {
+					// `actual_fee` should be zero here for any signed extrinsic. It would be
+					// non-zero here in case of unsigned extrinsics as they don't pay fees but
+					// `compute_actual_fee` is not aware of them.
+					debug_assert!(tip.is_zero(), "tip should be zero if initial fee was zero.");
+				},
+			}
+		}
+
+		Ok(())
+	}
+}
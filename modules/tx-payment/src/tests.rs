@@ -0,0 +1,496 @@
+// Tests for the transaction payment module
+
+use super::*;
+use crate as module_tx_payment;
+
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::DispatchClass,
+	parameter_types,
+	traits::{ConstU32, ConstU8, Everything, ExistenceRequirement, FindAuthor, WithdrawReasons},
+	weights::{IdentityFee, Weight},
+};
+use frame_system::EnsureRoot;
+use orml_traits::parameter_type_with_key;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BadOrigin, BlakeTwo256, IdentityLookup},
+	ConsensusEngineId,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Authorship: pallet_authorship::{Pallet, Storage},
+		TransactionPayment: pallet_transaction_payment::{Pallet, Storage, Event<T>},
+		Tokens: orml_tokens::{Pallet, Storage, Config<T>, Event<T>},
+		Currencies: orml_currencies::{Pallet, Call},
+		TxPayment: module_tx_payment::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+const ALICE: u64 = 1;
+const AUTHOR: u64 = 10;
+const TREASURY: u64 = 100;
+
+const NATIVE: u32 = 0;
+const DOT: u32 = 1;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub BlockWeights: frame_system::limits::BlockWeights =
+		frame_system::limits::BlockWeights::simple_max(Weight::from_parts(1024, u64::MAX));
+}
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = BlockWeights;
+	type BlockLength = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type RuntimeCall = RuntimeCall;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = BlockHashCount;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+impl pallet_balances::Config for Test {
+	type Balance = u64;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub static BlockAuthor: Option<u64> = Some(AUTHOR);
+}
+pub struct AuthorGiven;
+impl FindAuthor<u64> for AuthorGiven {
+	fn find_author<'a, I>(_digests: I) -> Option<u64>
+	where
+		I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+	{
+		BlockAuthor::get()
+	}
+}
+impl pallet_authorship::Config for Test {
+	type FindAuthor = AuthorGiven;
+	type EventHandler = ();
+}
+
+impl pallet_transaction_payment::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type OnChargeTransaction =
+		pallet_transaction_payment::CurrencyAdapter<Balances, DealWithFees<Test>>;
+	type OperationalFeeMultiplier = ConstU8<5>;
+	type WeightToFee = IdentityFee<u64>;
+	type LengthToFee = IdentityFee<u64>;
+	type FeeMultiplierUpdate = ();
+}
+
+parameter_types! {
+	pub static TokenExistentialDeposit: u64 = 1;
+}
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: u32| -> u64 {
+		TokenExistentialDeposit::get()
+	};
+}
+impl orml_tokens::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = u64;
+	type Amount = i64;
+	type CurrencyId = u32;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type CurrencyHooks = ();
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type DustRemovalWhitelist = Everything;
+}
+
+parameter_types! {
+	pub const GetNativeCurrencyId: u32 = NATIVE;
+	pub const TreasuryAccount: u64 = TREASURY;
+}
+impl orml_currencies::Config for Test {
+	type MultiCurrency = Tokens;
+	type NativeCurrency = orml_currencies::BasicCurrencyAdapter<Test, Balances, i64, u64>;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type WeightInfo = ();
+}
+
+/// DOT fees are twice the native fee, no other currency is accepted.
+pub struct DoubleForDot;
+impl FeeRate<u32, u64> for DoubleForDot {
+	fn convert(currency_id: u32, native_fee: u64) -> Option<u64> {
+		(currency_id == DOT).then(|| native_fee * 2)
+	}
+}
+
+parameter_type_with_key! {
+	pub DefaultFeeSplit: |source: FeeSource| -> FeeSplit {
+		match source {
+			FeeSource::TransactionFee =>
+				FeeSplit { treasury: Percent::from_percent(50), author: Percent::from_percent(30) },
+			FeeSource::Tip => FeeSplit { treasury: Percent::zero(), author: Percent::one() },
+			FeeSource::XcmRevenue =>
+				FeeSplit { treasury: Percent::from_percent(25), author: Percent::from_percent(25) },
+		}
+	};
+}
+impl module_tx_payment::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type CurrencyId = u32;
+	type MultiCurrency = Currencies;
+	type NativeCurrencyId = GetNativeCurrencyId;
+	type FeeRate = DoubleForDot;
+	type NativeCurrency = Balances;
+	type TreasuryAccount = TreasuryAccount;
+	type DefaultFeeSplit = DefaultFeeSplit;
+	type UpdateOrigin = EnsureRoot<u64>;
+	type WeightInfo = ();
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(ALICE, 10_000)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	orml_tokens::GenesisConfig::<Test> { balances: vec![(ALICE, DOT, 10_000)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+const CALL: &<Test as frame_system::Config>::RuntimeCall =
+	&RuntimeCall::Balances(pallet_balances::Call::transfer { dest: 2, value: 69 });
+
+fn info(weight: u64) -> DispatchInfo {
+	DispatchInfo {
+		weight: Weight::from_parts(weight, 0),
+		class: DispatchClass::Normal,
+		pays_fee: frame_support::dispatch::Pays::Yes,
+	}
+}
+
+fn post_info(actual_weight: u64) -> PostDispatchInfo {
+	PostDispatchInfo {
+		actual_weight: Some(Weight::from_parts(actual_weight, 0)),
+		pays_fee: frame_support::dispatch::Pays::Yes,
+	}
+}
+
+/// Charge `ALICE` for a transaction of length 10 and weight 100, of which only 50 were used.
+fn charge(currency_id: Option<u32>, tip: u64) {
+	let pre = ChargeCurrencyTxPayment::<Test>::from(tip, currency_id)
+		.pre_dispatch(&ALICE, CALL, &info(100), 10)
+		.unwrap();
+	assert_ok!(ChargeCurrencyTxPayment::<Test>::post_dispatch(
+		Some(pre),
+		&info(100),
+		&post_info(50),
+		10,
+		&Ok(())
+	));
+}
+
+#[test]
+fn fee_split_shares_never_exceed_amount() {
+	for treasury in 0..=100u8 {
+		for author in 0..=(100 - treasury) {
+			let split = FeeSplit {
+				treasury: Percent::from_percent(treasury),
+				author: Percent::from_percent(author),
+			};
+			assert!(split.is_valid());
+			for amount in [0u64, 1, 7, 99, 100, 12_345, u64::MAX] {
+				let (to_treasury, to_author) = split.shares(amount);
+				assert!(to_treasury.saturating_add(to_author) <= amount);
+			}
+		}
+	}
+	assert!(!FeeSplit { treasury: Percent::from_percent(60), author: Percent::from_percent(41) }
+		.is_valid());
+}
+
+#[test]
+fn native_fee_and_tip_are_split() {
+	new_test_ext().execute_with(|| {
+		let issuance = Balances::total_issuance();
+
+		charge(None, 40);
+
+		// length 10 + weight 50 + tip 40
+		let paid = 10_000 - Balances::free_balance(ALICE);
+		assert_eq!(paid, 100);
+		assert_eq!(Balances::free_balance(TREASURY), 30);
+		assert_eq!(Balances::free_balance(AUTHOR), 18 + 40);
+		let burned = issuance - Balances::total_issuance();
+		assert_eq!(burned, 12);
+		assert_eq!(
+			Balances::free_balance(TREASURY) + Balances::free_balance(AUTHOR) + burned,
+			paid
+		);
+	});
+}
+
+#[test]
+fn currency_fee_and_tip_are_split() {
+	new_test_ext().execute_with(|| {
+		let issuance = Tokens::total_issuance(DOT);
+
+		charge(Some(DOT), 40);
+
+		// twice length 10 + weight 50 + tip 40
+		let paid = 10_000 - Tokens::free_balance(DOT, &ALICE);
+		assert_eq!(paid, 200);
+		assert_eq!(Tokens::free_balance(DOT, &TREASURY), 60);
+		assert_eq!(Tokens::free_balance(DOT, &AUTHOR), 36 + 80);
+		let burned = issuance - Tokens::total_issuance(DOT);
+		assert_eq!(burned, 24);
+		assert_eq!(
+			Tokens::free_balance(DOT, &TREASURY) + Tokens::free_balance(DOT, &AUTHOR) + burned,
+			paid
+		);
+		// nothing is taken from the native currency
+		assert_eq!(Balances::free_balance(ALICE), 10_000);
+	});
+}
+
+#[test]
+fn currency_fee_is_withdrawn_and_refunded() {
+	new_test_ext().execute_with(|| {
+		// twice length 10 + weight 100 + tip 40
+		let pre = ChargeCurrencyTxPayment::<Test>::from(40, Some(DOT))
+			.pre_dispatch(&ALICE, CALL, &info(100), 10)
+			.unwrap();
+		assert!(matches!(pre.2, InitialPayment::Currency(DOT, 300)));
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 10_000 - 300);
+
+		// only weight 50 was used
+		assert_ok!(ChargeCurrencyTxPayment::<Test>::post_dispatch(
+			Some(pre),
+			&info(100),
+			&post_info(50),
+			10,
+			&Ok(())
+		));
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 10_000 - 200);
+		System::assert_last_event(
+			Event::CurrencyTxFeePaid { who: ALICE, currency_id: DOT, actual_fee: 200, tip: 80 }
+				.into(),
+		);
+	});
+}
+
+#[test]
+fn native_currency_id_pays_in_native_currency() {
+	new_test_ext().execute_with(|| {
+		let pre = ChargeCurrencyTxPayment::<Test>::from(0, Some(NATIVE))
+			.pre_dispatch(&ALICE, CALL, &info(100), 10)
+			.unwrap();
+		assert!(matches!(pre.2, InitialPayment::Native(Some(_))));
+		assert_eq!(Balances::free_balance(ALICE), 10_000 - 110);
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 10_000);
+	});
+}
+
+#[test]
+fn currency_fee_must_be_accepted_and_affordable() {
+	new_test_ext().execute_with(|| {
+		let payment_error: TransactionValidityError = InvalidTransaction::Payment.into();
+		let pre_dispatch = |currency_id| {
+			ChargeCurrencyTxPayment::<Test>::from(0, Some(currency_id))
+				.pre_dispatch(&ALICE, CALL, &info(100), 10)
+				.map(|_| ())
+		};
+
+		assert_eq!(pre_dispatch(2), Err(payment_error));
+		assert_eq!(
+			ChargeCurrencyTxPayment::<Test>::from(0, Some(2))
+				.validate(&ALICE, CALL, &info(100), 10)
+				.map(|_| ()),
+			Err(payment_error)
+		);
+
+		// the fee of 220 would leave nothing, the account is kept alive
+		assert_ok!(Tokens::withdraw(DOT, &ALICE, 10_000 - 220));
+		assert_eq!(pre_dispatch(DOT), Err(payment_error));
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 220);
+
+		assert_ok!(Tokens::deposit(DOT, &ALICE, 1));
+		assert_ok!(pre_dispatch(DOT));
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 1);
+	});
+}
+
+#[test]
+fn author_share_goes_to_treasury_without_author() {
+	new_test_ext().execute_with(|| {
+		BlockAuthor::set(None);
+		let issuance = Balances::total_issuance();
+
+		charge(None, 40);
+
+		assert_eq!(Balances::free_balance(AUTHOR), 0);
+		assert_eq!(Balances::free_balance(TREASURY), 30 + 18 + 40);
+		assert_eq!(issuance - Balances::total_issuance(), 12);
+	});
+}
+
+#[test]
+fn native_imbalances_are_split() {
+	new_test_ext().execute_with(|| {
+		let issuance = Balances::total_issuance();
+		let withdraw = |amount| {
+			Balances::withdraw(
+				&ALICE,
+				amount,
+				WithdrawReasons::FEE,
+				ExistenceRequirement::KeepAlive,
+			)
+			.unwrap()
+		};
+
+		DealWithFees::<Test>::on_unbalanceds(vec![withdraw(1_000), withdraw(10)].into_iter());
+
+		assert_eq!(Balances::free_balance(TREASURY), 500);
+		assert_eq!(Balances::free_balance(AUTHOR), 300 + 10);
+		assert_eq!(issuance - Balances::total_issuance(), 200);
+	});
+}
+
+#[test]
+fn xcm_revenue_is_split() {
+	new_test_ext().execute_with(|| {
+		let issuance = Tokens::total_issuance(DOT);
+
+		TxPayment::distribute(FeeSource::XcmRevenue, DOT, 1_000);
+
+		assert_eq!(Tokens::free_balance(DOT, &TREASURY), 250);
+		assert_eq!(Tokens::free_balance(DOT, &AUTHOR), 250);
+		// revenue is already out of circulation, only the shares are deposited
+		assert_eq!(Tokens::total_issuance(DOT) - issuance, 500);
+		System::assert_last_event(
+			Event::FeeDistributed {
+				source: FeeSource::XcmRevenue,
+				currency_id: DOT,
+				to_treasury: 250,
+				to_author: 250,
+				burned: 500,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn shares_below_the_existential_deposit_are_redirected() {
+	new_test_ext().execute_with(|| {
+		let issuance = Tokens::total_issuance(DOT);
+
+		// neither share can open an account, both are burned
+		TokenExistentialDeposit::set(600);
+		TxPayment::distribute(FeeSource::XcmRevenue, DOT, 1_000);
+		assert_eq!(Tokens::total_issuance(DOT), issuance);
+		System::assert_last_event(
+			Event::FeeDistributed {
+				source: FeeSource::XcmRevenue,
+				currency_id: DOT,
+				to_treasury: 0,
+				to_author: 0,
+				burned: 1_000,
+			}
+			.into(),
+		);
+
+		// the author's share goes to the treasury, which can then open an account
+		TokenExistentialDeposit::set(300);
+		TxPayment::distribute(FeeSource::XcmRevenue, DOT, 1_000);
+		assert_eq!(Tokens::free_balance(DOT, &TREASURY), 500);
+		assert_eq!(Tokens::free_balance(DOT, &AUTHOR), 0);
+		System::assert_last_event(
+			Event::FeeDistributed {
+				source: FeeSource::XcmRevenue,
+				currency_id: DOT,
+				to_treasury: 500,
+				to_author: 0,
+				burned: 500,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn set_fee_split_works() {
+	new_test_ext().execute_with(|| {
+		let split =
+			FeeSplit { treasury: Percent::from_percent(70), author: Percent::from_percent(30) };
+		assert_noop!(
+			TxPayment::set_fee_split(
+				RuntimeOrigin::signed(ALICE),
+				FeeSource::TransactionFee,
+				split
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			TxPayment::set_fee_split(
+				RuntimeOrigin::root(),
+				FeeSource::TransactionFee,
+				FeeSplit { treasury: Percent::from_percent(70), author: Percent::from_percent(31) }
+			),
+			Error::<Test>::InvalidFeeSplit
+		);
+
+		assert_ok!(TxPayment::set_fee_split(
+			RuntimeOrigin::root(),
+			FeeSource::TransactionFee,
+			split
+		));
+		assert_eq!(TxPayment::fee_split(FeeSource::TransactionFee), split);
+		assert_eq!(split.burn(), Percent::zero());
+		System::assert_last_event(
+			Event::FeeSplitSet { source: FeeSource::TransactionFee, split }.into(),
+		);
+
+		charge(None, 0);
+		assert_eq!(Balances::free_balance(TREASURY), 42);
+		assert_eq!(Balances::free_balance(AUTHOR), 18);
+	});
+}
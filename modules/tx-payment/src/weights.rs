@@ -0,0 +1,33 @@
+//! Weights for module_tx_payment
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for module_tx_payment.
+pub trait WeightInfo {
+	fn set_fee_split() -> Weight;
+}
+
+/// Weights for module_tx_payment using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: TxPayment FeeSplits (r:0 w:1)
+	// Estimated, not benchmarked yet.
+	fn set_fee_split() -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Estimated, not benchmarked yet.
+	fn set_fee_split() -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+}